@@ -6,6 +6,8 @@ PROJECT_TUNNEL_HOST="${PROJECT_TUNNEL_HOST:-}"
 PROJECT_TUNNEL_USER="${PROJECT_TUNNEL_USER:-}"
 PROJECT_TUNNEL_PORT="${PROJECT_TUNNEL_PORT:-22}"
 PROJECT_TUNNEL_KEY="${PROJECT_TUNNEL_KEY:-$HOME/.ssh/id_ed25519_hetzner}"
+# Clés d'hôte épinglées depuis le devcenter
+KNOWN_HOSTS="${GMDEV_KNOWN_HOSTS:-$HOME/.gestionmax-devcenter/known_hosts}"
 
 LOCAL_MONGO="${PROJECT_LOCAL_MONGO:-27017}"
 REMOTE_MONGO="${PROJECT_REMOTE_MONGO:-27017}"
@@ -25,8 +27,9 @@ echo "🔐 Tunnel SSH: localhost:$LOCAL_MONGO → $PROJECT_TUNNEL_HOST:$REMOTE_M
 ssh \
     -i "$PROJECT_TUNNEL_KEY" \
     -p "$PROJECT_TUNNEL_PORT" \
-    -o StrictHostKeyChecking=no \
-    -o UserKnownHostsFile=/dev/null \
+    -o StrictHostKeyChecking=yes \
+    -o UserKnownHostsFile="$KNOWN_HOSTS" \
+    -o GlobalKnownHostsFile=/dev/null \
     -o ServerAliveInterval=60 \
     -o ServerAliveCountMax=3 \
     -N \
@@ -2,35 +2,23 @@
 #![allow(non_snake_case)]
 
 use chrono::Utc;
-use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::process::Command;
-
-/// Liste des dossiers à ignorer lors du scan
-const IGNORE_DIRS: &[&str] = &[
-    "_archive",
-    "archive",
-    "docs",
-    "documentation",
-    "frontend-backup",
-    "backend-backup",
-    ".git",
-    ".vscode",
-    ".idea",
-    "__tests__",
-    "dist",
-    "node_modules",
-];
 
-/// Vérifie si un dossier doit être ignoré
-fn should_ignore_dir(dir_name: &str) -> bool {
-    IGNORE_DIRS
-        .iter()
-        .any(|&ignore| dir_name == ignore || dir_name.starts_with(ignore))
-}
+use crate::compose::{self, ComposeConfig, ComposeFile};
+use crate::detectors::{Detection, DetectorRegistry, ServiceRole, MIN_CONFIDENCE};
+use crate::git_branches::WorktreeVariant;
+use crate::git_url::{origin_remote, GitRemote};
+use crate::ignore_rules::IgnoreRules;
+use crate::manifest::{self, ManifestService};
+use crate::pairing::{best_pairs, rank_pairings, RepoPair, DEFAULT_SCAN_DEPTH};
+use crate::ports::{detect_service_port, PortDetection};
+use crate::projects_v3::project_id_from_name;
+use crate::scan_report::ScanTrace;
+use crate::ssh_keys;
+use crate::workspace::{self, Workspace};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ScanScript {
@@ -58,6 +46,20 @@ pub struct ProjectScanScripts {
 pub struct Ports {
     pub backend: u16,
     pub frontend: u16,
+    // Ports publiés par les services compose (mongo, postgres...), par nom de service
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub services: BTreeMap<String, u16>,
+}
+
+/// Ports par défaut : backend Payload 3010, frontend Next.js 3000
+impl Default for Ports {
+    fn default() -> Self {
+        Self {
+            backend: 3010,
+            frontend: 3000,
+            services: BTreeMap::new(),
+        }
+    }
 }
 
 #[allow(dead_code)]
@@ -71,6 +73,7 @@ pub struct ProjectEnvironment {
 
 #[allow(dead_code, non_snake_case)]
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(from = "TunnelConfig")]
 pub struct Tunnel {
     pub enabled: bool,
     pub host: String,
@@ -78,14 +81,170 @@ pub struct Tunnel {
     pub port: u16,
     #[serde(rename = "privateKey")]
     pub private_key: String,
+    // Alias d'un bloc `Host` de ~/.ssh/config, utilisé à la place de host/user/port/clé
+    #[serde(rename = "sshAlias", skip_serializing_if = "Option::is_none")]
+    pub ssh_alias: Option<String>,
+    // Rebonds successifs (`ProxyJump`), ex: "deploy@bastion:2222"
+    #[serde(rename = "proxyJump", skip_serializing_if = "Vec::is_empty")]
+    pub proxy_jump: Vec<String>,
+    pub forwards: Vec<PortForward>,
+}
+
+/// Forme lue de la configuration : les anciens champs `localMongo`/`remoteMongo`
+/// sont migrés en un transfert `mongo`
+#[derive(Deserialize)]
+struct TunnelConfig {
+    #[serde(default)]
+    enabled: bool,
+    #[serde(default)]
+    host: String,
+    #[serde(default)]
+    user: String,
+    #[serde(default)]
+    port: u16,
+    #[serde(default, rename = "privateKey")]
+    private_key: String,
+    #[serde(default, rename = "sshAlias")]
+    ssh_alias: Option<String>,
+    #[serde(default, rename = "proxyJump")]
+    proxy_jump: Vec<String>,
+    #[serde(default)]
+    forwards: Vec<PortForward>,
     #[serde(rename = "localMongo")]
-    pub local_mongo: u16,
+    local_mongo: Option<u16>,
     #[serde(rename = "remoteMongo")]
-    pub remote_mongo: u16,
+    remote_mongo: Option<u16>,
+}
+
+impl From<TunnelConfig> for Tunnel {
+    fn from(config: TunnelConfig) -> Self {
+        let mut forwards = config.forwards;
+        if forwards.is_empty() {
+            if let Some(local) = config.local_mongo {
+                forwards.push(PortForward::local("mongo", local, config.remote_mongo.unwrap_or(27017)));
+            }
+        }
+        Tunnel {
+            enabled: config.enabled,
+            host: config.host,
+            user: config.user,
+            port: config.port,
+            private_key: config.private_key,
+            ssh_alias: config.ssh_alias.filter(|alias| !alias.trim().is_empty()),
+            proxy_jump: config.proxy_jump,
+            forwards,
+        }
+    }
+}
+
+impl Tunnel {
+    /// Transfert MongoDB historique (nommé `mongo`, sinon le premier)
+    pub fn mongo_forward(&self) -> Option<&PortForward> {
+        self.forwards
+            .iter()
+            .find(|f| f.name == "mongo")
+            .or_else(|| self.forwards.first())
+    }
+}
+
+/// Sens d'un transfert de port ssh
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ForwardKind {
+    /// `-L` : port local vers un hôte joignable depuis le serveur
+    #[default]
+    Local,
+    /// `-R` : port du serveur vers un hôte joignable depuis cette machine
+    Remote,
+    /// `-D` : proxy SOCKS sur le port local
+    Dynamic,
+}
+
+/// Vérification qu'un transfert est prêt
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ForwardCheck {
+    /// Une connexion locale reste ouverte : le canal distant a pu s'ouvrir
+    #[default]
+    Forward,
+    /// Le port local accepte les connexions (proxy SOCKS, services qui ferment vite)
+    Listen,
+    /// Aucune vérification
+    None,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PortForward {
+    // Nom affiché ("mongo", "postgres", "redis"...)
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub kind: ForwardKind,
+    // Adresse d'écoute locale (local, dynamic) ou de destination sur cette machine (remote)
+    #[serde(default = "default_forward_address", alias = "bind_address")]
+    pub bind_address: String,
+    #[serde(alias = "local_port")]
+    pub local_port: u16,
+    // Destination vue depuis le serveur (local) ou adresse d'écoute sur le serveur (remote)
+    #[serde(default = "default_forward_address", alias = "remote_host")]
+    pub remote_host: String,
+    // Ignoré pour un transfert dynamic
+    #[serde(default, alias = "remote_port")]
+    pub remote_port: u16,
+    #[serde(default)]
+    pub check: ForwardCheck,
+}
+
+fn default_forward_address() -> String {
+    "127.0.0.1".to_string()
+}
+
+impl PortForward {
+    pub fn local(name: &str, local_port: u16, remote_port: u16) -> Self {
+        PortForward {
+            name: name.to_string(),
+            kind: ForwardKind::Local,
+            bind_address: default_forward_address(),
+            local_port,
+            remote_host: default_forward_address(),
+            remote_port,
+            check: ForwardCheck::default(),
+        }
+    }
+
+    /// Option ssh du transfert (`-L 127.0.0.1:27017:127.0.0.1:27017`)
+    pub fn ssh_args(&self) -> [String; 2] {
+        match self.kind {
+            ForwardKind::Local => [
+                "-L".to_string(),
+                format!(
+                    "{}:{}:{}:{}",
+                    self.bind_address, self.local_port, self.remote_host, self.remote_port
+                ),
+            ],
+            ForwardKind::Remote => [
+                "-R".to_string(),
+                format!(
+                    "{}:{}:{}:{}",
+                    self.remote_host, self.remote_port, self.bind_address, self.local_port
+                ),
+            ],
+            ForwardKind::Dynamic => [
+                "-D".to_string(),
+                format!("{}:{}", self.bind_address, self.local_port),
+            ],
+        }
+    }
+
+    /// Port ouvert sur cette machine, `None` pour un transfert remote
+    pub fn listen_port(&self) -> Option<u16> {
+        (self.kind != ForwardKind::Remote).then_some(self.local_port)
+    }
 }
 
 #[allow(dead_code)]
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct ProjectCommands {
     pub backend: Option<String>,
     pub frontend: Option<String>,
@@ -94,15 +253,77 @@ pub struct ProjectCommands {
 }
 
 #[allow(dead_code, non_snake_case)]
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct ProjectServiceConfig {
     pub start: Option<String>,
     pub stop: Option<String>,
     pub port: Option<u16>,
+    #[serde(rename = "envFiles", alias = "env_files")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub env_files: Vec<String>,
+    #[serde(rename = "healthCheck", alias = "health_check")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub health_check: Option<HealthCheck>,
+    // Variables propres au service, prioritaires sur l'environnement du projet et du profil
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+}
+
+/// App exécutable d'un monorepo, proposée comme service du projet
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AppService {
+    pub path: String,
+    pub port: u16,
+    #[serde(rename = "devCommand")]
+    pub dev_command: String,
+    /// Paquets du workspace à construire avant de lancer l'app
+    #[serde(rename = "dependsOn")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+}
+
+/// Vérification de santé d'un service (URL absolue ou chemin relatif au port du service)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct HealthCheck {
+    pub url: String,
+    #[serde(rename = "intervalSecs", alias = "interval_secs")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interval_secs: Option<u64>,
+    #[serde(rename = "timeoutSecs", alias = "timeout_secs")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+}
+
+/// Surcharges locales appliquées par-dessus le manifeste du dépôt
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ProjectOverrides {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ports: Option<PortsOverride>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commands: Option<ProjectCommands>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environment: Option<ProjectEnvironment>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tunnel: Option<Tunnel>,
+}
+
+/// Profil d'environnement nommé (local, staging, prod-readonly...)
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ProjectProfile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(flatten)]
+    pub overrides: ProjectOverrides,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct PortsOverride {
+    pub backend: Option<u16>,
+    pub frontend: Option<u16>,
 }
 
 #[allow(dead_code, non_snake_case)]
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct ProjectV3 {
     pub id: String,
     pub name: String,
@@ -142,6 +363,61 @@ pub struct ProjectV3 {
     // Activation du projet (un seul projet peut être actif à la fois)
     #[serde(default)]
     pub enabled: bool,
+
+    // Manifeste versionné (.gmdev.toml / gmdev.json) dont le projet est issu
+    #[serde(rename = "manifestPath")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub manifest_path: Option<String>,
+
+    // Erreur de lecture du manifeste : le projet enregistré est conservé tel quel
+    #[serde(rename = "manifestError")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub manifest_error: Option<String>,
+
+    // Surcharges locales, prioritaires sur le manifeste
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub overrides: Option<ProjectOverrides>,
+
+    // Profils d'environnement et profil actif (aucun = configuration de base)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, ProjectProfile>,
+    #[serde(rename = "activeProfile")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_profile: Option<String>,
+
+    // Apps et paquets partagés d'un monorepo
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<Workspace>,
+
+    // Un service par app exécutable du monorepo, par nom d'app
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub apps: BTreeMap<String, AppService>,
+
+    // Services docker compose lancés avec le projet
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compose: Option<ComposeConfig>,
+
+    // Variante temporaire d'un autre projet, dans un worktree git
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub worktree: Option<WorktreeVariant>,
+
+    // Remote `origin` du dépôt (lien vers GitHub, GitLab...)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote: Option<GitRemote>,
+}
+
+/// Projet de test : chemins sous `/tmp/<id>`, ports par défaut, activé
+#[cfg(test)]
+pub(crate) fn test_project(id: &str) -> ProjectV3 {
+    ProjectV3 {
+        id: id.to_string(),
+        name: id.to_string(),
+        root_path: format!("/tmp/{}", id),
+        backend_path: format!("/tmp/{}/backend", id),
+        frontend_path: format!("/tmp/{}/frontend", id),
+        enabled: true,
+        ..Default::default()
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -149,145 +425,390 @@ pub struct ProjectScanResult {
     pub name: String,
     pub backend_path: Option<String>,
     pub backend_port: Option<u16>,
+    /// Provenance du port ("manifeste", ".env.local ligne 4"...), `None` si non trouvé
+    pub backend_port_source: Option<String>,
     pub backend_start: Option<String>,
     pub backend_stop: Option<String>,
     pub frontend_path: Option<String>,
     pub frontend_port: Option<u16>,
+    pub frontend_port_source: Option<String>,
     pub frontend_start: Option<String>,
     pub frontend_stop: Option<String>,
     pub scripts_path: Option<String>,
     pub scripts: ProjectScanScripts,
+    pub manifest_path: Option<String>,
+    /// Stacks détectées (confiance, commande de dev, sources de port, healthcheck)
+    pub backend_stack: Option<Detection>,
+    pub frontend_stack: Option<Detection>,
+    /// Monorepo : une app par service proposé
+    pub workspace: Option<Workspace>,
+    /// Services docker compose (bases de données, stockage...)
+    pub compose: Option<ComposeFile>,
     pub warnings: Vec<String>,
 }
 
-/// Ouvre un dialogue pour choisir un dossier de projet
-#[tauri::command]
-pub async fn pick_project_folder() -> Result<String, String> {
-    // Utiliser zenity ou un autre dialogue système pour choisir un dossier
-    let output = Command::new("zenity")
-        .arg("--file-selection")
-        .arg("--directory")
-        .arg("--title=Choisir le dossier du projet")
-        .output()
-        .map_err(|e| {
-            format!(
-                "Failed to open file picker: {}. Install zenity: sudo apt install zenity",
-                e
-            )
-        })?;
-
-    if !output.status.success() {
-        return Err("File picker cancelled".to_string());
-    }
-
-    let path =
-        String::from_utf8(output.stdout).map_err(|e| format!("Failed to parse path: {}", e))?;
-
-    Ok(path.trim().to_string())
-}
-
 /// Détecte automatiquement la structure d'un projet (AutoScan v2 - Monorepo)
+///
+/// Si le dépôt contient un manifeste (`.gmdev.toml` ou `gmdev.json`), ses
+/// déclarations l'emportent sur les heuristiques de détection.
 #[tauri::command]
 pub async fn autoscan_project(root_path: String) -> Result<ProjectScanResult, String> {
-    let root = PathBuf::from(&root_path);
+    scan_project(Path::new(&root_path), &mut ScanTrace::default())
+}
+
+/// Scan d'un projet ; chaque décision est consignée dans `trace` (voir `scan_report`)
+pub(crate) fn scan_project(
+    root: &Path,
+    trace: &mut ScanTrace,
+) -> Result<ProjectScanResult, String> {
+    let root = root.to_path_buf();
 
     if !root.exists() {
-        return Err(format!("Path does not exist: {}", root_path));
+        return Err(format!("Path does not exist: {}", root.display()));
     }
 
     let mut warnings = Vec::new();
 
-    let project_name = format_project_name(
-        root.file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("Projet"),
-    );
+    let manifest = manifest::load_manifest(&root)?;
+    let manifest_service = |name: &str| manifest.as_ref().and_then(|(_, m)| m.services.get(name));
+    let manifest_file = manifest
+        .as_ref()
+        .map(|(path, _)| path.to_string_lossy().to_string());
+    // Service déclaré par le manifeste, avec le chemin du manifeste pour la provenance
+    let declared = |name: &str| manifest_service(name).zip(manifest_file.as_deref());
+    let workspace = workspace::detect_workspace(&root)?;
+    let rules = IgnoreRules::for_root(&root);
+
+    let project_name = match manifest.as_ref().and_then(|(_, m)| m.name.clone()) {
+        Some(name) => {
+            trace.record("name", &name, manifest_file.clone(), "manifest", 1.0);
+            name
+        }
+        None => {
+            let name = format_project_name(
+                root.file_name()
+                    .and_then(|n| n.to_str())
+                    .unwrap_or("Projet"),
+            );
+            trace.record("name", &name, None, "folder-name", 0.5);
+            name
+        }
+    };
+    if let Some(path) = &manifest_file {
+        trace.record("manifestPath", path, Some(path.clone()), "manifest", 1.0);
+    }
 
-    let backend_path = detect_backend_v2(&root, 0)?;
+    let registry = DetectorRegistry::with_defaults();
+    let (backend_path, backend_stack) = locate_service(
+        &root,
+        ServiceRole::Backend,
+        declared("backend"),
+        workspace.as_ref(),
+        &registry,
+        &rules,
+        trace,
+    )?;
     if backend_path.is_none() {
-        warnings.push("Backend Payload non détecté".to_string());
+        warnings.push("Backend non détecté".to_string());
     }
-    let backend_port = match backend_path.as_ref() {
-        Some(path) => detect_backend_port(Path::new(path))?.or(Some(3010)), // Port par défaut GestionMax: 3010
-        None => None,
-    };
+    let (backend_port, backend_port_source) = service_port(
+        ServiceRole::Backend,
+        declared("backend"),
+        backend_path.as_deref(),
+        trace,
+        &mut warnings,
+    );
 
-    let frontend_path = detect_frontend_v2(&root, 0)?;
+    let (frontend_path, frontend_stack) = locate_service(
+        &root,
+        ServiceRole::Frontend,
+        declared("frontend"),
+        workspace.as_ref(),
+        &registry,
+        &rules,
+        trace,
+    )?;
     if frontend_path.is_none() {
-        warnings.push("Frontend Next.js non détecté".to_string());
+        warnings.push("Frontend non détecté".to_string());
     }
-    let frontend_port = match frontend_path.as_ref() {
-        Some(path) => detect_frontend_port(Path::new(path))?.or(Some(3000)), // Port par défaut GestionMax: 3000
-        None => None,
-    };
+    let (frontend_port, frontend_port_source) = service_port(
+        ServiceRole::Frontend,
+        declared("frontend"),
+        frontend_path.as_deref(),
+        trace,
+        &mut warnings,
+    );
 
-    let scripts_detection = detect_scripts_info(&root)?;
+    let scripts_detection = detect_scripts_info(&root, &rules, trace)?;
     if scripts_detection.path.is_none() {
         warnings.push("Dossier scripts non détecté (scripts/)".to_string());
     }
     warnings.extend(scripts_detection.warnings.clone());
 
-    let backend_start = scripts_detection
-        .scripts
-        .backend
-        .as_ref()
-        .map(|s| s.start.clone());
-    let backend_stop = scripts_detection
-        .scripts
-        .backend
-        .as_ref()
-        .and_then(|s| s.stop.clone());
-    let frontend_start = scripts_detection
-        .scripts
-        .frontend
-        .as_ref()
-        .map(|s| s.start.clone());
-    let frontend_stop = scripts_detection
-        .scripts
-        .frontend
-        .as_ref()
-        .and_then(|s| s.stop.clone());
+    let backend_start = service_command(
+        ServiceRole::Backend,
+        declared("backend"),
+        &scripts_detection,
+        backend_stack.as_ref(),
+        trace,
+    );
+    let backend_stop = manifest_service("backend")
+        .and_then(|s| s.stop.clone())
+        .or_else(|| {
+            scripts_detection
+                .scripts
+                .backend
+                .as_ref()
+                .and_then(|s| s.stop.clone())
+        });
+    let frontend_start = service_command(
+        ServiceRole::Frontend,
+        declared("frontend"),
+        &scripts_detection,
+        frontend_stack.as_ref(),
+        trace,
+    );
+    let frontend_stop = manifest_service("frontend")
+        .and_then(|s| s.stop.clone())
+        .or_else(|| {
+            scripts_detection
+                .scripts
+                .frontend
+                .as_ref()
+                .and_then(|s| s.stop.clone())
+        });
+
+    let compose = compose::detect_compose(&root, trace, &mut warnings);
 
     Ok(ProjectScanResult {
         name: project_name,
         backend_path,
         backend_port,
+        backend_port_source,
         backend_start,
         backend_stop,
         frontend_path,
         frontend_port,
+        frontend_port_source,
         frontend_start,
         frontend_stop,
         scripts_path: scripts_detection.path,
         scripts: scripts_detection.scripts,
+        manifest_path: manifest_file,
+        backend_stack,
+        frontend_stack,
+        workspace,
+        compose,
         warnings,
     })
 }
 
-/// Détecte le dossier backend Payload (v2 - avec ignore list et recherche récursive)
-fn detect_backend_v2(root: &Path, depth: u32) -> Result<Option<String>, String> {
-    // Limiter à 2 niveaux de profondeur
-    if depth > 2 {
-        return Ok(None);
+fn role_key(role: ServiceRole) -> &'static str {
+    match role {
+        ServiceRole::Backend => "backend",
+        ServiceRole::Frontend => "frontend",
     }
+}
 
-    // Vérifier si le root lui-même est un backend Payload
-    let package_json = root.join("package.json");
-    let payload_config = root.join("payload.config.ts");
-    let src_dir = root.join("src");
+/// Dossier et stack d'un service : manifeste, puis app de monorepo, puis heuristiques
+fn locate_service(
+    root: &Path,
+    role: ServiceRole,
+    declared: Option<(&ManifestService, &str)>,
+    workspace: Option<&Workspace>,
+    registry: &DetectorRegistry,
+    rules: &IgnoreRules,
+    trace: &mut ScanTrace,
+) -> Result<(Option<String>, Option<Detection>), String> {
+    let key = role_key(role);
+    let field = format!("{}Path", key);
+
+    let (path, rule, source, confidence) =
+        if let Some((path, file)) = declared.and_then(|(s, file)| Some((s.path.as_ref()?, file))) {
+            let path = root.join(path).to_string_lossy().to_string();
+            (
+                Some(path),
+                "manifest".to_string(),
+                Some(file.to_string()),
+                1.0,
+            )
+        } else if let Some(app) = workspace.and_then(|w| w.best_app(role)) {
+            for other in workspace
+                .iter()
+                .flat_map(|w| w.apps.iter())
+                .filter(|other| other.role == Some(role) && other.path != app.path)
+            {
+                trace.reject(
+                    &field,
+                    &other.path,
+                    "workspace",
+                    other.confidence,
+                    format!("Confiance inférieure à celle de {}", app.name),
+                );
+            }
+            let source = Path::new(&app.path).join("package.json");
+            (
+                Some(app.path.clone()),
+                "workspace".to_string(),
+                Some(source.to_string_lossy().to_string()),
+                app.confidence,
+            )
+        } else {
+            let path = match role {
+                ServiceRole::Backend => detect_backend_v2(root, 0, registry, rules, trace)?,
+                ServiceRole::Frontend => detect_frontend_v2(root, 0, registry, rules, trace)?,
+            };
+            (path, "directory-scan".to_string(), None, 0.3)
+        };
+
+    let Some(path) = path else {
+        return Ok((None, None));
+    };
 
-    if package_json.exists() && (payload_config.exists() || src_dir.exists()) {
-        // Vérifier si c'est vraiment Payload
-        if payload_config.exists() {
-            return Ok(Some(root.to_string_lossy().to_string()));
+    // Détections triées par confiance ; à égalité, l'ordre d'enregistrement l'emporte
+    let stack_field = format!("{}Stack", key);
+    let mut stack: Option<Detection> = None;
+    for detection in registry
+        .detect(Path::new(&path))
+        .into_iter()
+        .filter(|d| d.role == role)
+    {
+        match &stack {
+            None if detection.confidence >= MIN_CONFIDENCE => stack = Some(detection),
+            None => trace.reject(
+                &stack_field,
+                &detection.stack,
+                "stack",
+                detection.confidence,
+                format!("Sous le seuil de confiance ({})", MIN_CONFIDENCE),
+            ),
+            Some(chosen) => trace.reject(
+                &stack_field,
+                &detection.stack,
+                "stack",
+                detection.confidence,
+                format!("Confiance inférieure à {}", chosen.label),
+            ),
         }
+    }
 
-        // Vérifier dans package.json
-        if let Ok(content) = fs::read_to_string(&package_json) {
-            if content.contains("payload") || content.contains("@payloadcms") {
-                return Ok(Some(root.to_string_lossy().to_string()));
-            }
+    // Sans manifeste ni monorepo, la confiance du dossier est celle de la stack détectée
+    let (rule, source, confidence) = match (&stack, rule.as_str()) {
+        (Some(d), "directory-scan") => (
+            format!("stack:{}", d.stack),
+            Some(path.clone()),
+            d.confidence,
+        ),
+        _ => (rule, source, confidence),
+    };
+    trace.record(&field, &path, source, rule, confidence);
+    if let Some(d) = &stack {
+        trace.record(
+            &stack_field,
+            &d.stack,
+            Some(path.clone()),
+            format!("stack:{}", d.stack),
+            d.confidence,
+        );
+    }
+
+    Ok((Some(path), stack))
+}
+
+/// Port d'un service et sa provenance ; avertit quand rien ne le définit
+fn service_port(
+    role: ServiceRole,
+    declared: Option<(&ManifestService, &str)>,
+    path: Option<&str>,
+    trace: &mut ScanTrace,
+    warnings: &mut Vec<String>,
+) -> (Option<u16>, Option<String>) {
+    let key = role_key(role);
+    let field = format!("ports.{}", key);
+
+    if let Some((port, file)) = declared.and_then(|(s, file)| Some((s.port?, file))) {
+        trace.record(&field, port, Some(file.to_string()), "manifest", 1.0);
+        return (Some(port), Some("manifeste".to_string()));
+    }
+    let Some(path) = path else {
+        return (None, None);
+    };
+
+    let detection = match role {
+        ServiceRole::Backend => detect_backend_port(Path::new(path)),
+        ServiceRole::Frontend => detect_frontend_port(Path::new(path)),
+    };
+    match detection {
+        Some(d) => {
+            let file = Path::new(path).join(&d.file).to_string_lossy().to_string();
+            trace.record(&field, d.port, Some(file), d.rule.clone(), d.confidence());
+            (Some(d.port), Some(d.source))
         }
+        None => {
+            warnings.push(format!(
+                "Port {} non trouvé (ni .env, ni script package.json, ni fichier de config)",
+                key
+            ));
+            (None, None)
+        }
+    }
+}
+
+/// Commande de démarrage : manifeste, puis scripts/, puis commande de dev de la stack
+fn service_command(
+    role: ServiceRole,
+    declared: Option<(&ManifestService, &str)>,
+    scripts_detection: &ScriptsDetection,
+    stack: Option<&Detection>,
+    trace: &mut ScanTrace,
+) -> Option<String> {
+    let field = format!("commands.{}", role_key(role));
+    let script = match role {
+        ServiceRole::Backend => scripts_detection.scripts.backend.as_ref(),
+        ServiceRole::Frontend => scripts_detection.scripts.frontend.as_ref(),
+    };
+
+    let (command, source, rule, confidence) = if let Some((command, file)) =
+        declared.and_then(|(s, file)| Some((s.command.clone()?, file)))
+    {
+        (command, Some(file.to_string()), "manifest".to_string(), 1.0)
+    } else if let Some(script) = script {
+        (
+            script.start.clone(),
+            scripts_detection.path.clone(),
+            "scripts-dir".to_string(),
+            0.9,
+        )
+    } else {
+        let stack = stack?;
+        (
+            stack.dev_command.clone(),
+            None,
+            format!("stack:{}", stack.stack),
+            stack.confidence,
+        )
+    };
+
+    trace.record(&field, &command, source, rule, confidence);
+    Some(command)
+}
+
+/// Détecte le dossier backend (v2 - avec ignore list et recherche récursive)
+fn detect_backend_v2(
+    root: &Path,
+    depth: u32,
+    registry: &DetectorRegistry,
+    rules: &IgnoreRules,
+    trace: &mut ScanTrace,
+) -> Result<Option<String>, String> {
+    // Limiter à 2 niveaux de profondeur
+    if depth > 2 {
+        return Ok(None);
+    }
+
+    // Vérifier si le root lui-même est un backend connu (Payload, Directus, Strapi...)
+    if registry.best(root, ServiceRole::Backend).is_some() {
+        return Ok(Some(root.to_string_lossy().to_string()));
     }
 
     // Rechercher récursivement dans les sous-dossiers
@@ -297,15 +818,14 @@ fn detect_backend_v2(root: &Path, depth: u32) -> Result<Option<String>, String>
                 let path = entry.path();
 
                 if path.is_dir() {
-                    let dir_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
-
-                    // Ignorer les dossiers parasites
-                    if should_ignore_dir(dir_name) {
+                    // Ignorer les dossiers parasites (.gitignore, .gmdevignore, réglages)
+                    if let Some(skipped) = rules.skip(&path) {
+                        trace.skip(skipped);
                         continue;
                     }
 
                     // Rechercher récursivement
-                    if let Ok(Some(backend_path)) = detect_backend_v2(&path, depth + 1) {
+                    if let Ok(Some(backend_path)) = detect_backend_v2(&path, depth + 1, registry, rules, trace) {
                         return Ok(Some(backend_path));
                     }
                 }
@@ -316,44 +836,22 @@ fn detect_backend_v2(root: &Path, depth: u32) -> Result<Option<String>, String>
     Ok(None)
 }
 
-/// Détecte le dossier frontend Next.js (v2 - avec ignore list et recherche récursive)
-fn detect_frontend_v2(root: &Path, depth: u32) -> Result<Option<String>, String> {
+/// Détecte le dossier frontend (v2 - avec ignore list et recherche récursive)
+fn detect_frontend_v2(
+    root: &Path,
+    depth: u32,
+    registry: &DetectorRegistry,
+    rules: &IgnoreRules,
+    trace: &mut ScanTrace,
+) -> Result<Option<String>, String> {
     // Limiter à 2 niveaux de profondeur
     if depth > 2 {
         return Ok(None);
     }
 
-    // Vérifier si le root lui-même est un frontend Next.js
-    let package_json = root.join("package.json");
-    let next_config = root.join("next.config.js");
-
-    if package_json.exists() {
-        if let Ok(content) = fs::read_to_string(&package_json) {
-            if content.contains("\"next\"") || content.contains("nextjs") {
-                // Ignorer les backups
-                let dir_name = root.file_name().and_then(|n| n.to_str()).unwrap_or("");
-
-                if !should_ignore_dir(dir_name) {
-                    return Ok(Some(root.to_string_lossy().to_string()));
-                }
-            }
-        }
-    }
-
-    // Vérifier next.config.js
-    if next_config.exists() {
-        let package_json = root.join("package.json");
-        if package_json.exists() {
-            if let Ok(content) = fs::read_to_string(&package_json) {
-                if content.contains("\"next\"") {
-                    let dir_name = root.file_name().and_then(|n| n.to_str()).unwrap_or("");
-
-                    if !should_ignore_dir(dir_name) {
-                        return Ok(Some(root.to_string_lossy().to_string()));
-                    }
-                }
-            }
-        }
+    // Vérifier si le root lui-même est un frontend connu (Next.js, Nuxt, Vite...)
+    if registry.best(root, ServiceRole::Frontend).is_some() {
+        return Ok(Some(root.to_string_lossy().to_string()));
     }
 
     // Rechercher récursivement dans les sous-dossiers
@@ -363,15 +861,14 @@ fn detect_frontend_v2(root: &Path, depth: u32) -> Result<Option<String>, String>
                 let path = entry.path();
 
                 if path.is_dir() {
-                    let dir_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
-
-                    // Ignorer les dossiers parasites
-                    if should_ignore_dir(dir_name) {
+                    // Ignorer les dossiers parasites (.gitignore, .gmdevignore, réglages)
+                    if let Some(skipped) = rules.skip(&path) {
+                        trace.skip(skipped);
                         continue;
                     }
 
                     // Rechercher récursivement
-                    if let Ok(Some(frontend_path)) = detect_frontend_v2(&path, depth + 1) {
+                    if let Ok(Some(frontend_path)) = detect_frontend_v2(&path, depth + 1, registry, rules, trace) {
                         return Ok(Some(frontend_path));
                     }
                 }
@@ -383,7 +880,12 @@ fn detect_frontend_v2(root: &Path, depth: u32) -> Result<Option<String>, String>
 }
 
 /// Détecte le dossier scripts (v2 - avec ignore list et recherche récursive)
-fn detect_scripts_v2(root: &Path, depth: u32) -> Result<Option<String>, String> {
+fn detect_scripts_v2(
+    root: &Path,
+    depth: u32,
+    rules: &IgnoreRules,
+    trace: &mut ScanTrace,
+) -> Result<Option<String>, String> {
     // Limiter à 2 niveaux de profondeur
     if depth > 2 {
         return Ok(None);
@@ -412,8 +914,9 @@ fn detect_scripts_v2(root: &Path, depth: u32) -> Result<Option<String>, String>
                 if path.is_dir() {
                     let dir_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
 
-                    // Ignorer les dossiers parasites
-                    if should_ignore_dir(dir_name) {
+                    // Ignorer les dossiers parasites (.gitignore, .gmdevignore, réglages)
+                    if let Some(skipped) = rules.skip(&path) {
+                        trace.skip(skipped);
                         continue;
                     }
 
@@ -428,7 +931,7 @@ fn detect_scripts_v2(root: &Path, depth: u32) -> Result<Option<String>, String>
                     }
 
                     // Rechercher récursivement
-                    if let Ok(Some(scripts_path)) = detect_scripts_v2(&path, depth + 1) {
+                    if let Ok(Some(scripts_path)) = detect_scripts_v2(&path, depth + 1, rules, trace) {
                         return Ok(Some(scripts_path));
                     }
                 }
@@ -486,8 +989,12 @@ const FRONTEND_START_CANDIDATES: &[&str] = &[
 ];
 const FRONTEND_STOP_CANDIDATES: &[&str] = &["frontend-off.sh", "stop-frontend.sh"];
 
-fn detect_scripts_info(root: &Path) -> Result<ScriptsDetection, String> {
-    let scripts_path = detect_scripts_v2(root, 0)?;
+fn detect_scripts_info(
+    root: &Path,
+    rules: &IgnoreRules,
+    trace: &mut ScanTrace,
+) -> Result<ScriptsDetection, String> {
+    let scripts_path = detect_scripts_v2(root, 0, rules, trace)?;
     let mut warnings = Vec::new();
 
     let tunnel = scripts_path.as_ref().and_then(|path| {
@@ -593,130 +1100,13 @@ fn find_script(dir: &Path, candidates: &[&str]) -> Option<String> {
 }
 
 /// Détecte le port du backend
-fn detect_backend_port(backend_path: &Path) -> Result<Option<u16>, String> {
-    // 1. Chercher dans .env.local (priorité)
-    let env_local = backend_path.join(".env.local");
-    if env_local.exists() {
-        if let Ok(content) = fs::read_to_string(&env_local) {
-            if let Some(port) =
-                extract_port_from_env(&content, vec!["PORT", "BACKEND_PORT", "PAYLOAD_PORT"])
-            {
-                return Ok(Some(port));
-            }
-        }
-    }
-    
-    // 2. Chercher dans .env
-    let env_file = backend_path.join(".env");
-    if env_file.exists() {
-        if let Ok(content) = fs::read_to_string(&env_file) {
-            if let Some(port) =
-                extract_port_from_env(&content, vec!["PORT", "BACKEND_PORT", "PAYLOAD_PORT"])
-            {
-                return Ok(Some(port));
-            }
-        }
-    }
-
-    // 3. Chercher dans payload.config.ts
-    let config_file = backend_path.join("payload.config.ts");
-    if config_file.exists() {
-        if let Ok(content) = fs::read_to_string(&config_file) {
-            if let Some(port) = extract_port_from_regex(&content, r"serverURL.*:(\d+)") {
-                return Ok(Some(port));
-            }
-        }
-    }
-
-    // 4. Chercher dans package.json
-    let package_json = backend_path.join("package.json");
-    if package_json.exists() {
-        if let Ok(content) = fs::read_to_string(&package_json) {
-            if let Some(port) =
-                extract_port_from_regex(&content, r#""dev":\s*"payload[^"]*--port\s+(\d+)"#)
-            {
-                return Ok(Some(port));
-            }
-        }
-    }
-
-    // Port par défaut GestionMax: 3010
-    Ok(Some(3010))
+fn detect_backend_port(backend_path: &Path) -> Option<PortDetection> {
+    detect_service_port(backend_path, ServiceRole::Backend, "payload")
 }
 
 /// Détecte le port du frontend
-fn detect_frontend_port(frontend_path: &Path) -> Result<Option<u16>, String> {
-    // 1. Chercher dans .env.local
-    let env_file = frontend_path.join(".env.local");
-    if env_file.exists() {
-        if let Ok(content) = fs::read_to_string(&env_file) {
-            if let Some(port) = extract_port_from_env(&content, vec!["PORT", "NEXT_PORT", "FRONTEND_PORT"]) {
-                return Ok(Some(port));
-            }
-        }
-    }
-    
-    // 2. Chercher dans .env
-    let env_file = frontend_path.join(".env");
-    if env_file.exists() {
-        if let Ok(content) = fs::read_to_string(&env_file) {
-            if let Some(port) = extract_port_from_env(&content, vec!["PORT", "NEXT_PORT", "FRONTEND_PORT"]) {
-                return Ok(Some(port));
-            }
-        }
-    }
-
-    // 3. Chercher dans next.config.js
-    let config_file = frontend_path.join("next.config.js");
-    if config_file.exists() {
-        if let Ok(content) = fs::read_to_string(&config_file) {
-            if let Some(port) = extract_port_from_regex(&content, r"port:\s*(\d+)") {
-                return Ok(Some(port));
-            }
-        }
-    }
-
-    // 4. Chercher dans package.json
-    let package_json = frontend_path.join("package.json");
-    if package_json.exists() {
-        if let Ok(content) = fs::read_to_string(&package_json) {
-            if let Some(port) =
-                extract_port_from_regex(&content, r#""dev":\s*"next dev[^"]*-p\s+(\d+)"#)
-            {
-                return Ok(Some(port));
-            }
-        }
-    }
-
-    // Port par défaut GestionMax: 3000
-    Ok(Some(3000))
-}
-
-/// Extrait un port depuis un fichier .env
-fn extract_port_from_env(content: &str, keys: Vec<&str>) -> Option<u16> {
-    for line in content.lines() {
-        for key in &keys {
-            if let Some(pos) = line.find(&format!("{}=", key)) {
-                let value = &line[pos + key.len() + 1..];
-                let value = value.trim().split('#').next().unwrap_or(value).trim();
-                if let Ok(port) = value.parse::<u16>() {
-                    return Some(port);
-                }
-            }
-        }
-    }
-    None
-}
-
-/// Extrait un port depuis une regex
-fn extract_port_from_regex(content: &str, pattern: &str) -> Option<u16> {
-    let re = Regex::new(pattern).ok()?;
-    if let Some(captures) = re.captures(content) {
-        if let Some(port_str) = captures.get(1) {
-            return port_str.as_str().parse::<u16>().ok();
-        }
-    }
-    None
+fn detect_frontend_port(frontend_path: &Path) -> Option<PortDetection> {
+    detect_service_port(frontend_path, ServiceRole::Frontend, "next")
 }
 
 fn format_project_name(name: &str) -> String {
@@ -741,12 +1131,34 @@ fn format_project_name(name: &str) -> String {
 #[allow(dead_code)]
 #[tauri::command]
 pub async fn autoscan_project_v3(root_path: String) -> Result<ProjectV3, String> {
-    let root = PathBuf::from(&root_path);
+    project_v3_from_root(Path::new(&root_path))
+}
+
+/// Projet V3 proposé pour un dossier, avec le remote `origin` du dépôt
+pub(crate) fn project_v3_from_root(root: &Path) -> Result<ProjectV3, String> {
+    let mut project = detect_project_v3(root)?;
+    project.remote = origin_remote(root);
+    Ok(project)
+}
+
+/// Manifeste, monorepo, puis dossiers conventionnels
+fn detect_project_v3(root: &Path) -> Result<ProjectV3, String> {
+    let root = root.to_path_buf();
+    let root_path = root.to_string_lossy().to_string();
 
     if !root.exists() {
         return Err("Root path does not exist".into());
     }
 
+    if let Some((manifest_path, m)) = manifest::load_manifest(&root)? {
+        return manifest::project_from_manifest(&root, &manifest_path, &m);
+    }
+
+    // Monorepo : les apps du workspace remplacent les dossiers conventionnels
+    if let Some(ws) = workspace::detect_workspace(&root)?.filter(|w| !w.apps.is_empty()) {
+        return Ok(workspace::project_from_workspace(&root, ws));
+    }
+
     let name = root
         .file_name()
         .and_then(|n| n.to_str())
@@ -774,8 +1186,11 @@ pub async fn autoscan_project_v3(root_path: String) -> Result<ProjectV3, String>
         w
     };
 
+    let compose = compose::find_compose_file(&root)
+        .and_then(|path| compose::read_compose_file(&path).ok());
+
     Ok(ProjectV3 {
-        id: name.clone(),
+        id: project_id_from_name(&name),
         name,
         root_path,
         backend_path: backend
@@ -784,152 +1199,235 @@ pub async fn autoscan_project_v3(root_path: String) -> Result<ProjectV3, String>
         frontend_path: frontend
             .map(|p| p.to_string_lossy().to_string())
             .unwrap_or_default(),
-        backend_type: None, // Détection automatique à implémenter si nécessaire
         ports: Ports {
             backend: 3010,
             frontend: 3000,
+            services: compose
+                .as_ref()
+                .map(|c| compose::compose_ports(&c.services))
+                .unwrap_or_default(),
         },
-        tunnel: None,
-        environment: None,
-        backend: None,
-        frontend: None,
-        commands: None,
         created_at: Utc::now().to_rfc3339(),
         enabled: true, // Par défaut activé pour rétrocompatibilité
+        compose: compose.map(|c| ComposeConfig {
+            services: c.services.into_iter().map(|s| s.name).collect(),
+            file: c.path,
+        }),
+        ..Default::default()
     })
 }
 
 /// Crée une configuration tunnel par défaut pour Coolify
+///
+/// La clé proposée est une clé réellement présente dans ~/.ssh (voir `ssh_keys`) ;
+/// sans clé trouvée, le champ reste vide plutôt que de pointer vers un fichier absent.
 fn create_default_tunnel(local_mongo_port: u16) -> Option<Tunnel> {
-    let home = dirs::home_dir()?;
-    
-    // Valeurs par défaut communes pour Coolify
-    // L'utilisateur pourra les modifier via le Project Manager
-    let default_key = home.join(".ssh/id_ed25519_hetzner");
-    
-    // Vérifier si la clé existe, sinon utiliser une clé générique
-    let key_path = if default_key.exists() {
-        default_key.to_string_lossy().to_string()
-    } else {
-        // Chercher d'autres clés SSH communes
-        let ssh_dir = home.join(".ssh");
-        let common_keys = ["id_ed25519_hetzner", "id_ed25519", "id_rsa"];
-        
-        common_keys.iter()
-            .find_map(|key| {
-                let path = ssh_dir.join(key);
-                if path.exists() {
-                    Some(path.to_string_lossy().to_string())
-                } else {
-                    None
-                }
-            })
-            .unwrap_or_else(|| home.join(".ssh/id_ed25519").to_string_lossy().to_string())
-    };
-    
+    let key_path = ssh_keys::default_private_key().unwrap_or_default();
+
     Some(Tunnel {
         enabled: false, // Désactivé par défaut, l'utilisateur l'activera
         host: String::new(), // À configurer par l'utilisateur
         user: "root".to_string(),
         port: 22,
         private_key: key_path,
-        local_mongo: local_mongo_port,
-        remote_mongo: 27017,
+        ssh_alias: None,
+        proxy_jump: Vec::new(),
+        forwards: vec![PortForward::local("mongo", local_mongo_port, 27017)],
     })
 }
 
-/// Scanne un dossier parent et détecte tous les repos indépendants (backend + frontend séparés)
-#[tauri::command]
-pub async fn scan_independent_repos(parent_path: String) -> Result<Vec<ProjectV3>, String> {
-    // Gérer l'expansion de ~
-    let expanded_path = if parent_path.starts_with("~/") {
-        let home = dirs::home_dir()
-            .ok_or_else(|| "Home directory not found".to_string())?;
-        home.join(&parent_path[2..])
-    } else {
-        PathBuf::from(&parent_path)
+/// Dépôt classé backend ou frontend par l'autoscan
+#[derive(Debug, Clone)]
+pub struct RepoCandidate {
+    pub path: String,
+    /// Nom du projet déduit du dossier ("gestionmax-backend" -> "gestionmax")
+    pub name: String,
+    pub stack: Detection,
+}
+
+/// Dépôts trouvés sous un dossier parent
+#[derive(Debug, Default)]
+pub struct RepoScan {
+    pub backends: Vec<RepoCandidate>,
+    pub frontends: Vec<RepoCandidate>,
+    /// Projets complets : dépôts avec manifeste et monorepos
+    pub projects: Vec<ProjectV3>,
+    /// Dépôts ignorés (manifeste ou workspace invalide)
+    pub warnings: Vec<String>,
+}
+
+/// Suggestions de `scan_independent_repos`
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct IndependentReposScan {
+    pub projects: Vec<ProjectV3>,
+    /// Dépôts ignorés, avec la raison
+    pub warnings: Vec<String>,
+}
+
+/// Chemin du dossier parent, avec expansion de `~/`
+pub(crate) fn expand_parent_path(parent_path: &str) -> Result<PathBuf, String> {
+    let parent = match parent_path.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir()
+            .ok_or_else(|| "Home directory not found".to_string())?
+            .join(rest),
+        None => PathBuf::from(parent_path),
     };
-    
-    let parent = expanded_path;
-    
+
     if !parent.exists() {
         return Err(format!("Path does not exist: {}", parent_path));
     }
-    
     if !parent.is_dir() {
         return Err(format!("Path is not a directory: {}", parent_path));
     }
-    
-    let mut backends: Vec<(String, String)> = Vec::new(); // (path, project_name)
-    let mut frontends: Vec<(String, String)> = Vec::new();
-    
-    // Scanner tous les sous-dossiers
-    let entries = fs::read_dir(&parent)
-        .map_err(|e| format!("Failed to read directory: {}", e))?;
-    
-    for entry in entries {
-        let entry = entry.map_err(|e| format!("Failed to read entry: {}", e))?;
-        let path = entry.path();
-        
-        if !path.is_dir() {
-            continue;
-        }
-        
-        let dir_name = path.file_name()
+    Ok(parent)
+}
+
+/// Classe les sous-dossiers jusqu'à `depth` niveaux (1 = enfants directs)
+///
+/// La recherche ne descend pas dans un dépôt déjà classé, ni dans un dépôt git
+/// qui n'est ni un backend ni un frontend.
+pub(crate) fn collect_repos(
+    parent: &Path,
+    depth: u32,
+    registry: &DetectorRegistry,
+) -> Result<RepoScan, String> {
+    let mut scan = RepoScan::default();
+    let rules = IgnoreRules::for_root(parent);
+    collect_repos_into(parent, depth, registry, &rules, &mut scan)?;
+    Ok(scan)
+}
+
+fn collect_repos_into(
+    dir: &Path,
+    depth: u32,
+    registry: &DetectorRegistry,
+    rules: &IgnoreRules,
+    scan: &mut RepoScan,
+) -> Result<(), String> {
+    if depth == 0 {
+        return Ok(());
+    }
+
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read directory: {}", e))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_dir())
+        .collect();
+    paths.sort();
+
+    for path in paths {
+        let dir_name = path
+            .file_name()
             .and_then(|n| n.to_str())
             .unwrap_or("")
             .to_string();
-        
-        // Ignorer les dossiers système
-        if should_ignore_dir(&dir_name) {
+
+        // Ignorer les dossiers système et ceux exclus par les règles d'ignore
+        if rules.is_ignored(&path) {
             continue;
         }
-        
-        // Vérifier si c'est un backend Payload
-        let package_json = path.join("package.json");
-        let payload_config = path.join("payload.config.ts");
-        
-        if package_json.exists() {
-            if let Ok(content) = fs::read_to_string(&package_json) {
-                if content.contains("payload") || content.contains("@payloadcms") || payload_config.exists() {
-                    let project_name = extract_project_name(&dir_name, "backend");
-                    backends.push((path.to_string_lossy().to_string(), project_name));
-                    continue;
-                }
+
+        // Un dépôt avec manifeste se décrit lui-même : pas d'heuristique ni d'appariement.
+        // Un manifeste invalide écarte ce dépôt seulement.
+        let from_manifest = manifest::load_manifest(&path).and_then(|found| {
+            found
+                .map(|(manifest_path, m)| {
+                    manifest::project_from_manifest(&path, &manifest_path, &m)
+                })
+                .transpose()
+        });
+        match from_manifest {
+            Ok(Some(project)) => {
+                scan.projects.push(project);
+                continue;
+            }
+            Ok(None) => {}
+            Err(e) => {
+                scan.warnings.push(e);
+                continue;
             }
         }
-        
-        // Vérifier si c'est un frontend Next.js
-        let next_config = path.join("next.config.js");
-        if package_json.exists() {
-            if let Ok(content) = fs::read_to_string(&package_json) {
-                if content.contains("\"next\"") || content.contains("nextjs") || next_config.exists() {
-                    let project_name = extract_project_name(&dir_name, "frontend");
-                    frontends.push((path.to_string_lossy().to_string(), project_name));
-                }
+
+        // Un monorepo forme un seul projet, avec une app par service
+        // Un workspace invalide écarte ce dépôt seulement
+        match workspace::detect_workspace(&path) {
+            Ok(Some(ws)) if !ws.apps.is_empty() => {
+                scan.projects
+                    .push(workspace::project_from_workspace(&path, ws));
+                continue;
+            }
+            Ok(_) => {}
+            Err(e) => {
+                scan.warnings.push(format!("{}: {}", path.display(), e));
+                continue;
             }
         }
+
+        // Classer le dossier d'après la stack détectée (backend prioritaire)
+        let candidate = |suffix: &str, stack: Detection| RepoCandidate {
+            path: path.to_string_lossy().to_string(),
+            name: extract_project_name(&dir_name, suffix),
+            stack,
+        };
+        if let Some(detection) = registry.best(&path, ServiceRole::Backend) {
+            scan.backends.push(candidate("backend", detection));
+        } else if let Some(detection) = registry.best(&path, ServiceRole::Frontend) {
+            scan.frontends.push(candidate("frontend", detection));
+        } else if !path.join(".git").exists() {
+            collect_repos_into(&path, depth - 1, registry, rules, scan)?;
+        }
     }
-    
-    // Associer backends et frontends par nom de projet
-    let mut projects: Vec<ProjectV3> = Vec::new();
-    let mut used_backends = std::collections::HashSet::new();
+
+    Ok(())
+}
+
+/// Scanne un dossier parent et détecte tous les repos indépendants (backend + frontend séparés)
+///
+/// `depth` fixe la profondeur de recherche (1 par défaut). Sans `pairs`, les
+/// meilleures associations de `rank_repo_pairings` sont retenues ; sinon seules
+/// les associations confirmées par l'utilisateur sont utilisées.
+#[tauri::command]
+pub async fn scan_independent_repos(
+    parent_path: String,
+    depth: Option<u32>,
+    pairs: Option<Vec<RepoPair>>,
+) -> Result<IndependentReposScan, String> {
+    let parent = expand_parent_path(&parent_path)?;
+    let registry = DetectorRegistry::with_defaults();
+    let RepoScan {
+        backends,
+        frontends,
+        projects: manifest_projects,
+        warnings,
+    } = collect_repos(&parent, depth.unwrap_or(DEFAULT_SCAN_DEPTH), &registry)?;
+    let pairs = match pairs {
+        Some(pairs) => pairs,
+        None => best_pairs(&rank_pairings(&backends, &frontends)),
+    };
+
+    // Associer chaque backend au frontend retenu
+    let mut projects: Vec<ProjectV3> = manifest_projects;
     let mut used_frontends = std::collections::HashSet::new();
     let mut mongo_port_counter = 27017u16; // Port par défaut GestionMax: 27017
     
-    for (backend_path, backend_name) in &backends {
-        // Chercher un frontend correspondant
-        let matching_frontend = frontends.iter()
-            .find(|(_, frontend_name)| frontend_name == backend_name);
+    for RepoCandidate {
+        path: backend_path,
+        name: backend_name,
+        stack: backend_stack,
+    } in &backends
+    {
+        let matching_frontend = pairs
+            .iter()
+            .find(|pair| &pair.backend_path == backend_path)
+            .and_then(|pair| frontends.iter().find(|f| f.path == pair.frontend_path));
         
         let frontend_path = matching_frontend
-            .map(|(path, _)| path.clone())
+            .map(|f| f.path.clone())
             .unwrap_or_default();
         
-        if let Some((_, _)) = matching_frontend {
-            used_frontends.insert(matching_frontend.unwrap().0.clone());
+        if let Some(frontend) = matching_frontend {
+            used_frontends.insert(frontend.path.clone());
         }
-        used_backends.insert(backend_path.clone());
         
         // Créer le projet avec un ID unique basé sur le nom du dossier backend
         let backend_dir = PathBuf::from(backend_path)
@@ -937,20 +1435,15 @@ pub async fn scan_independent_repos(parent_path: String) -> Result<Vec<ProjectV3
             .and_then(|n| n.to_str())
             .unwrap_or(&backend_name)
             .to_string();
-        let id = backend_dir.to_lowercase()
-            .replace(' ', "-")
-            .replace('_', "-")
-            .replace('/', "-");
+        let id = project_id_from_name(&backend_dir);
         let root_path = parent.to_string_lossy().to_string(); // Utiliser le parent comme root
         
         let backend_port = detect_backend_port(Path::new(backend_path))
-            .unwrap_or(None)
-            .unwrap_or(3000); // Port par défaut 3000 pour backend Payload
+            .map_or(3000, |d| d.port); // Port par défaut 3000 pour backend Payload
         
         let frontend_port = if !frontend_path.is_empty() {
             detect_frontend_port(Path::new(&frontend_path))
-                .unwrap_or(None)
-                .unwrap_or(3010) // Port par défaut 3010 pour frontend Next.js
+                .map_or(3010, |d| d.port) // Port par défaut 3010 pour frontend Next.js
         } else {
             3010
         };
@@ -962,24 +1455,18 @@ pub async fn scan_independent_repos(parent_path: String) -> Result<Vec<ProjectV3
         // Créer un tunnel par défaut (désactivé, à configurer par l'utilisateur)
         let tunnel = create_default_tunnel(local_mongo_port);
         
-        // Commandes par défaut (npm run dev pour backend et frontend)
+        // Commandes de dev par défaut des stacks détectées
         let commands = Some(ProjectCommands {
-            backend: Some("npm run dev".to_string()),
-            frontend: if !frontend_path.is_empty() {
-                Some("npm run dev".to_string())
-            } else {
-                None
-            },
+            backend: Some(backend_stack.dev_command.clone()),
+            frontend: matching_frontend.map(|f| f.stack.dev_command.clone()),
             tunnel: None,
             netdata: None,
         });
         
-        // Détecter automatiquement le type de backend (payload si payload.config.ts existe)
-        let backend_type = if Path::new(&backend_path).join("payload.config.ts").exists() 
-            || Path::new(&backend_path).join("payload.config.js").exists() {
-            Some("payload".to_string())
-        } else {
-            None
+        // Type de backend CMS connu de l'interface (payload ou directus)
+        let backend_type = match backend_stack.stack.as_str() {
+            "payload" | "directus" => Some(backend_stack.stack.clone()),
+            _ => None,
         };
         
         projects.push(ProjectV3 {
@@ -992,26 +1479,30 @@ pub async fn scan_independent_repos(parent_path: String) -> Result<Vec<ProjectV3
             ports: Ports {
                 backend: backend_port,
                 frontend: frontend_port,
+                ..Default::default()
             },
             tunnel,
-            environment: None,
-            backend: None,
-            frontend: None,
             commands,
             created_at: Utc::now().to_rfc3339(),
             enabled: true, // Par défaut activé pour rétrocompatibilité
+            remote: origin_remote(Path::new(&backend_path)),
+            ..Default::default()
         });
     }
     
     // Ajouter les frontends orphelins (sans backend correspondant)
-    for (frontend_path, frontend_name) in &frontends {
+    for RepoCandidate {
+        path: frontend_path,
+        name: frontend_name,
+        stack: frontend_stack,
+    } in &frontends
+    {
         if !used_frontends.contains(frontend_path) {
-            let id = frontend_name.to_lowercase().replace(' ', "-").replace('_', "-");
+            let id = project_id_from_name(frontend_name);
             let root_path = parent.to_string_lossy().to_string();
             
             let frontend_port = detect_frontend_port(Path::new(frontend_path))
-                .unwrap_or(None)
-                .unwrap_or(3010); // Port par défaut 3010 pour frontend Next.js
+                .map_or(3010, |d| d.port); // Port par défaut 3010 pour frontend Next.js
             
             // Assigner un port MongoDB local unique pour ce projet
             let local_mongo_port = mongo_port_counter;
@@ -1020,10 +1511,10 @@ pub async fn scan_independent_repos(parent_path: String) -> Result<Vec<ProjectV3
             // Créer un tunnel par défaut (désactivé, à configurer par l'utilisateur)
             let tunnel = create_default_tunnel(local_mongo_port);
             
-            // Commandes par défaut
+            // Commande de dev par défaut de la stack détectée
             let commands = Some(ProjectCommands {
                 backend: None,
-                frontend: Some("npm run dev".to_string()),
+                frontend: Some(frontend_stack.dev_command.clone()),
                 tunnel: None,
                 netdata: None,
             });
@@ -1032,25 +1523,23 @@ pub async fn scan_independent_repos(parent_path: String) -> Result<Vec<ProjectV3
                 id,
                 name: frontend_name.clone(),
                 root_path,
-                backend_path: String::new(),
                 frontend_path: frontend_path.clone(),
-                backend_type: None, // Frontend only, pas de backend
                 ports: Ports {
                     backend: 3010,
                     frontend: frontend_port,
+                    ..Default::default()
                 },
                 tunnel,
-                environment: None,
-                backend: None,
-                frontend: None,
                 commands,
                 created_at: Utc::now().to_rfc3339(),
                 enabled: true, // Par défaut activé pour rétrocompatibilité
+                remote: origin_remote(Path::new(frontend_path)),
+                ..Default::default()
             });
         }
     }
     
-    Ok(projects)
+    Ok(IndependentReposScan { projects, warnings })
 }
 
 /// Extrait le nom du projet depuis le nom du dossier
@@ -1102,3 +1591,24 @@ fn extract_project_name(dir_name: &str, suffix: &str) -> String {
         format_project_name(&cleaned)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::projects_v3::is_valid_project_id;
+
+    #[test]
+    fn test_project_v3_from_root_builds_valid_id() {
+        let parent = std::env::temp_dir().join(format!("gmdev-autoscan-{}", uuid::Uuid::new_v4()));
+        let root = parent.join("Mon Site.v2");
+        fs::create_dir_all(root.join("backend")).unwrap();
+        fs::create_dir_all(root.join("frontend")).unwrap();
+
+        let project = project_v3_from_root(&root).unwrap();
+        assert_eq!(project.id, "mon-site-v2");
+        assert!(is_valid_project_id(&project.id));
+        assert_eq!(project.name, "Mon Site.v2");
+
+        fs::remove_dir_all(&parent).unwrap();
+    }
+}
@@ -8,14 +8,23 @@ use tauri::path::BaseDirectory;
 use tauri::{AppHandle, Manager};
 
 use crate::autoscan::ProjectV3;
-use crate::projects_v3::{load_projects_v3, ProjectConfigV3};
-use crate::gmd::{run_gmd, run_gmd_streaming, is_gmd_available, GmdCommand, GmdRunId};
-
+use crate::dependencies;
+use crate::compose;
+use crate::profiles;
+use crate::tunnels;
+use crate::secrets::{redact, serialize_redacted};
+use crate::projects_v3::{load_projects_v3, read_config, write_config, ProjectConfigV3};
+use crate::gmd::{run_gmd, run_gmd_streaming, is_gmd_available, GmdCommand, GmdResult, GmdRunId};
+
+/// Résultat d'une commande de service ; les sorties sont masquées à la sérialisation
 #[derive(Serialize)]
 pub struct ScriptResult {
+    #[serde(serialize_with = "serialize_redacted")]
     pub stdout: String,
+    #[serde(serialize_with = "serialize_redacted")]
     pub stderr: String,
     pub code: i32,
+    pub profile: Option<String>,
 }
 
 fn get_script_path(app_handle: &AppHandle, script_name: &str) -> Result<PathBuf, String> {
@@ -75,22 +84,102 @@ fn run_gmdev_command(args: &[&str], cwd: Option<PathBuf>) -> ScriptResult {
         args: args_vec,
         cwd,
         project_id: None, // L'ancienne fonction ne gérait pas project_id
+        envs: Vec::new(),
+        profile: None,
     };
     
-    match run_gmd(cmd) {
+    to_script_result(run_gmd(cmd), None)
+}
+
+/// Exécute une commande gmdev pour un projet avec sa configuration résolue
+///
+/// gmdev lit alors `projects-v3.runtime.json` (profil actif appliqué) et le
+/// résultat est marqué avec le profil du projet.
+pub(crate) fn run_gmdev_for_project(args: &[&str], cfg: &ProjectConfigV3, project: &ProjectV3) -> ScriptResult {
+    let args_vec: Vec<String> = args.iter().map(|s| s.to_string()).collect();
+    let profile = project.active_profile.clone();
+
+    let envs = match profiles::gmdev_env(cfg, &args_vec, &project.id) {
+        Ok(envs) => envs,
+        Err(err) => return to_script_result(Err(err), profile),
+    };
+
+    let cmd = GmdCommand {
+        args: args_vec,
+        cwd: Some(PathBuf::from(&project.root_path)),
+        project_id: None, // Le project_id est déjà passé dans args
+        envs,
+        profile: profile.clone(),
+    };
+
+    to_script_result(run_gmd(cmd), profile)
+}
+
+fn to_script_result(result: Result<GmdResult, String>, profile: Option<String>) -> ScriptResult {
+    match result {
         Ok(result) => ScriptResult {
             stdout: result.stdout,
             stderr: result.stderr,
             code: result.code,
+            profile,
         },
         Err(err) => ScriptResult {
             stdout: String::new(),
             stderr: err,
             code: -1,
+            profile,
         },
     }
 }
 
+/// Démarre, arrête ou redémarre un service d'un projet
+///
+/// Le tunnel SSH est géré nativement (voir `tunnels`), les autres services par gmdev.
+pub(crate) fn run_service_for_project(
+    action: &str,
+    gmdev_service: &str,
+    cfg: &ProjectConfigV3,
+    project: &ProjectV3,
+) -> ScriptResult {
+    if gmdev_service == "compose" {
+        return compose::run_compose_service(action, project);
+    }
+    if gmdev_service != "tunnel" {
+        return run_gmdev_for_project(&[action, gmdev_service, &project.id], cfg, project);
+    }
+
+    let profile = project.active_profile.clone();
+    let stopped = match action {
+        "stop" | "restart" => tunnels::stop_project_tunnel(&project.id),
+        _ => false,
+    };
+    let result = match action {
+        "stop" if stopped => Ok("Tunnel SSH stopped".to_string()),
+        "stop" => Ok("Tunnel SSH was not running".to_string()),
+        "start" | "restart" => tunnels::start_project_tunnel(project).map(|status| {
+            let forwards: Vec<String> = status
+                .forwards
+                .iter()
+                .map(|f| format!("{} {}", f.forward.name, f.forward.local_port))
+                .collect();
+            format!(
+                "Tunnel SSH {} [{}] ({:?})",
+                status.destination.unwrap_or_default(),
+                forwards.join(", "),
+                status.state
+            )
+        }),
+        _ => Err(format!("Action inconnue pour le tunnel: {}", action)),
+    };
+    let result = result.map(|stdout| GmdResult {
+        stdout,
+        stderr: String::new(),
+        code: 0,
+        profile: profile.clone(),
+    });
+    to_script_result(result, profile)
+}
+
 /// Vérifie si gmdev est disponible (wrapper pour compatibilité)
 /// 
 /// ⚠️ DÉPRÉCIÉ : Utiliser `is_gmd_available()` depuis le module `gmd` à la place
@@ -113,6 +202,7 @@ fn run_embedded_script(
             stdout: String::new(),
             stderr: "gmdev n'est pas disponible. gmdev est le runtime officiel et doit être installé. Installez-le et ajoutez-le à votre PATH.".to_string(),
             code: 127,
+            profile: None,
         };
     }
     
@@ -124,6 +214,7 @@ fn run_embedded_script(
                 stdout: String::new(),
                 stderr: format!("Script '{}' non mappé vers gmdev. gmdev est le seul runtime autorisé.", script_name),
                 code: 127,
+                profile: None,
             };
         }
     };
@@ -157,82 +248,6 @@ fn is_port_listening(port: u16) -> bool {
     TcpListener::bind(format!("127.0.0.1:{}", port)).is_err()
 }
 
-/// OBSOLÈTE depuis v2.0 - Migration vers gmdev
-/// Les variables d'environnement sont maintenant gérées par gmdev
-#[allow(dead_code)]
-fn build_envs(project: &ProjectV3, service: &str) -> Vec<(String, String)> {
-    let mut envs = vec![(
-        "PROJECT_ROOT_PATH".to_string(),
-        project.root_path.clone(),
-    )];
-
-    match service {
-        "backend" => {
-            envs.push(("PROJECT_BACKEND_PATH".to_string(), project.backend_path.clone()));
-            envs.push(("PROJECT_BACKEND_PORT".to_string(), project.ports.backend.to_string()));
-            if let Some(commands) = &project.commands {
-                if let Some(cmd) = &commands.backend {
-                    envs.push(("PROJECT_BACKEND_COMMAND".to_string(), cmd.clone()));
-                }
-            }
-            if let Some(environment) = &project.environment {
-                for (key, value) in environment.backend_env.iter() {
-                    envs.push((
-                        format!("PROJECT_BACKEND_ENV_{}", key.to_ascii_uppercase()),
-                        value.clone(),
-                    ));
-                }
-            }
-        }
-        "frontend" => {
-            envs.push(("PROJECT_FRONTEND_PATH".to_string(), project.frontend_path.clone()));
-            envs.push(("PROJECT_FRONTEND_PORT".to_string(), project.ports.frontend.to_string()));
-            if let Some(commands) = &project.commands {
-                if let Some(cmd) = &commands.frontend {
-                    envs.push(("PROJECT_FRONTEND_COMMAND".to_string(), cmd.clone()));
-                }
-            }
-            if let Some(environment) = &project.environment {
-                for (key, value) in environment.frontend_env.iter() {
-                    envs.push((
-                        format!("PROJECT_FRONTEND_ENV_{}", key.to_ascii_uppercase()),
-                        value.clone(),
-                    ));
-                }
-            }
-        }
-        "tunnel" => {
-            if let Some(tunnel) = &project.tunnel {
-                // Ne définir les variables que si le tunnel est activé et configuré
-                if tunnel.enabled && !tunnel.host.is_empty() {
-                    envs.push(("PROJECT_TUNNEL_HOST".to_string(), tunnel.host.clone()));
-                    envs.push(("PROJECT_TUNNEL_USER".to_string(), tunnel.user.clone()));
-                    envs.push(("PROJECT_TUNNEL_PORT".to_string(), tunnel.port.to_string()));
-                    envs.push(("PROJECT_LOCAL_MONGO".to_string(), tunnel.local_mongo.to_string()));
-                    envs.push(("PROJECT_REMOTE_MONGO".to_string(), tunnel.remote_mongo.to_string()));
-                    envs.push(("PROJECT_TUNNEL_KEY".to_string(), tunnel.private_key.clone()));
-                    if let Some(commands) = &project.commands {
-                        if let Some(cmd) = &commands.tunnel {
-                            envs.push(("PROJECT_TUNNEL_COMMAND".to_string(), cmd.clone()));
-                        }
-                    }
-                }
-            }
-        }
-        "netdata" => {
-            envs.push(("PROJECT_NETDATA_PORT".to_string(), "19999".to_string()));
-            if let Some(commands) = &project.commands {
-                if let Some(cmd) = &commands.netdata {
-                    envs.push(("PROJECT_NETDATA_COMMAND".to_string(), cmd.clone()));
-                }
-            }
-        }
-        _ => {}
-    }
-
-    envs
-}
-
 /// OBSOLÈTE depuis v2.0 - Migration vers gmdev
 /// Le statut est maintenant obtenu via gmdev status
 #[allow(dead_code)]
@@ -273,27 +288,6 @@ fn status_for_frontend(project: &ProjectV3) -> bool {
     })
 }
 
-/// OBSOLÈTE depuis v2.0 - Migration vers gmdev
-/// Le statut est maintenant obtenu via gmdev status
-#[allow(dead_code)]
-fn status_for_tunnel(project: &ProjectV3) -> bool {
-    if let Some(tunnel) = &project.tunnel {
-        let pattern = format!(
-            "{}:127.0.0.1:{}",
-            tunnel.local_mongo, tunnel.remote_mongo
-        );
-        let mut system = System::new_all();
-        system.refresh_all();
-        system.processes().values().any(|process| {
-            let name = process.name().to_ascii_lowercase();
-            let cmd = process.cmd().join(" ").to_ascii_lowercase();
-            name.contains("ssh") && cmd.contains(&pattern)
-        })
-    } else {
-        false
-    }
-}
-
 /// OBSOLÈTE depuis v2.0 - Migration vers gmdev
 /// Le statut est maintenant obtenu via gmdev status
 #[allow(dead_code)]
@@ -635,21 +629,17 @@ pub async fn stop_project_service(
             }
         }
 
-        // Sinon, essayer de trouver et arrêter les processus SSH liés au projet
-        // Utiliser pkill pour arrêter les tunnels SSH
-        let output = Command::new("pkill")
-            .arg("-f")
-            .arg("ssh.*-L") // Chercher les tunnels SSH avec port forwarding
-            .output();
-
-        if let Ok(cmd_output) = output {
-            if cmd_output.status.success() || cmd_output.status.code() == Some(1) {
-                // Code 1 signifie qu'aucun processus n'a été trouvé (déjà arrêté)
-                return Ok("Tunnel SSH stopped or was not running".to_string());
+        // Sinon, arrêter uniquement le tunnel géré pour ce projet
+        let managed = read_config()?
+            .projects
+            .into_iter()
+            .find(|p| p.backend_path == project_path || p.frontend_path == project_path);
+        if let Some(managed) = managed {
+            if tunnels::stop_project_tunnel(&managed.id) {
+                return Ok("Tunnel SSH stopped".to_string());
             }
         }
 
-        // Si on arrive ici, considérer que c'est un succès (tunnel peut ne pas être en cours)
         return Ok("Tunnel SSH was not running".to_string());
     }
 
@@ -771,10 +761,10 @@ pub async fn run_command(cmd: String) -> Result<String, String> {
 
     if output.status.success() {
         let stdout = String::from_utf8_lossy(&output.stdout);
-        Ok(stdout.to_string())
+        Ok(redact(&stdout))
     } else {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        Err(format!("Command failed: {}", stderr))
+        Err(format!("Command failed: {}", redact(&stderr)))
     }
 }
 
@@ -900,7 +890,7 @@ pub async fn stop_service(
 
 #[tauri::command]
 pub async fn start_service_v3(
-    _app_handle: AppHandle,
+    app_handle: AppHandle,
     project_id: String,
     service: String,
 ) -> Result<ScriptResult, String> {
@@ -918,16 +908,68 @@ pub async fn start_service_v3(
         "backend" => "back",
         "frontend" => "front",
         "tunnel" => "tunnel",
+        "compose" => "compose",
         _ => return Err(format!("Service inconnu: {}", service)),
     };
     
+    // Dépendances périmées : installation automatique ou event deps:outdated
+    if !matches!(service.as_str(), "tunnel" | "compose") {
+        dependencies::check_before_start(&app_handle, project, &service)?;
+    }
+
     // Appeler gmdev start avec le project_id explicite (seule source de vérité)
-    let cwd = Some(PathBuf::from(&project.root_path));
-    Ok(run_gmdev_command(&["start", gmdev_service, &project_id], cwd))
+    Ok(run_service_for_project("start", gmdev_service, &cfg, project))
+}
+
+/// Statut d'un service V3, marqué avec le profil d'environnement actif
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ServiceStatusV3 {
+    pub status: String, // "RUNNING" | "STOPPED"
+    pub profile: Option<String>,
+}
+
+/// Sortie brute d'une commande gmdev (status, logs), marquée avec le profil actif
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GmdevOutput {
+    pub project_id: String,
+    pub profile: Option<String>,
+    #[serde(serialize_with = "serialize_redacted")]
+    pub output: String,
+}
+
+/// Détermine si un service tourne d'après la sortie de `gmdev status`
+pub(crate) fn gmdev_service_status(
+    cfg: &ProjectConfigV3,
+    project: &ProjectV3,
+    gmdev_service: &str,
+) -> Result<String, String> {
+    if gmdev_service == "tunnel" {
+        let running = tunnels::project_tunnel_status(&project.id).is_running();
+        return Ok(if running { "RUNNING" } else { "STOPPED" }.into());
+    }
+    if gmdev_service == "compose" {
+        return compose::compose_service_status(project);
+    }
+
+    let result = run_gmdev_for_project(&["status", &project.id], cfg, project);
+
+    // Parser la sortie de gmdev status
+    // Format attendu: tunnel(front/back) + ports + pid + last logs
+    if result.code == 0 {
+        let output = result.stdout.to_lowercase();
+        // Vérifier si le service est mentionné comme running dans la sortie
+        if output.contains(gmdev_service) && (output.contains("running") || output.contains("active")) {
+            return Ok("RUNNING".into());
+        }
+        return Ok("STOPPED".into());
+    }
+
+    // Si gmdev status échoue, on ne peut pas déterminer le statut
+    Err(format!("Impossible de déterminer le statut via gmdev: {}", result.stderr))
 }
 
 #[tauri::command]
-pub async fn status_service_v3(project_id: String, service: String) -> Result<String, String> {
+pub async fn status_service_v3(project_id: String, service: String) -> Result<ServiceStatusV3, String> {
     // gmdev est la seule source de vérité - OBLIGATOIRE
     if !is_gmd_available() {
         return Err("gmdev n'est pas disponible. gmdev est le runtime officiel et doit être installé.".to_string());
@@ -937,6 +979,7 @@ pub async fn status_service_v3(project_id: String, service: String) -> Result<St
         "backend" => "back",
         "frontend" => "front",
         "tunnel" => "tunnel",
+        "compose" => "compose",
         _ => return Err(format!("Service inconnu: {}", service)),
     };
     
@@ -945,21 +988,12 @@ pub async fn status_service_v3(project_id: String, service: String) -> Result<St
         get_project_by_id(&cfg, &project_id).ok_or_else(|| "Project not found".to_string())?;
     
     // Utiliser gmdev status avec le project_id explicite (seule source de vérité)
-    let result = run_gmdev_command(&["status", &project_id], Some(PathBuf::from(&project.root_path)));
+    let status = gmdev_service_status(&cfg, project, gmdev_service)?;
     
-    // Parser la sortie de gmdev status
-    // Format attendu: tunnel(front/back) + ports + pid + last logs
-    if result.code == 0 {
-        let output = result.stdout.to_lowercase();
-        // Vérifier si le service est mentionné comme running dans la sortie
-        if output.contains(gmdev_service) && (output.contains("running") || output.contains("active")) {
-            return Ok("RUNNING".into());
-        }
-        return Ok("STOPPED".into());
-    }
-    
-    // Si gmdev status échoue, on ne peut pas déterminer le statut
-    Err(format!("Impossible de déterminer le statut via gmdev: {}", result.stderr))
+    Ok(ServiceStatusV3 {
+        status,
+        profile: project.active_profile.clone(),
+    })
 }
 
 #[tauri::command]
@@ -982,12 +1016,12 @@ pub async fn stop_service_v3(
         "backend" => "back",
         "frontend" => "front",
         "tunnel" => "tunnel",
+        "compose" => "compose",
         _ => return Err(format!("Service inconnu: {}", service)),
     };
     
     // Appeler gmdev stop avec le project_id explicite (seule source de vérité)
-    let cwd = Some(PathBuf::from(&project.root_path));
-    Ok(run_gmdev_command(&["stop", gmdev_service, &project_id], cwd))
+    Ok(run_service_for_project("stop", gmdev_service, &cfg, project))
 }
 #[tauri::command]
 pub async fn kill_zombies_v3(_app_handle: AppHandle) -> Result<ScriptResult, String> {
@@ -1004,7 +1038,7 @@ pub async fn kill_zombies_v3(_app_handle: AppHandle) -> Result<ScriptResult, Str
 /// 
 /// Essaie d'abord `gmdev status --json` si disponible, sinon utilise le format texte
 #[tauri::command]
-pub async fn get_gmdev_status(project_id: String) -> Result<String, String> {
+pub async fn get_gmdev_status(project_id: String) -> Result<GmdevOutput, String> {
     if !is_gmd_available() {
         return Err("gmdev n'est pas disponible. Assurez-vous qu'il est installé et dans votre PATH.".to_string());
     }
@@ -1014,18 +1048,26 @@ pub async fn get_gmdev_status(project_id: String) -> Result<String, String> {
         get_project_by_id(&cfg, &project_id).ok_or_else(|| "Project not found".to_string())?;
     
     // Essayer d'abord avec --json si disponible
-    let json_result = run_gmdev_command(&["status", "--json", &project_id], Some(PathBuf::from(&project.root_path)));
+    let json_result = run_gmdev_for_project(&["status", "--json", &project_id], &cfg, project);
     
     if json_result.code == 0 && !json_result.stdout.trim().is_empty() {
         // JSON disponible et valide
-        return Ok(json_result.stdout);
+        return Ok(GmdevOutput {
+            project_id,
+            profile: json_result.profile,
+            output: json_result.stdout,
+        });
     }
     
     // Fallback sur le format texte standard
-    let result = run_gmdev_command(&["status", &project_id], Some(PathBuf::from(&project.root_path)));
+    let result = run_gmdev_for_project(&["status", &project_id], &cfg, project);
     
     if result.code == 0 {
-        Ok(result.stdout)
+        Ok(GmdevOutput {
+            project_id,
+            profile: result.profile,
+            output: result.stdout,
+        })
     } else {
         Err(format!("Erreur gmdev status: {}", result.stderr))
     }
@@ -1037,7 +1079,7 @@ pub async fn get_gmdev_logs(
     project_id: String,
     service: String,
     tail: Option<u32>,
-) -> Result<String, String> {
+) -> Result<GmdevOutput, String> {
     if !is_gmdev_available() {
         return Err("gmdev n'est pas disponible. Assurez-vous qu'il est installé et dans votre PATH.".to_string());
     }
@@ -1058,10 +1100,14 @@ pub async fn get_gmdev_logs(
     // Passer le project_id comme dernier argument après --tail
     let args = vec!["logs", gmdev_service, "--tail", &tail_str, &project_id];
     
-    let result = run_gmdev_command(&args, Some(PathBuf::from(&project.root_path)));
+    let result = run_gmdev_for_project(&args, &cfg, project);
     
     if result.code == 0 {
-        Ok(result.stdout)
+        Ok(GmdevOutput {
+            project_id: project_id.clone(),
+            profile: result.profile,
+            output: result.stdout,
+        })
     } else {
         Err(format!("Erreur gmdev logs: {}", result.stderr))
     }
@@ -1070,7 +1116,7 @@ pub async fn get_gmdev_logs(
 /// Redémarre un service via gmdev restart
 #[tauri::command]
 pub async fn restart_service_v3(
-    _app_handle: AppHandle,
+    app_handle: AppHandle,
     project_id: String,
     service: String,
 ) -> Result<ScriptResult, String> {
@@ -1088,12 +1134,101 @@ pub async fn restart_service_v3(
         "backend" => "back",
         "frontend" => "front",
         "tunnel" => "tunnel",
+        "compose" => "compose",
         _ => return Err(format!("Service inconnu: {}", service)),
     };
     
+    // Dépendances périmées : installation automatique ou event deps:outdated
+    if !matches!(service.as_str(), "tunnel" | "compose") {
+        dependencies::check_before_start(&app_handle, project, &service)?;
+    }
+
     // Appeler gmdev restart avec le project_id explicite (seule source de vérité)
-    let cwd = Some(PathBuf::from(&project.root_path));
-    Ok(run_gmdev_command(&["restart", gmdev_service, &project_id], cwd))
+    Ok(run_service_for_project("restart", gmdev_service, &cfg, project))
+}
+
+/// Résultat d'un changement de profil d'environnement
+#[derive(Serialize)]
+pub struct ProfileSwitchResult {
+    pub project_id: String,
+    pub previous_profile: Option<String>,
+    pub active_profile: Option<String>,
+    /// Services dont la configuration change avec le nouveau profil
+    pub affected: Vec<String>,
+    /// Services effectivement redémarrés (ceux qui tournaient)
+    pub restarted: Vec<String>,
+    pub results: Vec<ScriptResult>,
+}
+
+/// Change le profil d'environnement actif d'un projet (None = configuration de base)
+///
+/// Les services en cours d'exécution dont la configuration change sont arrêtés
+/// avec l'ancien profil puis redémarrés avec le nouveau.
+#[tauri::command]
+pub async fn switch_profile(
+    project_id: String,
+    profile: Option<String>,
+) -> Result<ProfileSwitchResult, String> {
+    let mut cfg = read_config()?;
+    let index = cfg
+        .projects
+        .iter()
+        .position(|p| p.id == project_id)
+        .ok_or_else(|| "Project not found".to_string())?;
+
+    if let Some(name) = &profile {
+        if !cfg.projects[index].profiles.contains_key(name) {
+            return Err(format!("Profil '{}' introuvable pour le projet {}", name, project_id));
+        }
+    }
+
+    let previous_profile = cfg.projects[index].active_profile.clone();
+    let before = profiles::effective_project(&cfg.projects[index]);
+    let mut next = cfg.projects[index].clone();
+    next.active_profile = profile.clone();
+    let affected = profiles::affected_services(&before, &profiles::effective_project(&next));
+
+    // Arrêter les services concernés avec l'ancienne configuration
+    let mut restarted = Vec::new();
+    let mut results = Vec::new();
+    if is_gmd_available() {
+        let project = cfg.projects[index].clone();
+        for service in &affected {
+            let gmdev_service = gmdev_service_name(service);
+            if gmdev_service_status(&cfg, &project, gmdev_service).as_deref() == Ok("RUNNING") {
+                results.push(run_service_for_project("stop", gmdev_service, &cfg, &project));
+                restarted.push(service.to_string());
+            }
+        }
+    }
+
+    cfg.projects[index] = next;
+    write_config(&cfg)?;
+
+    // Redémarrer avec le nouveau profil
+    let project = cfg.projects[index].clone();
+    for service in &restarted {
+        let gmdev_service = gmdev_service_name(service);
+        results.push(run_service_for_project("start", gmdev_service, &cfg, &project));
+    }
+
+    Ok(ProfileSwitchResult {
+        project_id,
+        previous_profile,
+        active_profile: profile,
+        affected: affected.iter().map(|s| s.to_string()).collect(),
+        restarted,
+        results,
+    })
+}
+
+pub(crate) fn gmdev_service_name(service: &str) -> &'static str {
+    match service {
+        "backend" => "back",
+        "frontend" => "front",
+        "compose" => "compose",
+        _ => "tunnel",
+    }
 }
 
 /// Commande Tauri pour exécuter une commande gmdev de manière centralisée avec streaming
@@ -1111,8 +1246,8 @@ pub async fn restart_service_v3(
 /// - `app`: Handle de l'application Tauri pour émettre les events
 /// 
 /// # Events émis
-/// - `gmd:log`: Pour chaque ligne de stdout/stderr (payload: `{ runId, ts, level, line, cmd, cwd }`)
-/// - `gmd:exit`: Quand la commande se termine (payload: `{ runId, exitCode }`)
+/// - `gmd:log`: Pour chaque ligne de stdout/stderr (payload: `{ runId, ts, level, line, cmd, cwd, profile }`)
+/// - `gmd:exit`: Quand la commande se termine (payload: `{ runId, exitCode, profile }`)
 /// 
 /// # Exemple
 /// ```typescript
@@ -1139,11 +1274,23 @@ pub async fn run_gmd_command(
     // Générer un runId unique
     let run_id = format!("gmd-{}", uuid::Uuid::new_v4().to_string());
     
+    // Un projet connu est exécuté avec sa configuration résolue (profil actif)
+    let (envs, profile) = match project_id.as_deref() {
+        Some(id) => {
+            let cfg = read_config()?;
+            let profile = get_project_by_id(&cfg, id).and_then(|p| p.active_profile.clone());
+            (profiles::gmdev_env(&cfg, &args, id)?, profile)
+        }
+        None => (Vec::new(), None),
+    };
+    
     let cwd_path = cwd.map(PathBuf::from);
     let cmd = GmdCommand {
         args,
         cwd: cwd_path,
         project_id,
+        envs,
+        profile,
     };
     
     // Lancer le streaming en arrière-plan
@@ -1497,6 +1644,10 @@ pub async fn get_system_stats_v3() -> Result<SystemStats, String> {
 /// Lit les logs en temps réel
 #[tauri::command]
 pub async fn read_logs() -> Result<String, String> {
+    system_logs().map(|logs| redact(&logs))
+}
+
+fn system_logs() -> Result<String, String> {
     // Essayer d'abord journalctl (systèmes modernes avec systemd)
     let journalctl_output = Command::new("journalctl")
         .arg("--no-pager")
@@ -0,0 +1,659 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use serde_yaml::Value;
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::autoscan::ProjectV3;
+use crate::commands::ScriptResult;
+use crate::env::{expand_vars, parse_dotenv};
+use crate::projects_v3::load_projects_v3;
+use crate::scan_report::ScanTrace;
+
+/// Fichiers compose reconnus, dans l'ordre de priorité de `docker compose`
+pub const COMPOSE_FILES: &[&str] = &[
+    "compose.yaml",
+    "compose.yml",
+    "docker-compose.yaml",
+    "docker-compose.yml",
+];
+
+/// Images connues et type de service affiché (préfixe du nom de l'image)
+const KNOWN_IMAGES: &[(&str, &str)] = &[
+    ("mongo", "mongo"),
+    ("postgres", "postgres"),
+    ("postgis", "postgres"),
+    ("redis", "redis"),
+    ("minio", "minio"),
+    ("mysql", "mysql"),
+    ("mariadb", "mariadb"),
+];
+
+/// Lignes de logs renvoyées par défaut
+const DEFAULT_LOG_TAIL: u32 = 200;
+
+/// Port publié sur l'hôte par un service compose
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PublishedPort {
+    pub published: u16,
+    pub target: u16,
+    pub protocol: String,
+}
+
+/// Service déclaré dans un fichier compose
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ComposeService {
+    pub name: String,
+    pub image: Option<String>,
+    /// "mongo", "postgres", "redis", "minio"... d'après l'image
+    pub kind: Option<String>,
+    pub ports: Vec<PublishedPort>,
+}
+
+/// Fichier compose trouvé par l'autoscan
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ComposeFile {
+    pub path: String,
+    pub services: Vec<ComposeService>,
+}
+
+/// Services compose gérés par un projet (`compose` dans projects-v3.json)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ComposeConfig {
+    /// Chemin absolu du fichier compose
+    pub file: String,
+    /// Services lancés avec le projet, tous si vide
+    #[serde(default)]
+    pub services: Vec<String>,
+}
+
+/// État d'un service compose, traduit dans le modèle de statut des services
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ComposeServiceStatus {
+    pub name: String,
+    /// "RUNNING" | "STARTING" | "STOPPED" | "ERROR"
+    pub status: String,
+    /// État du conteneur (`running`, `exited`...), vide s'il n'existe pas
+    pub state: String,
+    pub health: Option<String>,
+    pub ports: Vec<PublishedPort>,
+}
+
+pub fn find_compose_file(root: &Path) -> Option<PathBuf> {
+    COMPOSE_FILES
+        .iter()
+        .map(|name| root.join(name))
+        .find(|path| path.is_file())
+}
+
+fn image_kind(image: &str) -> Option<String> {
+    // registry/org/nom:tag -> nom
+    let name = image.rsplit('/').next().unwrap_or(image);
+    let name = name.split([':', '@']).next().unwrap_or(name);
+    KNOWN_IMAGES
+        .iter()
+        .find(|(prefix, _)| name.starts_with(prefix))
+        .map(|(_, kind)| kind.to_string())
+}
+
+fn yaml_port(value: &Value) -> Option<u16> {
+    match value {
+        Value::Number(n) => n.as_u64().and_then(|n| u16::try_from(n).ok()),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+/// Syntaxe courte : `"8080:80"`, `"127.0.0.1:8080:80/udp"`, `"[::1]:8080:80"`
+///
+/// Un port sans publication (`"80"`, `"127.0.0.1::80"`) ou une plage est ignoré.
+fn parse_short_port(spec: &str) -> Option<PublishedPort> {
+    let (mapping, protocol) = spec.split_once('/').unwrap_or((spec, "tcp"));
+    let (host, target) = mapping.rsplit_once(':')?;
+    let published = host.rsplit(':').next().unwrap_or(host);
+    Some(PublishedPort {
+        published: published.parse().ok()?,
+        target: target.parse().ok()?,
+        protocol: protocol.to_string(),
+    })
+}
+
+fn parse_port(value: &Value) -> Option<PublishedPort> {
+    match value {
+        Value::String(spec) => parse_short_port(spec.trim()),
+        Value::Mapping(_) => Some(PublishedPort {
+            published: yaml_port(value.get("published")?)?,
+            target: yaml_port(value.get("target")?)?,
+            protocol: value
+                .get("protocol")
+                .and_then(Value::as_str)
+                .unwrap_or("tcp")
+                .to_string(),
+        }),
+        // Nombre seul : port du conteneur non publié
+        _ => None,
+    }
+}
+
+/// Services d'un fichier compose (schémas v2 et v3, clé `services`)
+///
+/// Les variables sont résolues comme le fait `docker compose` : environnement
+/// du processus d'abord, puis `env` (le `.env` à côté du fichier).
+pub fn parse_compose(
+    content: &str,
+    env: &[(String, String)],
+) -> Result<Vec<ComposeService>, String> {
+    let lookup = |name: &str| {
+        std::env::var(name).ok().or_else(|| {
+            env.iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.clone())
+        })
+    };
+    let content = expand_vars(content, &lookup);
+    let doc: Value =
+        serde_yaml::from_str(&content).map_err(|e| format!("Invalid compose YAML: {e}"))?;
+
+    let Some(services) = doc.get("services").and_then(Value::as_mapping) else {
+        return Ok(Vec::new());
+    };
+    Ok(services
+        .iter()
+        .filter_map(|(name, service)| {
+            let name = name.as_str()?.to_string();
+            let image = service
+                .get("image")
+                .and_then(Value::as_str)
+                .map(str::to_string);
+            let ports = service
+                .get("ports")
+                .and_then(Value::as_sequence)
+                .map(|ports| ports.iter().filter_map(parse_port).collect())
+                .unwrap_or_default();
+            Some(ComposeService {
+                kind: image.as_deref().and_then(image_kind),
+                name,
+                image,
+                ports,
+            })
+        })
+        .collect())
+}
+
+pub fn read_compose_file(path: &Path) -> Result<ComposeFile, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let env = path
+        .parent()
+        .and_then(|dir| fs::read_to_string(dir.join(".env")).ok())
+        .map(|text| parse_dotenv(&text))
+        .unwrap_or_default();
+    let services =
+        parse_compose(&content, &env).map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(ComposeFile {
+        path: path.to_string_lossy().to_string(),
+        services,
+    })
+}
+
+/// Premier port TCP publié de chaque service, pour `ports.services`
+pub fn compose_ports(services: &[ComposeService]) -> BTreeMap<String, u16> {
+    services
+        .iter()
+        .filter_map(|service| {
+            let port = service.ports.iter().find(|p| p.protocol == "tcp")?;
+            Some((service.name.clone(), port.published))
+        })
+        .collect()
+}
+
+/// Fichier compose de la racine, consigné dans la trace de l'autoscan
+pub(crate) fn detect_compose(
+    root: &Path,
+    trace: &mut ScanTrace,
+    warnings: &mut Vec<String>,
+) -> Option<ComposeFile> {
+    let path = find_compose_file(root)?;
+    let compose = match read_compose_file(&path) {
+        Ok(compose) => compose,
+        Err(e) => {
+            warnings.push(format!("Fichier compose ignoré: {e}"));
+            return None;
+        }
+    };
+
+    let source = Some(compose.path.clone());
+    let config = ComposeConfig {
+        file: compose.path.clone(),
+        services: compose.services.iter().map(|s| s.name.clone()).collect(),
+    };
+    trace.record("compose", &config, source.clone(), "compose", 0.9);
+    let ports = compose_ports(&compose.services);
+    if !ports.is_empty() {
+        trace.record("ports.services", &ports, source, "compose", 0.9);
+    }
+    Some(compose)
+}
+
+/// Statut d'un conteneur dans le modèle des services (RUNNING, STOPPED...)
+fn container_status(state: &str, health: &str, exit_code: i64) -> &'static str {
+    match (state, health) {
+        ("running", "unhealthy") | ("dead", _) => "ERROR",
+        ("running", "starting") | ("restarting", _) => "STARTING",
+        ("running", _) => "RUNNING",
+        ("exited", _) if exit_code != 0 => "ERROR",
+        _ => "STOPPED",
+    }
+}
+
+/// Sortie de `docker compose ps --format json` : un tableau (compose < 2.21)
+/// ou un objet par ligne
+fn parse_ps(output: &str) -> Vec<ComposeServiceStatus> {
+    let entries: Vec<JsonValue> = match serde_json::from_str(output.trim()) {
+        Ok(JsonValue::Array(entries)) => entries,
+        _ => output
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect(),
+    };
+
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let name = entry.get("Service")?.as_str()?.to_string();
+            let state = entry
+                .get("State")
+                .and_then(JsonValue::as_str)
+                .unwrap_or_default()
+                .to_string();
+            let health = entry
+                .get("Health")
+                .and_then(JsonValue::as_str)
+                .filter(|h| !h.is_empty())
+                .map(str::to_string);
+            let exit_code = entry
+                .get("ExitCode")
+                .and_then(JsonValue::as_i64)
+                .unwrap_or(0);
+            let ports = entry
+                .get("Publishers")
+                .and_then(JsonValue::as_array)
+                .map(|publishers| {
+                    publishers
+                        .iter()
+                        .filter_map(|p| {
+                            let published = p.get("PublishedPort")?.as_u64()?;
+                            Some(PublishedPort {
+                                published: u16::try_from(published).ok().filter(|p| *p != 0)?,
+                                target: u16::try_from(p.get("TargetPort")?.as_u64()?).ok()?,
+                                protocol: p
+                                    .get("Protocol")
+                                    .and_then(JsonValue::as_str)
+                                    .unwrap_or("tcp")
+                                    .to_string(),
+                            })
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            Some(ComposeServiceStatus {
+                status: container_status(&state, health.as_deref().unwrap_or(""), exit_code)
+                    .to_string(),
+                name,
+                state,
+                health,
+                ports,
+            })
+        })
+        .collect()
+}
+
+/// Exécute `docker compose -f <fichier>` depuis le dossier du fichier
+pub struct ComposeRunner {
+    file: PathBuf,
+    /// PATH utilisé pour trouver `docker` (celui du processus si `None`)
+    path_env: Option<OsString>,
+}
+
+impl ComposeRunner {
+    pub fn new(file: &Path) -> Self {
+        Self {
+            file: file.to_path_buf(),
+            path_env: None,
+        }
+    }
+
+    fn run(&self, args: &[&str]) -> Result<ScriptResult, String> {
+        let mut cmd = Command::new("docker");
+        if let Some(path) = &self.path_env {
+            cmd.env("PATH", path);
+        }
+        if let Some(dir) = self.file.parent() {
+            cmd.current_dir(dir);
+        }
+        let output = cmd
+            .arg("compose")
+            .arg("-f")
+            .arg(&self.file)
+            .args(args)
+            .output()
+            .map_err(|e| format!("docker introuvable: {e}"))?;
+
+        Ok(ScriptResult {
+            stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+            code: output.status.code().unwrap_or(-1),
+            profile: None,
+        })
+    }
+
+    pub fn up(&self, services: &[String]) -> Result<ScriptResult, String> {
+        let mut args = vec!["up", "-d"];
+        args.extend(services.iter().map(String::as_str));
+        self.run(&args)
+    }
+
+    /// `down` pour tout le fichier ; sinon arrête et supprime les seuls services choisis
+    pub fn down(&self, services: &[String]) -> Result<ScriptResult, String> {
+        if services.is_empty() {
+            return self.run(&["down"]);
+        }
+        let names: Vec<&str> = services.iter().map(String::as_str).collect();
+        let stopped = self.run(&[&["stop"], names.as_slice()].concat())?;
+        if stopped.code != 0 {
+            return Ok(stopped);
+        }
+        self.run(&[&["rm", "-f"], names.as_slice()].concat())
+    }
+
+    /// Statut de chaque service ; un service sans conteneur est STOPPED
+    pub fn ps(&self, services: &[String]) -> Result<Vec<ComposeServiceStatus>, String> {
+        let result = self.run(&["ps", "-a", "--format", "json"])?;
+        if result.code != 0 {
+            return Err(format!(
+                "docker compose ps a échoué: {}",
+                result.stderr.trim()
+            ));
+        }
+        let mut statuses = parse_ps(&result.stdout);
+        if !services.is_empty() {
+            statuses.retain(|s| services.contains(&s.name));
+            for name in services {
+                if !statuses.iter().any(|s| &s.name == name) {
+                    statuses.push(ComposeServiceStatus {
+                        name: name.clone(),
+                        status: "STOPPED".to_string(),
+                        state: String::new(),
+                        health: None,
+                        ports: Vec::new(),
+                    });
+                }
+            }
+        }
+        Ok(statuses)
+    }
+
+    pub fn logs(&self, service: &str, tail: u32) -> Result<ScriptResult, String> {
+        let tail = tail.to_string();
+        self.run(&["logs", "--no-color", "--tail", &tail, service])
+    }
+}
+
+/// Projet, fichier compose et services sélectionnés
+async fn compose_project(project_id: &str) -> Result<(ProjectV3, ComposeConfig), String> {
+    let cfg = load_projects_v3().await?;
+    let project = cfg
+        .projects
+        .into_iter()
+        .find(|p| p.id == project_id)
+        .ok_or_else(|| "Project not found".to_string())?;
+    let compose = project
+        .compose
+        .clone()
+        .ok_or_else(|| format!("Aucun fichier compose configuré pour {}", project_id))?;
+    Ok((project, compose))
+}
+
+fn with_profile(mut result: ScriptResult, project: &ProjectV3) -> ScriptResult {
+    result.profile = project.active_profile.clone();
+    result
+}
+
+/// Statut de l'ensemble des services : une erreur l'emporte, RUNNING quand tous
+/// tournent, STARTING tant qu'une partie seulement a démarré
+fn aggregate_status(statuses: &[ComposeServiceStatus]) -> &'static str {
+    let count = |status: &str| statuses.iter().filter(|s| s.status == status).count();
+    if count("ERROR") > 0 {
+        "ERROR"
+    } else if !statuses.is_empty() && count("RUNNING") == statuses.len() {
+        "RUNNING"
+    } else if count("RUNNING") + count("STARTING") > 0 {
+        "STARTING"
+    } else {
+        "STOPPED"
+    }
+}
+
+/// Statut du service `compose` d'un projet, dans le modèle des services V3
+pub(crate) fn compose_service_status(project: &ProjectV3) -> Result<String, String> {
+    let compose = project
+        .compose
+        .as_ref()
+        .ok_or_else(|| format!("Aucun fichier compose configuré pour {}", project.id))?;
+    let statuses = ComposeRunner::new(Path::new(&compose.file)).ps(&compose.services)?;
+    Ok(aggregate_status(&statuses).to_string())
+}
+
+/// `start`, `stop` ou `restart` du service `compose` d'un projet
+pub(crate) fn run_compose_service(action: &str, project: &ProjectV3) -> ScriptResult {
+    let result = project
+        .compose
+        .as_ref()
+        .ok_or_else(|| format!("Aucun fichier compose configuré pour {}", project.id))
+        .and_then(|compose| {
+            let runner = ComposeRunner::new(Path::new(&compose.file));
+            match action {
+                "start" => runner.up(&compose.services),
+                "stop" => runner.down(&compose.services),
+                "restart" => runner.down(&compose.services).and_then(|stopped| {
+                    if stopped.code != 0 {
+                        return Ok(stopped);
+                    }
+                    runner.up(&compose.services)
+                }),
+                _ => Err(format!("Action inconnue pour compose: {}", action)),
+            }
+        })
+        .unwrap_or_else(|e| ScriptResult {
+            stdout: String::new(),
+            stderr: e,
+            code: 1,
+            profile: None,
+        });
+    with_profile(result, project)
+}
+
+/// Parse le fichier compose d'un dossier (sans rien lancer)
+#[tauri::command]
+pub async fn scan_compose(root_path: String) -> Result<Option<ComposeFile>, String> {
+    find_compose_file(Path::new(&root_path))
+        .map(|path| read_compose_file(&path))
+        .transpose()
+}
+
+/// `docker compose up -d` pour les services choisis (ceux du projet par défaut)
+#[tauri::command]
+pub async fn compose_up(
+    project_id: String,
+    services: Option<Vec<String>>,
+) -> Result<ScriptResult, String> {
+    let (project, compose) = compose_project(&project_id).await?;
+    let services = services.unwrap_or(compose.services);
+    let result = ComposeRunner::new(Path::new(&compose.file)).up(&services)?;
+    Ok(with_profile(result, &project))
+}
+
+#[tauri::command]
+pub async fn compose_down(
+    project_id: String,
+    services: Option<Vec<String>>,
+) -> Result<ScriptResult, String> {
+    let (project, compose) = compose_project(&project_id).await?;
+    let services = services.unwrap_or(compose.services);
+    let result = ComposeRunner::new(Path::new(&compose.file)).down(&services)?;
+    Ok(with_profile(result, &project))
+}
+
+#[tauri::command]
+pub async fn compose_status(project_id: String) -> Result<Vec<ComposeServiceStatus>, String> {
+    let (_, compose) = compose_project(&project_id).await?;
+    ComposeRunner::new(Path::new(&compose.file)).ps(&compose.services)
+}
+
+#[tauri::command]
+pub async fn compose_logs(
+    project_id: String,
+    service: String,
+    tail: Option<u32>,
+) -> Result<ScriptResult, String> {
+    let (project, compose) = compose_project(&project_id).await?;
+    let result = ComposeRunner::new(Path::new(&compose.file))
+        .logs(&service, tail.unwrap_or(DEFAULT_LOG_TAIL))?;
+    Ok(with_profile(result, &project))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_compose() {
+        let content = r#"
+version: "3.8"
+services:
+  mongo:
+    image: mongo:7
+    ports:
+      - "${MONGO_PORT:-27017}:27017"
+  db:
+    image: bitnami/postgresql:16
+    ports:
+      - target: 5432
+        published: "5433"
+  cache:
+    image: redis
+    ports:
+      - "127.0.0.1:6380:6379/tcp"
+      - "6379"
+  s3:
+    image: quay.io/minio/minio@sha256:abc
+    ports: ["9000:9000", "9001:9001/udp"]
+  worker:
+    build: .
+"#;
+        let env = vec![("MONGO_PORT".to_string(), "27018".to_string())];
+        let services = parse_compose(content, &env).unwrap();
+
+        let names: Vec<&str> = services.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["mongo", "db", "cache", "s3", "worker"]);
+        let kinds: Vec<Option<&str>> = services.iter().map(|s| s.kind.as_deref()).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                Some("mongo"),
+                Some("postgres"),
+                Some("redis"),
+                Some("minio"),
+                None
+            ]
+        );
+        assert_eq!(services[2].ports.len(), 1);
+        assert_eq!(services[3].ports[1].protocol, "udp");
+
+        let ports = compose_ports(&services);
+        assert_eq!(ports.get("mongo"), Some(&27018));
+        assert_eq!(ports.get("db"), Some(&5433));
+        assert_eq!(ports.get("cache"), Some(&6380));
+        assert_eq!(ports.get("s3"), Some(&9000));
+        assert_eq!(ports.get("worker"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_runner_with_fake_docker() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("gmdev-compose-{}", uuid::Uuid::new_v4()));
+        let bin = dir.join("bin");
+        fs::create_dir_all(&bin).unwrap();
+        let calls = dir.join("calls.log");
+        let script = format!(
+            r#"#!/bin/sh
+echo "$@" >> "{}"
+case "$*" in
+  *" ps "*)
+    echo '{{"Service":"mongo","State":"running","Health":"healthy","ExitCode":0,"Publishers":[{{"URL":"0.0.0.0","TargetPort":27017,"PublishedPort":27018,"Protocol":"tcp"}}]}}'
+    echo '{{"Service":"redis","State":"exited","Health":"","ExitCode":1,"Publishers":[]}}'
+    ;;
+  *" logs "*) echo "ready" ;;
+esac
+"#,
+            calls.display()
+        );
+        let docker = bin.join("docker");
+        fs::write(&docker, script).unwrap();
+        fs::set_permissions(&docker, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let file = dir.join("docker-compose.yml");
+        let runner = ComposeRunner {
+            file: file.clone(),
+            path_env: Some(bin.into_os_string()),
+        };
+        let services = vec![
+            "mongo".to_string(),
+            "redis".to_string(),
+            "minio".to_string(),
+        ];
+
+        assert_eq!(runner.up(&services[..1]).unwrap().code, 0);
+        runner.down(&services[..1]).unwrap();
+        runner.down(&[]).unwrap();
+        assert_eq!(runner.logs("mongo", 50).unwrap().stdout.trim(), "ready");
+
+        let statuses = runner.ps(&services).unwrap();
+        let summary: Vec<(&str, &str)> = statuses
+            .iter()
+            .map(|s| (s.name.as_str(), s.status.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("mongo", "RUNNING"),
+                ("redis", "ERROR"),
+                ("minio", "STOPPED")
+            ]
+        );
+        assert_eq!(statuses[0].ports[0].published, 27018);
+        assert_eq!(aggregate_status(&statuses), "ERROR");
+        assert_eq!(aggregate_status(&statuses[..1]), "RUNNING");
+        assert_eq!(aggregate_status(&statuses[2..]), "STOPPED");
+
+        let file = file.display();
+        let log = fs::read_to_string(&calls).unwrap();
+        let expected = [
+            format!("compose -f {file} up -d mongo"),
+            format!("compose -f {file} stop mongo"),
+            format!("compose -f {file} rm -f mongo"),
+            format!("compose -f {file} down"),
+            format!("compose -f {file} logs --no-color --tail 50 mongo"),
+            format!("compose -f {file} ps -a --format json"),
+        ];
+        assert_eq!(log.lines().collect::<Vec<_>>(), expected);
+
+        let _ = fs::remove_dir_all(dir);
+    }
+}
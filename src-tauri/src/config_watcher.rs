@@ -0,0 +1,316 @@
+use notify_debouncer_mini::notify::RecursiveMode;
+use notify_debouncer_mini::{new_debouncer, DebounceEventResult};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+use std::sync::{mpsc, Mutex};
+use std::thread;
+use std::time::Duration;
+use tauri::AppHandle;
+
+use crate::autoscan::ProjectV3;
+use crate::commands::{gmdev_service_name, gmdev_service_status};
+use crate::profiles::{affected_services, effective_project};
+use crate::projects_v3::{config_file_path, read_config, validate_config, ProjectConfigV3};
+use crate::secrets::emit_redacted;
+
+/// Délai de regroupement des écritures successives (éditeurs, git pull)
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Différences d'un projet entre deux lectures de la configuration
+///
+/// Les champs sont des chemins pointés (`ports.backend`, `tunnel.host`...).
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct ProjectChange {
+    pub project_id: String,
+    /// "added", "removed" ou "modified"
+    pub change: String,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub modified: Vec<String>,
+    /// Services en cours d'exécution dont la configuration effective a changé
+    pub restart_required: Vec<String>,
+}
+
+/// Payload de l'event `config:changed`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ConfigChangedEvent {
+    pub changes: Vec<ProjectChange>,
+    /// Erreurs de lecture ou de validation de la nouvelle configuration
+    pub errors: Vec<String>,
+}
+
+fn flatten(prefix: &str, value: &Value, out: &mut BTreeMap<String, Value>) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (key, child) in map {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                flatten(&path, child, out);
+            }
+        }
+        Value::Null => {}
+        _ => {
+            out.insert(prefix.to_string(), value.clone());
+        }
+    }
+}
+
+fn flat_fields(project: &ProjectV3) -> BTreeMap<String, Value> {
+    let mut fields = BTreeMap::new();
+    if let Ok(value) = serde_json::to_value(project) {
+        flatten("", &value, &mut fields);
+    }
+    fields
+}
+
+/// Compare deux versions d'un même projet champ par champ
+fn diff_project(before: &ProjectV3, after: &ProjectV3) -> ProjectChange {
+    let before_fields = flat_fields(before);
+    let after_fields = flat_fields(after);
+
+    let mut change = ProjectChange {
+        project_id: after.id.clone(),
+        change: "modified".to_string(),
+        ..Default::default()
+    };
+    for (field, value) in &after_fields {
+        match before_fields.get(field) {
+            None => change.added.push(field.clone()),
+            Some(previous) if previous != value => change.modified.push(field.clone()),
+            _ => {}
+        }
+    }
+    change.removed = before_fields
+        .keys()
+        .filter(|field| !after_fields.contains_key(*field))
+        .cloned()
+        .collect();
+
+    change.restart_required =
+        affected_services(&effective_project(before), &effective_project(after))
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+
+    change
+}
+
+/// Différences entre deux configurations, projet par projet
+///
+/// `restart_required` liste les services concernés, qu'ils tournent ou non.
+pub fn diff_configs(before: &ProjectConfigV3, after: &ProjectConfigV3) -> Vec<ProjectChange> {
+    let mut changes = Vec::new();
+
+    for project in &after.projects {
+        match before.projects.iter().find(|p| p.id == project.id) {
+            Some(previous) => {
+                let change = diff_project(previous, project);
+                if !change.added.is_empty()
+                    || !change.removed.is_empty()
+                    || !change.modified.is_empty()
+                {
+                    changes.push(change);
+                }
+            }
+            None => changes.push(ProjectChange {
+                project_id: project.id.clone(),
+                change: "added".to_string(),
+                ..Default::default()
+            }),
+        }
+    }
+
+    for project in &before.projects {
+        if !after.projects.iter().any(|p| p.id == project.id) {
+            changes.push(ProjectChange {
+                project_id: project.id.clone(),
+                change: "removed".to_string(),
+                ..Default::default()
+            });
+        }
+    }
+
+    changes
+}
+
+/// Fichiers surveillés : projects-v3.json et les manifestes des projets
+fn watched_files(config: &ProjectConfigV3) -> BTreeSet<PathBuf> {
+    let mut files = BTreeSet::from([config_file_path()]);
+    files.extend(
+        config
+            .projects
+            .iter()
+            .filter_map(|p| p.manifest_path.as_ref())
+            .map(PathBuf::from),
+    );
+    files
+}
+
+/// Ne garde que les services qui tournent (statut lu avec l'ancienne configuration) ;
+/// un statut indéterminé laisse le service marqué
+fn keep_running(change: &mut ProjectChange, previous: &ProjectConfigV3) {
+    let Some(project) = previous.projects.iter().find(|p| p.id == change.project_id) else {
+        return;
+    };
+    change.restart_required.retain(|service| {
+        gmdev_service_status(previous, project, gmdev_service_name(service)).as_deref()
+            != Ok("STOPPED")
+    });
+}
+
+/// Dernière configuration connue, `None` tant qu'aucune lecture n'a réussi
+///
+/// Les écritures de l'application la mettent à jour (`record_config_write`) : la
+/// surveillance ne signale ainsi que les modifications faites hors de l'application.
+static SNAPSHOT: Mutex<Option<ProjectConfigV3>> = Mutex::new(None);
+
+fn snapshot() -> Option<ProjectConfigV3> {
+    SNAPSHOT.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Remplace la configuration connue et renvoie la précédente
+fn replace_snapshot(config: ProjectConfigV3) -> Option<ProjectConfigV3> {
+    SNAPSHOT
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .replace(config)
+}
+
+/// Enregistre une écriture de projects-v3.json faite par l'application
+pub fn record_config_write() {
+    if let Ok(config) = read_config() {
+        replace_snapshot(config);
+    }
+}
+
+fn emit_errors(app: &AppHandle, errors: Vec<String>) {
+    emit_redacted(
+        app,
+        "config:changed",
+        &ConfigChangedEvent {
+            changes: Vec::new(),
+            errors,
+        },
+    );
+}
+
+/// Surveille la configuration et émet `config:changed` quand elle est modifiée
+/// hors de l'application (éditeur, `gmdev activate`, git pull d'un manifeste)
+pub fn start_config_watcher(app: AppHandle) {
+    thread::spawn(move || {
+        let (tx, rx) = mpsc::channel::<DebounceEventResult>();
+        let mut debouncer = match new_debouncer(DEBOUNCE, tx) {
+            Ok(debouncer) => debouncer,
+            Err(e) => {
+                emit_errors(
+                    &app,
+                    vec![format!(
+                        "Surveillance de la configuration indisponible: {e}"
+                    )],
+                );
+                return;
+            }
+        };
+
+        // Une configuration illisible au démarrage ne sert pas de référence : la
+        // première lecture valide devient la référence, sans signaler de changement
+        match read_config() {
+            Ok(config) => {
+                replace_snapshot(config);
+            }
+            Err(e) => emit_errors(&app, vec![e]),
+        }
+        let mut dirs: BTreeSet<PathBuf> = BTreeSet::new();
+
+        loop {
+            // Les dossiers parents sont surveillés : les éditeurs remplacent le fichier
+            let files = watched_files(&snapshot().unwrap_or_default());
+            for dir in files.iter().filter_map(|f| f.parent()) {
+                if !dirs.contains(dir)
+                    && debouncer
+                        .watcher()
+                        .watch(dir, RecursiveMode::NonRecursive)
+                        .is_ok()
+                {
+                    dirs.insert(dir.to_path_buf());
+                }
+            }
+
+            let Ok(result) = rx.recv() else {
+                return;
+            };
+            let Ok(events) = result else {
+                continue;
+            };
+            let files = watched_files(&snapshot().unwrap_or_default());
+            if !events.iter().any(|e| files.contains(&e.path)) {
+                continue;
+            }
+
+            let config = match read_config() {
+                Ok(config) => config,
+                Err(e) => {
+                    emit_errors(&app, vec![e]);
+                    continue;
+                }
+            };
+            let errors: Vec<String> = validate_config(&config).err().into_iter().collect();
+            let Some(previous) = replace_snapshot(config.clone()) else {
+                if !errors.is_empty() {
+                    emit_errors(&app, errors);
+                }
+                continue;
+            };
+
+            // Une écriture de l'application a déjà mis la référence à jour : rien à signaler
+            let mut changes = diff_configs(&previous, &config);
+            for change in changes.iter_mut() {
+                keep_running(change, &previous);
+            }
+
+            if !changes.is_empty() || !errors.is_empty() {
+                emit_redacted(
+                    &app,
+                    "config:changed",
+                    &ConfigChangedEvent { changes, errors },
+                );
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::autoscan::{test_project, ProjectCommands};
+
+    #[test]
+    fn test_diff_configs() {
+        let before = ProjectConfigV3 {
+            projects: vec![test_project("ops"), test_project("old")],
+        };
+        let mut ops = test_project("ops");
+        ops.name = "Ops".to_string();
+        ops.commands = Some(ProjectCommands {
+            frontend: Some("pnpm dev".to_string()),
+            ..Default::default()
+        });
+        let after = ProjectConfigV3 {
+            projects: vec![ops, test_project("new")],
+        };
+
+        let changes = diff_configs(&before, &after);
+        assert_eq!(changes.len(), 3);
+        assert_eq!(changes[0].project_id, "ops");
+        assert_eq!(changes[0].modified, vec!["name"]);
+        assert_eq!(changes[0].added, vec!["commands.frontend"]);
+        assert_eq!(changes[0].restart_required, vec!["frontend"]);
+        assert_eq!(changes[1].change, "added");
+        assert_eq!(changes[2].change, "removed");
+    }
+}
@@ -0,0 +1,457 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use std::thread;
+use tauri::AppHandle;
+
+use crate::autoscan::ProjectV3;
+use crate::commands::ScriptResult;
+use crate::gmd::{GmdLogEvent, GmdRunId};
+use crate::package_json::{
+    detect_package_manager, find_lockfile, read_package_json, PackageManager,
+};
+use crate::projects_v3::{config_dir, read_config};
+use crate::secrets::emit_redacted;
+use crate::settings::read_settings;
+
+/// Empreintes des lockfiles au moment de la dernière installation, par dossier
+const RECORDS_FILE_NAME: &str = "dependencies.json";
+
+/// Sérialise les lectures-écritures de dependencies.json (installations en parallèle)
+static RECORDS_LOCK: Mutex<()> = Mutex::new(());
+
+/// Installation réussie d'un dossier
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct InstallRecord {
+    /// Lockfile (ou package.json sans lockfile) dont l'empreinte est gardée
+    pub file: String,
+    pub hash: String,
+    pub package_manager: PackageManager,
+    pub installed_at: String,
+}
+
+type InstallRecords = BTreeMap<String, InstallRecord>;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum DependencyState {
+    UpToDate,
+    /// Le lockfile a changé depuis la dernière installation
+    Outdated,
+    /// Pas de node_modules
+    Missing,
+    /// Installé hors du devcenter : l'empreinte actuelle sert de référence
+    Untracked,
+}
+
+/// État des dépendances d'un service
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DependencyStatus {
+    pub service: String,
+    /// Dossier où lancer l'installation (racine du monorepo si le lockfile y est)
+    pub dir: String,
+    pub file: String,
+    pub package_manager: PackageManager,
+    pub state: DependencyState,
+    pub installed_at: Option<String>,
+}
+
+impl DependencyStatus {
+    pub fn needs_install(&self) -> bool {
+        matches!(
+            self.state,
+            DependencyState::Outdated | DependencyState::Missing
+        )
+    }
+}
+
+/// Payload de l'event deps:exit
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DepsExitEvent {
+    pub run_id: String,
+    pub exit_code: i32,
+    /// État après l'installation
+    pub status: Option<DependencyStatus>,
+    /// Installation réussie mais empreinte non enregistrée
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+fn records_path() -> PathBuf {
+    config_dir().join(RECORDS_FILE_NAME)
+}
+
+fn read_records() -> InstallRecords {
+    fs::read_to_string(records_path())
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+fn hash_file(path: &Path) -> Result<String, String> {
+    let content =
+        fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    Ok(format!("{:x}", Sha256::digest(&content)))
+}
+
+/// `file` modifié après `dir` (node_modules réécrit à chaque installation)
+fn modified_after(file: &Path, dir: &Path) -> bool {
+    let modified = |path: &Path| fs::metadata(path).and_then(|m| m.modified()).ok();
+    matches!((modified(file), modified(dir)), (Some(file), Some(dir)) if file > dir)
+}
+
+/// Dossier d'installation et fichier suivi d'un service Node (`None` sans package.json)
+fn install_target(service_dir: &Path) -> Option<(PathBuf, PathBuf)> {
+    if !service_dir.join("package.json").exists() {
+        return None;
+    }
+    match find_lockfile(service_dir) {
+        Some(lockfile) => Some((lockfile.parent()?.to_path_buf(), lockfile)),
+        None => Some((service_dir.to_path_buf(), service_dir.join("package.json"))),
+    }
+}
+
+fn package_manager(dir: &Path) -> PackageManager {
+    let package = read_package_json(dir).ok().flatten();
+    detect_package_manager(dir, package.as_ref())
+}
+
+/// Compare l'empreinte actuelle du lockfile à celle de la dernière installation ;
+/// sans empreinte, un lockfile plus récent que node_modules le rend périmé
+pub fn dependency_status(
+    service: &str,
+    service_dir: &Path,
+    records: &InstallRecords,
+) -> Option<DependencyStatus> {
+    let (dir, file) = install_target(service_dir)?;
+    let key = dir.to_string_lossy().to_string();
+    let record = records.get(&key);
+    let state = if !dir.join("node_modules").is_dir() {
+        DependencyState::Missing
+    } else {
+        match record {
+            None if modified_after(&file, &dir.join("node_modules")) => DependencyState::Outdated,
+            None => DependencyState::Untracked,
+            Some(record) if hash_file(&file).ok().as_ref() == Some(&record.hash) => {
+                DependencyState::UpToDate
+            }
+            Some(_) => DependencyState::Outdated,
+        }
+    };
+    Some(DependencyStatus {
+        service: service.to_string(),
+        file: file.to_string_lossy().to_string(),
+        package_manager: package_manager(&dir),
+        state,
+        installed_at: record.map(|r| r.installed_at.clone()),
+        dir: key,
+    })
+}
+
+/// Enregistre l'empreinte du lockfile d'un dossier qui vient d'être installé
+pub fn record_install(dir: &Path, file: &Path) -> Result<(), String> {
+    let record = InstallRecord {
+        file: file.to_string_lossy().to_string(),
+        hash: hash_file(file)?,
+        package_manager: package_manager(dir),
+        installed_at: chrono::Utc::now().to_rfc3339(),
+    };
+
+    let _guard = RECORDS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut records = read_records();
+    records.insert(dir.to_string_lossy().to_string(), record);
+    let text = serde_json::to_string_pretty(&records)
+        .map_err(|e| format!("Failed to serialize dependencies: {e}"))?;
+    fs::write(records_path(), text).map_err(|e| format!("Failed to write dependencies: {e}"))
+}
+
+fn install_command(dir: &Path) -> Command {
+    let mut command = Command::new(package_manager(dir).program());
+    command.arg("install").current_dir(dir);
+    command
+}
+
+/// Installe les dépendances d'un service et attend la fin ; l'empreinte est enregistrée en cas de succès
+pub fn install_blocking(status: &DependencyStatus) -> ScriptResult {
+    let dir = Path::new(&status.dir);
+    let mut result = match install_command(dir).output() {
+        Ok(output) => ScriptResult {
+            stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+            code: output.status.code().unwrap_or(-1),
+            profile: None,
+        },
+        Err(e) => ScriptResult {
+            stdout: String::new(),
+            stderr: format!(
+                "Failed to execute {}: {}",
+                package_manager(dir).program(),
+                e
+            ),
+            code: -1,
+            profile: None,
+        },
+    };
+    if result.code == 0 {
+        if let Err(e) = record_install(dir, Path::new(&status.file)) {
+            result.stderr.push_str(&format!("\n{}", e));
+        }
+    }
+    result
+}
+
+fn service_dirs(project: &ProjectV3) -> Vec<(&'static str, &str)> {
+    [
+        ("backend", project.backend_path.as_str()),
+        ("frontend", project.frontend_path.as_str()),
+    ]
+    .into_iter()
+    .filter(|(_, path)| !path.trim().is_empty())
+    .collect()
+}
+
+/// État des dépendances des services Node d'un projet
+///
+/// Une installation faite hors du devcenter est enregistrée comme référence la
+/// première fois qu'elle est vue : un changement ultérieur du lockfile la périme.
+pub fn project_dependencies(project: &ProjectV3) -> Vec<DependencyStatus> {
+    let records = read_records();
+    let statuses: Vec<DependencyStatus> = service_dirs(project)
+        .into_iter()
+        .filter_map(|(service, dir)| dependency_status(service, Path::new(dir), &records))
+        .collect();
+    for status in &statuses {
+        if status.state == DependencyState::Untracked {
+            // En cas d'échec, la référence est retentée au prochain contrôle
+            let _ = record_install(Path::new(&status.dir), Path::new(&status.file));
+        }
+    }
+    statuses
+}
+
+/// Installe les dépendances manquantes ou périmées, une fois par dossier d'installation
+pub fn install_stale(project: &ProjectV3) -> (Vec<String>, Vec<ScriptResult>) {
+    let mut installed: Vec<String> = Vec::new();
+    let mut results = Vec::new();
+    for status in project_dependencies(project) {
+        if status.needs_install() && !installed.contains(&status.dir) {
+            results.push(install_blocking(&status));
+            installed.push(status.dir);
+        }
+    }
+    (installed, results)
+}
+
+/// Vérifie les dépendances d'un service avant son démarrage
+///
+/// Périmées : installation automatique si le réglage est actif, sinon event `deps:outdated`.
+pub(crate) fn check_before_start(
+    app: &AppHandle,
+    project: &ProjectV3,
+    service: &str,
+) -> Result<(), String> {
+    let Some(status) = project_dependencies(project)
+        .into_iter()
+        .find(|s| s.service == service)
+        .filter(DependencyStatus::needs_install)
+    else {
+        return Ok(());
+    };
+
+    if !read_settings()?.auto_install_dependencies {
+        emit_redacted(app, "deps:outdated", &status);
+        return Ok(());
+    }
+    let result = install_blocking(&status);
+    if result.code != 0 {
+        return Err(format!(
+            "Installation des dépendances échouée dans {}: {}",
+            status.dir,
+            result.stderr.trim()
+        ));
+    }
+    Ok(())
+}
+
+fn stream_lines<R: Read + Send + 'static>(
+    reader: R,
+    level: &'static str,
+    app: AppHandle,
+    run_id: String,
+    cmd: String,
+    cwd: String,
+) -> thread::JoinHandle<()> {
+    thread::spawn(move || {
+        for line in BufReader::new(reader).lines().map_while(Result::ok) {
+            let event = GmdLogEvent {
+                run_id: run_id.clone(),
+                ts: chrono::Utc::now().to_rfc3339(),
+                level: level.to_string(),
+                line,
+                cmd: cmd.clone(),
+                cwd: cwd.clone(),
+                profile: None,
+            };
+            emit_redacted(&app, "deps:log", &event);
+        }
+    })
+}
+
+/// État des dépendances des services Node d'un projet
+#[tauri::command]
+pub async fn check_dependencies(project_id: String) -> Result<Vec<DependencyStatus>, String> {
+    let cfg = read_config()?;
+    let project = cfg
+        .projects
+        .iter()
+        .find(|p| p.id == project_id)
+        .ok_or_else(|| "Project not found".to_string())?;
+    Ok(project_dependencies(project))
+}
+
+/// Lance l'installation des dépendances d'un service avec son gestionnaire de paquets
+///
+/// Comme `run_gmd_command` : renvoie un `runId`, puis émet `deps:log` pour chaque
+/// ligne et `deps:exit` à la fin, avec l'état mis à jour.
+#[tauri::command]
+pub async fn install_dependencies(
+    app: AppHandle,
+    project_id: String,
+    service: String,
+) -> Result<GmdRunId, String> {
+    let cfg = read_config()?;
+    let project = cfg
+        .projects
+        .iter()
+        .find(|p| p.id == project_id)
+        .ok_or_else(|| "Project not found".to_string())?;
+    let service_dir = service_dirs(project)
+        .into_iter()
+        .find(|(name, _)| *name == service)
+        .map(|(_, dir)| PathBuf::from(dir))
+        .ok_or_else(|| format!("Service inconnu: {}", service))?;
+    let (dir, file) = install_target(&service_dir)
+        .ok_or_else(|| format!("Pas de package.json dans {}", service_dir.display()))?;
+
+    let manager = package_manager(&dir);
+    let mut child = install_command(&dir)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to execute {}: {}", manager.program(), e))?;
+
+    let run_id = format!("deps-{}", uuid::Uuid::new_v4());
+    let cmd = format!("{} install", manager.program());
+    let cwd = dir.to_string_lossy().to_string();
+    let readers = [
+        child.stdout.take().map(|out| {
+            stream_lines(
+                out,
+                "stdout",
+                app.clone(),
+                run_id.clone(),
+                cmd.clone(),
+                cwd.clone(),
+            )
+        }),
+        child.stderr.take().map(|err| {
+            stream_lines(
+                err,
+                "stderr",
+                app.clone(),
+                run_id.clone(),
+                cmd.clone(),
+                cwd.clone(),
+            )
+        }),
+    ];
+
+    let exit_run_id = run_id.clone();
+    thread::spawn(move || {
+        let exit_code = child.wait().map_or(-1, |s| s.code().unwrap_or(-1));
+        for reader in readers.into_iter().flatten() {
+            let _ = reader.join();
+        }
+        let error = if exit_code == 0 {
+            record_install(&dir, &file).err()
+        } else {
+            None
+        };
+        let event = DepsExitEvent {
+            run_id: exit_run_id,
+            exit_code,
+            status: dependency_status(&service, &service_dir, &read_records()),
+            error,
+        };
+        emit_redacted(&app, "deps:exit", &event);
+    });
+
+    Ok(GmdRunId { run_id })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dependency_status() {
+        let root = std::env::temp_dir().join(format!("gmdev-deps-{}", uuid::Uuid::new_v4()));
+        let web = root.join("apps/web");
+        fs::create_dir_all(&web).unwrap();
+        fs::create_dir_all(root.join(".git")).unwrap();
+        fs::write(web.join("package.json"), "{}").unwrap();
+        fs::write(root.join("pnpm-lock.yaml"), "lockfileVersion: 9").unwrap();
+
+        // Le lockfile du monorepo fait installer à la racine
+        let mut records = InstallRecords::new();
+        let status = dependency_status("frontend", &web, &records).unwrap();
+        assert_eq!(status.dir, root.to_string_lossy());
+        assert_eq!(status.package_manager, PackageManager::Pnpm);
+        assert_eq!(status.state, DependencyState::Missing);
+
+        fs::create_dir_all(root.join("node_modules")).unwrap();
+        assert_eq!(
+            dependency_status("frontend", &web, &records).unwrap().state,
+            DependencyState::Untracked
+        );
+        // Lockfile modifié après l'installation faite hors du devcenter
+        fs::File::options()
+            .write(true)
+            .open(root.join("pnpm-lock.yaml"))
+            .unwrap()
+            .set_modified(std::time::SystemTime::now() + std::time::Duration::from_secs(60))
+            .unwrap();
+        assert_eq!(
+            dependency_status("frontend", &web, &records).unwrap().state,
+            DependencyState::Outdated
+        );
+
+        let key = root.to_string_lossy().to_string();
+        records.insert(
+            key,
+            InstallRecord {
+                file: status.file.clone(),
+                hash: hash_file(&root.join("pnpm-lock.yaml")).unwrap(),
+                package_manager: PackageManager::Pnpm,
+                installed_at: String::new(),
+            },
+        );
+        let status = dependency_status("frontend", &web, &records).unwrap();
+        assert_eq!(status.state, DependencyState::UpToDate);
+        assert!(!status.needs_install());
+
+        fs::write(root.join("pnpm-lock.yaml"), "lockfileVersion: 9\nnext: 15").unwrap();
+        let status = dependency_status("frontend", &web, &records).unwrap();
+        assert_eq!(status.state, DependencyState::Outdated);
+        assert!(status.needs_install());
+
+        assert!(dependency_status("backend", &root.join("apps"), &records).is_none());
+        let _ = fs::remove_dir_all(root);
+    }
+}
@@ -0,0 +1,636 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::package_json::{
+    read_package_json, script_commands, PackageJson, PackageManager, ScriptCommands,
+};
+
+/// Confiance minimale pour qu'une détection soit retenue par l'autoscan
+pub const MIN_CONFIDENCE: f32 = 0.5;
+
+/// Rôle d'un service dans un projet
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ServiceRole {
+    #[default]
+    Backend,
+    Frontend,
+}
+
+/// Endroit où lire le port d'un service, par ordre de priorité
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum PortSource {
+    /// Clés lues dans `.env.local` puis `.env`
+    Env { keys: Vec<String> },
+    /// Premier groupe capturé par `pattern` dans `file` (commentaires JS / TS ignorés)
+    File { file: String, pattern: String },
+    /// Options `-p` / `--port` ou préfixe `PORT=` des scripts de package.json
+    Scripts,
+    /// Port par défaut de la stack
+    Default { port: u16 },
+}
+
+/// Résultat d'un détecteur pour un dossier
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Detection {
+    /// Identifiant de la stack ("payload", "next", "django"...)
+    pub stack: String,
+    pub label: String,
+    pub role: ServiceRole,
+    /// Entre 0 et 1
+    pub confidence: f32,
+    /// Script `dev` du package.json via le bon gestionnaire, sinon commande usuelle de la stack
+    pub dev_command: String,
+    pub build_command: Option<String>,
+    pub start_command: Option<String>,
+    pub package_manager: Option<PackageManager>,
+    pub port_sources: Vec<PortSource>,
+    pub health_endpoint: Option<String>,
+}
+
+/// Écosystème dont le fichier de dépendances est lu
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Ecosystem {
+    #[default]
+    Node,
+    Python,
+    Ruby,
+    Go,
+}
+
+/// Fichiers d'un dossier lus une seule fois pour tous les détecteurs
+pub struct ProjectFiles {
+    pub dir: PathBuf,
+    package: Option<PackageJson>,
+    commands: ScriptCommands,
+    python: Option<String>,
+    gemfile: Option<String>,
+    go_mod: Option<String>,
+}
+
+impl ProjectFiles {
+    pub fn load(dir: &Path) -> Self {
+        let read = |name: &str| fs::read_to_string(dir.join(name)).ok();
+
+        // Un package.json illisible ne doit pas faire échouer tout le scan
+        let package = read_package_json(dir).ok().flatten();
+        let commands = package
+            .as_ref()
+            .map(|p| script_commands(dir, p))
+            .unwrap_or_default();
+
+        let python = match (read("pyproject.toml"), read("requirements.txt")) {
+            (None, None) => None,
+            (a, b) => Some(format!(
+                "{}\n{}",
+                a.unwrap_or_default(),
+                b.unwrap_or_default()
+            )),
+        };
+
+        Self {
+            dir: dir.to_path_buf(),
+            package,
+            commands,
+            python: python.map(|p| p.to_lowercase()),
+            gemfile: read("Gemfile"),
+            go_mod: read("go.mod"),
+        }
+    }
+
+    pub fn has_file(&self, relative: &str) -> bool {
+        self.dir.join(relative).exists()
+    }
+
+    pub fn has_manifest(&self, ecosystem: Ecosystem) -> bool {
+        match ecosystem {
+            Ecosystem::Node => self.package.is_some(),
+            Ecosystem::Python => self.python.is_some(),
+            Ecosystem::Ruby => self.gemfile.is_some(),
+            Ecosystem::Go => self.go_mod.is_some(),
+        }
+    }
+
+    /// Dépendance déclarée dans le fichier de l'écosystème
+    pub fn has_dependency(&self, ecosystem: Ecosystem, name: &str) -> bool {
+        match ecosystem {
+            Ecosystem::Node => self
+                .package
+                .as_ref()
+                .is_some_and(|p| p.has_dependency(name)),
+            Ecosystem::Python => self
+                .python
+                .as_deref()
+                .is_some_and(|text| contains_word(text, &name.to_lowercase())),
+            Ecosystem::Ruby => self.gemfile.as_deref().is_some_and(|text| {
+                text.contains(&format!("gem \"{}\"", name))
+                    || text.contains(&format!("gem '{}'", name))
+            }),
+            Ecosystem::Go => self
+                .go_mod
+                .as_deref()
+                .is_some_and(|text| text.contains(name)),
+        }
+    }
+
+    /// Un script package.json invoque `cli`
+    pub fn script_uses(&self, cli: &str) -> bool {
+        self.package.as_ref().is_some_and(|p| {
+            p.scripts
+                .values()
+                .any(|command| command.split_whitespace().any(|word| word == cli))
+        })
+    }
+}
+
+/// `word` apparaît comme nom de paquet complet (pas `django-environ` pour `django`...)
+fn contains_word(text: &str, word: &str) -> bool {
+    let is_name_char = |c: char| c.is_ascii_alphanumeric() || c == '_' || c == '-';
+    text.match_indices(word).any(|(pos, _)| {
+        let before = text[..pos].chars().next_back();
+        let after = text[pos + word.len()..].chars().next();
+        !before.is_some_and(is_name_char) && !after.is_some_and(is_name_char)
+    })
+}
+
+/// Détecteur d'une stack : implémenter ce trait et l'enregistrer dans un
+/// [`DetectorRegistry`] suffit pour que l'autoscan la reconnaisse
+pub trait Detector: Send + Sync {
+    fn stack(&self) -> &str;
+    fn role(&self) -> ServiceRole;
+    fn port_sources(&self) -> Vec<PortSource>;
+    /// `None` si aucun indice de la stack n'est présent
+    fn detect(&self, files: &ProjectFiles) -> Option<Detection>;
+}
+
+/// Détecteur déclaratif : dépendances, fichiers de configuration et script de dev
+#[derive(Debug, Clone, Default)]
+pub struct StackDetector {
+    pub stack: &'static str,
+    pub label: &'static str,
+    pub role: ServiceRole,
+    pub ecosystem: Ecosystem,
+    /// Au moins une de ces dépendances (vide: le fichier de l'écosystème suffit)
+    pub packages: &'static [&'static str],
+    /// Dépendances toutes requises en plus
+    pub requires: &'static [&'static str],
+    /// Une dépendance de `packages` est indispensable : les fichiers seuls ne suffisent pas
+    pub package_required: bool,
+    /// Fichiers propres à la stack
+    pub markers: &'static [&'static str],
+    /// Binaire appelé par les scripts package.json
+    pub cli: Option<&'static str>,
+    pub dev_command: &'static str,
+    pub port_sources: Vec<PortSource>,
+    pub health_endpoint: Option<&'static str>,
+}
+
+impl Detector for StackDetector {
+    fn stack(&self) -> &str {
+        self.stack
+    }
+
+    fn role(&self) -> ServiceRole {
+        self.role
+    }
+
+    fn port_sources(&self) -> Vec<PortSource> {
+        self.port_sources.clone()
+    }
+
+    fn detect(&self, files: &ProjectFiles) -> Option<Detection> {
+        if !files.has_manifest(self.ecosystem) {
+            return None;
+        }
+        if !self
+            .requires
+            .iter()
+            .all(|dep| files.has_dependency(self.ecosystem, dep))
+        {
+            return None;
+        }
+
+        let has_marker = self.markers.iter().any(|m| files.has_file(m));
+        let mut confidence: f32 = if self.packages.is_empty() {
+            0.5
+        } else if self
+            .packages
+            .iter()
+            .any(|dep| files.has_dependency(self.ecosystem, dep))
+        {
+            0.6
+        } else if has_marker && !self.package_required {
+            // Fichier de configuration sans la dépendance (déclarée à la racine d'un monorepo...)
+            0.2
+        } else {
+            return None;
+        };
+        if has_marker {
+            confidence += 0.3;
+        }
+        if self.cli.is_some_and(|cli| files.script_uses(cli)) {
+            confidence += 0.1;
+        }
+
+        let node = (self.ecosystem == Ecosystem::Node).then_some(&files.commands);
+        Some(Detection {
+            stack: self.stack.to_string(),
+            label: self.label.to_string(),
+            role: self.role,
+            confidence: confidence.min(1.0),
+            dev_command: node
+                .and_then(|c| c.dev.clone())
+                .unwrap_or_else(|| self.dev_command.to_string()),
+            build_command: node.and_then(|c| c.build.clone()),
+            start_command: node.and_then(|c| c.start.clone()),
+            package_manager: node.map(|c| c.package_manager),
+            port_sources: self.port_sources.clone(),
+            health_endpoint: self.health_endpoint.map(str::to_string),
+        })
+    }
+}
+
+fn env(keys: &[&str]) -> PortSource {
+    PortSource::Env {
+        keys: keys.iter().map(|k| k.to_string()).collect(),
+    }
+}
+
+fn file(file: &str, pattern: &str) -> PortSource {
+    PortSource::File {
+        file: file.to_string(),
+        pattern: pattern.to_string(),
+    }
+}
+
+fn config_files(files: &[&str], pattern: &str) -> Vec<PortSource> {
+    files.iter().map(|f| file(f, pattern)).collect()
+}
+
+fn default_port(port: u16) -> PortSource {
+    PortSource::Default { port }
+}
+
+/// Ensemble des détecteurs consultés par l'autoscan
+pub struct DetectorRegistry {
+    detectors: Vec<Box<dyn Detector>>,
+}
+
+impl DetectorRegistry {
+    pub fn new() -> Self {
+        Self {
+            detectors: Vec::new(),
+        }
+    }
+
+    pub fn register(&mut self, detector: Box<dyn Detector>) {
+        self.detectors.push(detector);
+    }
+
+    /// Registre avec toutes les stacks connues
+    pub fn with_defaults() -> Self {
+        use Ecosystem::*;
+        use ServiceRole::*;
+
+        let stacks = vec![
+            StackDetector {
+                stack: "payload",
+                label: "Payload CMS",
+                packages: &["payload", "@payloadcms/next"],
+                markers: &[
+                    "payload.config.ts",
+                    "payload.config.js",
+                    "src/payload.config.ts",
+                ],
+                cli: Some("payload"),
+                dev_command: "npm run dev",
+                port_sources: [
+                    vec![
+                        env(&["PORT", "BACKEND_PORT", "PAYLOAD_PORT"]),
+                        PortSource::Scripts,
+                    ],
+                    config_files(
+                        &[
+                            "payload.config.ts",
+                            "payload.config.js",
+                            "src/payload.config.ts",
+                        ],
+                        r"serverURL[^,\n]*:(\d+)",
+                    ),
+                    vec![default_port(3010)],
+                ]
+                .concat(),
+                health_endpoint: Some("/admin"),
+                ..Default::default()
+            },
+            StackDetector {
+                stack: "directus",
+                label: "Directus",
+                packages: &["directus", "@directus/api"],
+                markers: &["extensions", "snapshot.yaml"],
+                cli: Some("directus"),
+                dev_command: "npx directus start",
+                port_sources: vec![env(&["PORT"]), default_port(8055)],
+                health_endpoint: Some("/server/health"),
+                ..Default::default()
+            },
+            StackDetector {
+                stack: "strapi",
+                label: "Strapi",
+                packages: &["@strapi/strapi"],
+                markers: &["config/server.js", "config/server.ts"],
+                cli: Some("strapi"),
+                dev_command: "npm run develop",
+                port_sources: vec![env(&["PORT"]), PortSource::Scripts, default_port(1337)],
+                health_endpoint: Some("/_health"),
+                ..Default::default()
+            },
+            StackDetector {
+                stack: "nestjs",
+                label: "NestJS",
+                packages: &["@nestjs/core"],
+                markers: &["nest-cli.json"],
+                cli: Some("nest"),
+                dev_command: "npm run start:dev",
+                port_sources: vec![env(&["PORT"]), PortSource::Scripts, default_port(3000)],
+                ..Default::default()
+            },
+            StackDetector {
+                stack: "express",
+                label: "Express",
+                packages: &["express"],
+                cli: Some("nodemon"),
+                dev_command: "npm run dev",
+                port_sources: vec![env(&["PORT"]), PortSource::Scripts, default_port(3000)],
+                ..Default::default()
+            },
+            StackDetector {
+                stack: "next",
+                label: "Next.js",
+                role: Frontend,
+                packages: &["next"],
+                markers: &["next.config.js", "next.config.mjs", "next.config.ts"],
+                cli: Some("next"),
+                dev_command: "npm run dev",
+                port_sources: [
+                    vec![
+                        env(&["PORT", "NEXT_PORT", "FRONTEND_PORT"]),
+                        PortSource::Scripts,
+                    ],
+                    config_files(
+                        &["next.config.js", "next.config.mjs", "next.config.ts"],
+                        r"port:\s*(\d+)",
+                    ),
+                    vec![default_port(3000)],
+                ]
+                .concat(),
+                health_endpoint: Some("/"),
+                ..Default::default()
+            },
+            StackDetector {
+                stack: "nuxt",
+                label: "Nuxt",
+                role: Frontend,
+                packages: &["nuxt"],
+                markers: &["nuxt.config.ts", "nuxt.config.js"],
+                cli: Some("nuxt"),
+                dev_command: "npm run dev",
+                port_sources: vec![
+                    env(&["PORT", "NUXT_PORT"]),
+                    PortSource::Scripts,
+                    default_port(3000),
+                ],
+                health_endpoint: Some("/"),
+                ..Default::default()
+            },
+            StackDetector {
+                stack: "vite-react",
+                label: "Vite + React",
+                role: Frontend,
+                packages: &["vite"],
+                requires: &["react"],
+                markers: &["vite.config.ts", "vite.config.js"],
+                cli: Some("vite"),
+                dev_command: "npm run dev",
+                port_sources: vec![
+                    PortSource::Scripts,
+                    file("vite.config.ts", r"port:\s*(\d+)"),
+                    file("vite.config.js", r"port:\s*(\d+)"),
+                    default_port(5173),
+                ],
+                health_endpoint: Some("/"),
+                ..Default::default()
+            },
+            StackDetector {
+                stack: "astro",
+                label: "Astro",
+                role: Frontend,
+                packages: &["astro"],
+                markers: &["astro.config.mjs", "astro.config.ts"],
+                cli: Some("astro"),
+                dev_command: "npm run dev",
+                port_sources: vec![
+                    PortSource::Scripts,
+                    file("astro.config.mjs", r"port:\s*(\d+)"),
+                    default_port(4321),
+                ],
+                health_endpoint: Some("/"),
+                ..Default::default()
+            },
+            StackDetector {
+                stack: "sveltekit",
+                label: "SvelteKit",
+                role: Frontend,
+                packages: &["@sveltejs/kit"],
+                markers: &["svelte.config.js"],
+                cli: Some("vite"),
+                dev_command: "npm run dev",
+                port_sources: vec![
+                    PortSource::Scripts,
+                    file("vite.config.ts", r"port:\s*(\d+)"),
+                    default_port(5173),
+                ],
+                health_endpoint: Some("/"),
+                ..Default::default()
+            },
+            StackDetector {
+                stack: "django",
+                label: "Django",
+                ecosystem: Python,
+                packages: &["django"],
+                markers: &["manage.py"],
+                dev_command: "python manage.py runserver",
+                port_sources: vec![env(&["PORT", "DJANGO_PORT"]), default_port(8000)],
+                health_endpoint: Some("/admin/login/"),
+                ..Default::default()
+            },
+            StackDetector {
+                stack: "fastapi",
+                label: "FastAPI",
+                ecosystem: Python,
+                packages: &["fastapi"],
+                markers: &["main.py", "app/main.py"],
+                dev_command: "uvicorn main:app --reload",
+                port_sources: vec![env(&["PORT", "UVICORN_PORT"]), default_port(8000)],
+                health_endpoint: Some("/docs"),
+                ..Default::default()
+            },
+            StackDetector {
+                stack: "rails",
+                label: "Ruby on Rails",
+                ecosystem: Ruby,
+                packages: &["rails"],
+                markers: &["config/application.rb", "bin/rails"],
+                dev_command: "bin/rails server",
+                port_sources: vec![env(&["PORT"]), default_port(3000)],
+                health_endpoint: Some("/up"),
+                ..Default::default()
+            },
+            StackDetector {
+                stack: "go",
+                label: "Go",
+                ecosystem: Go,
+                // Un go.mod seul est aussi celui d'une bibliothèque ou d'un outil en ligne de commande
+                packages: &[
+                    "github.com/gin-gonic/gin",
+                    "github.com/labstack/echo",
+                    "github.com/gofiber/fiber",
+                    "github.com/go-chi/chi",
+                    "github.com/gorilla/mux",
+                ],
+                package_required: true,
+                markers: &["main.go", "cmd"],
+                dev_command: "go run .",
+                port_sources: vec![env(&["PORT"]), default_port(8080)],
+                ..Default::default()
+            },
+        ];
+
+        let mut registry = Self::new();
+        for stack in stacks {
+            registry.register(Box::new(stack));
+        }
+        registry
+    }
+
+    /// Toutes les détections pour un dossier, de la plus probable à la moins probable
+    pub fn detect(&self, dir: &Path) -> Vec<Detection> {
+        let files = ProjectFiles::load(dir);
+        let mut detections: Vec<Detection> = self
+            .detectors
+            .iter()
+            .filter_map(|d| d.detect(&files))
+            .collect();
+        detections.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+        detections
+    }
+
+    /// Meilleure détection d'un rôle au-dessus du seuil de confiance
+    pub fn best(&self, dir: &Path, role: ServiceRole) -> Option<Detection> {
+        let files = ProjectFiles::load(dir);
+        // max_by garde le dernier ex aequo : parcours inversé pour favoriser l'ordre d'enregistrement
+        self.detectors
+            .iter()
+            .rev()
+            .filter(|d| d.role() == role)
+            .filter_map(|d| d.detect(&files))
+            .filter(|d| d.confidence >= MIN_CONFIDENCE)
+            .max_by(|a, b| a.confidence.total_cmp(&b.confidence))
+    }
+
+    /// Sources de port déclarées par une stack, sans détection
+    pub fn port_sources(&self, stack: &str) -> Vec<PortSource> {
+        self.detectors
+            .iter()
+            .find(|d| d.stack() == stack)
+            .map(|d| d.port_sources())
+            .unwrap_or_default()
+    }
+}
+
+impl Default for DetectorRegistry {
+    fn default() -> Self {
+        Self::with_defaults()
+    }
+}
+
+/// Stacks détectées dans un dossier, avec leur score de confiance
+#[tauri::command]
+pub async fn detect_stacks(path: String) -> Result<Vec<Detection>, String> {
+    let dir = PathBuf::from(&path);
+    if !dir.is_dir() {
+        return Err(format!("Path is not a directory: {}", path));
+    }
+    Ok(DetectorRegistry::with_defaults().detect(&dir))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(files: &[(&str, &str)]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("gmdev-detect-{}", uuid::Uuid::new_v4()));
+        for (name, content) in files {
+            let path = dir.join(name);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn test_detects_directus_and_django() {
+        let registry = DetectorRegistry::with_defaults();
+
+        let directus = temp_dir(&[(
+            "package.json",
+            r#"{"dependencies": {"directus": "^10.0.0"}, "scripts": {"start": "directus start"}}"#,
+        )]);
+        let best = registry.best(&directus, ServiceRole::Backend).unwrap();
+        assert_eq!(best.stack, "directus");
+        assert_eq!(best.health_endpoint.as_deref(), Some("/server/health"));
+        assert!(registry.best(&directus, ServiceRole::Frontend).is_none());
+
+        let django = temp_dir(&[
+            ("requirements.txt", "Django==5.0\ndjango-environ\n"),
+            ("manage.py", ""),
+        ]);
+        let best = registry.best(&django, ServiceRole::Backend).unwrap();
+        assert_eq!(best.stack, "django");
+        assert!(best.confidence > 0.8);
+
+        let _ = fs::remove_dir_all(directus);
+        let _ = fs::remove_dir_all(django);
+    }
+
+    #[test]
+    fn test_go_backend_needs_a_web_framework() {
+        let registry = DetectorRegistry::with_defaults();
+
+        let tool = temp_dir(&[("go.mod", "module example.com/tool\n"), ("main.go", "")]);
+        assert!(registry.best(&tool, ServiceRole::Backend).is_none());
+
+        let api = temp_dir(&[
+            (
+                "go.mod",
+                "module example.com/api\n\nrequire github.com/gin-gonic/gin v1.9.1\n",
+            ),
+            ("main.go", ""),
+        ]);
+        assert_eq!(
+            registry.best(&api, ServiceRole::Backend).unwrap().stack,
+            "go"
+        );
+
+        let _ = fs::remove_dir_all(tool);
+        let _ = fs::remove_dir_all(api);
+    }
+
+    #[test]
+    fn test_contains_word() {
+        assert!(contains_word("django==5.0", "django"));
+        assert!(!contains_word("django-environ", "django"));
+    }
+}
@@ -0,0 +1,474 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::autoscan::{ProjectServiceConfig, ProjectV3};
+use crate::profiles::active_profile;
+use crate::projects_v3::{check_project_id, read_config};
+use crate::secrets::{is_sensitive, register_sensitive, resolve_value, secret_ref};
+
+/// Fichiers .env lus par défaut dans le dossier d'un service (le dernier l'emporte)
+const DEFAULT_ENV_FILES: &[&str] = &[".env", ".env.local"];
+
+/// Fragments de noms de variables considérées comme secrètes
+const SECRET_MARKERS: &[&str] = &[
+    "SECRET",
+    "PASSWORD",
+    "PASSWD",
+    "TOKEN",
+    "PRIVATE",
+    "API_KEY",
+    "APIKEY",
+    "CREDENTIAL",
+    "AUTH",
+    "DATABASE_URI",
+    "DATABASE_URL",
+    "MONGODB_URI",
+    "MONGO_URL",
+];
+
+const MASK: &str = "********";
+
+/// Variable de l'environnement effectif d'un service, avec sa provenance
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EnvVar {
+    pub key: String,
+    pub value: String,
+    /// "process", "file:<chemin>", "project", "profile:<nom>" ou "service"
+    pub source: String,
+    pub masked: bool,
+}
+
+/// Environnement effectif d'un service
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ResolvedEnv {
+    pub project_id: String,
+    pub service: String,
+    pub profile: Option<String>,
+    pub vars: Vec<EnvVar>,
+}
+
+/// Entrée d'un fichier .env avec son numéro de ligne (à partir de 1)
+#[derive(Debug, Clone, PartialEq)]
+pub struct DotenvEntry {
+    pub key: String,
+    pub value: String,
+    pub line: usize,
+}
+
+/// Parse le contenu d'un fichier .env (commentaires, `export`, guillemets)
+pub fn parse_dotenv(content: &str) -> Vec<(String, String)> {
+    parse_dotenv_entries(content)
+        .into_iter()
+        .map(|entry| (entry.key, entry.value))
+        .collect()
+}
+
+/// Comme `parse_dotenv`, en gardant les numéros de ligne
+///
+/// `${VAR}`, `${VAR:-défaut}` et `$VAR` sont remplacés (hors guillemets simples)
+/// par une variable définie plus haut dans le fichier, sinon par l'environnement
+/// du processus.
+pub fn parse_dotenv_entries(content: &str) -> Vec<DotenvEntry> {
+    let mut entries: Vec<DotenvEntry> = Vec::new();
+
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line).trim_start();
+
+        let Some((key, raw)) = line.split_once('=') else {
+            continue;
+        };
+        let key = key.trim();
+        if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            continue;
+        }
+
+        let lookup = |name: &str| {
+            entries
+                .iter()
+                .rev()
+                .find(|entry| entry.key == name)
+                .map(|entry| entry.value.clone())
+                .or_else(|| std::env::var(name).ok())
+        };
+        let value = parse_value(raw.trim(), &lookup);
+        entries.push(DotenvEntry {
+            key: key.to_string(),
+            value,
+            line: index + 1,
+        });
+    }
+
+    entries
+}
+
+fn parse_value(raw: &str, lookup: &dyn Fn(&str) -> Option<String>) -> String {
+    if let Some(rest) = raw.strip_prefix('"') {
+        let mut value = String::new();
+        let mut chars = rest.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => break,
+                '\\' => match chars.next() {
+                    Some('n') => value.push('\n'),
+                    Some('t') => value.push('\t'),
+                    Some(other) => value.push(other),
+                    None => break,
+                },
+                '$' => interpolate(&mut chars, &mut value, lookup),
+                _ => value.push(c),
+            }
+        }
+        value
+    } else if let Some(rest) = raw.strip_prefix('\'') {
+        rest.split('\'').next().unwrap_or("").to_string()
+    } else {
+        // Un commentaire en fin de ligne doit être précédé d'un espace
+        let raw = match raw.find(" #") {
+            Some(pos) => &raw[..pos],
+            None => raw,
+        };
+        let mut value = String::new();
+        let mut chars = raw.trim().chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' if chars.peek() == Some(&'$') => value.push(chars.next().unwrap_or('$')),
+                '$' => interpolate(&mut chars, &mut value, lookup),
+                _ => value.push(c),
+            }
+        }
+        value
+    }
+}
+
+/// Remplace les références de variables d'un texte (`$$` produit un `$` littéral)
+pub fn expand_vars(text: &str, lookup: &dyn Fn(&str) -> Option<String>) -> String {
+    let mut value = String::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '$' if chars.peek() == Some(&'$') => value.push(chars.next().unwrap_or('$')),
+            '$' => interpolate(&mut chars, &mut value, lookup),
+            _ => value.push(c),
+        }
+    }
+    value
+}
+
+/// Remplace une référence `${VAR}`, `${VAR:-défaut}` ou `$VAR` (le `$` est déjà lu)
+fn interpolate(
+    chars: &mut std::iter::Peekable<std::str::Chars>,
+    value: &mut String,
+    lookup: &dyn Fn(&str) -> Option<String>,
+) {
+    let is_name_char = |c: &char| c.is_ascii_alphanumeric() || *c == '_';
+
+    if chars.peek() == Some(&'{') {
+        chars.next();
+        let mut inner = String::new();
+        let mut closed = false;
+        for c in chars.by_ref() {
+            if c == '}' {
+                closed = true;
+                break;
+            }
+            inner.push(c);
+        }
+        if !closed {
+            value.push_str("${");
+            value.push_str(&inner);
+            return;
+        }
+        let (name, default) = match inner.split_once(":-") {
+            Some((name, default)) => (name, Some(default)),
+            None => (inner.as_str(), None),
+        };
+        match lookup(name).filter(|v| !v.is_empty()) {
+            Some(resolved) => value.push_str(&resolved),
+            None => value.push_str(default.unwrap_or("")),
+        }
+        return;
+    }
+
+    let mut name = String::new();
+    while let Some(c) = chars.peek().copied() {
+        if !is_name_char(&c) {
+            break;
+        }
+        name.push(c);
+        chars.next();
+    }
+    if name.is_empty() {
+        value.push('$');
+    } else {
+        value.push_str(&lookup(&name).unwrap_or_default());
+    }
+}
+
+/// Indique si une variable doit être masquée dans les réponses et les logs
+pub fn is_secret_key(key: &str) -> bool {
+    let upper = key.to_ascii_uppercase();
+    SECRET_MARKERS.iter().any(|marker| upper.contains(marker))
+}
+
+/// Masque une valeur si la clé est secrète ou si la valeur vient du coffre
+pub fn mask_value(key: &str, value: &str) -> (String, bool) {
+    if secret_ref(value).is_some() {
+        // La référence n'est pas secrète : l'afficher indique d'où vient la valeur
+        (value.to_string(), true)
+    } else if (is_secret_key(key) || is_sensitive(value)) && !value.is_empty() {
+        (MASK.to_string(), true)
+    } else {
+        (value.to_string(), false)
+    }
+}
+
+/// Dossier de travail d'un service
+fn service_dir(project: &ProjectV3, service: &str) -> Result<PathBuf, String> {
+    let path = match service {
+        "backend" => &project.backend_path,
+        "frontend" => &project.frontend_path,
+        _ => return Err(format!("Service inconnu: {}", service)),
+    };
+    if path.is_empty() {
+        return Err(format!(
+            "{} non configuré pour le projet {}",
+            service, project.id
+        ));
+    }
+    Ok(PathBuf::from(path))
+}
+
+fn service_config<'a>(project: &'a ProjectV3, service: &str) -> Option<&'a ProjectServiceConfig> {
+    match service {
+        "backend" => project.backend.as_ref(),
+        "frontend" => project.frontend.as_ref(),
+        _ => None,
+    }
+}
+
+fn environment_for(
+    env: Option<&crate::autoscan::ProjectEnvironment>,
+    service: &str,
+) -> BTreeMap<String, String> {
+    env.map(|e| match service {
+        "backend" => e.backend_env.clone(),
+        _ => e.frontend_env.clone(),
+    })
+    .unwrap_or_default()
+}
+
+/// Couches d'environnement d'un service, de la moins à la plus prioritaire :
+///
+/// 1. environnement du processus DevCenter (`process`, si demandé)
+/// 2. fichiers `.env` puis `.env.local` du dossier du service, ou les `envFiles` déclarés
+/// 3. `environment.<service>_env` du projet (`project`)
+/// 4. `environment` du profil actif (`profile:<nom>`)
+/// 5. surcharges du service, `backend.env` / `frontend.env` (`service`)
+///
+/// `project` est le projet tel qu'enregistré, sans le profil actif appliqué.
+pub fn resolve_service_env_vars(
+    project: &ProjectV3,
+    service: &str,
+    include_process: bool,
+) -> Result<Vec<EnvVar>, String> {
+    let dir = service_dir(project, service)?;
+    let mut resolved: BTreeMap<String, (String, String)> = BTreeMap::new();
+    let mut layer = |vars: Vec<(String, String)>, source: &str| {
+        for (key, value) in vars {
+            resolved.insert(key, (value, source.to_string()));
+        }
+    };
+
+    if include_process {
+        layer(std::env::vars().collect(), "process");
+    }
+
+    let declared = service_config(project, service)
+        .map(|c| c.env_files.clone())
+        .unwrap_or_default();
+    let files: Vec<String> = if declared.is_empty() {
+        DEFAULT_ENV_FILES.iter().map(|f| f.to_string()).collect()
+    } else {
+        declared
+    };
+    for file in files {
+        let path = dir.join(&file);
+        if let Ok(content) = fs::read_to_string(&path) {
+            layer(parse_dotenv(&content), &format!("file:{}", path.display()));
+        }
+    }
+
+    layer(
+        environment_for(project.environment.as_ref(), service)
+            .into_iter()
+            .collect(),
+        "project",
+    );
+
+    if let Some((name, profile)) = active_profile(project) {
+        layer(
+            environment_for(profile.overrides.environment.as_ref(), service)
+                .into_iter()
+                .collect(),
+            &format!("profile:{}", name),
+        );
+    }
+
+    if let Some(config) = service_config(project, service) {
+        layer(config.env.clone().into_iter().collect(), "service");
+    }
+
+    Ok(resolved
+        .into_iter()
+        .map(|(key, (value, source))| EnvVar {
+            key,
+            value,
+            source,
+            masked: false,
+        })
+        .collect())
+}
+
+/// Remplace les références `secret://` par leur valeur ; les variables secrètes
+/// sont enregistrées pour être masquées dans les logs
+fn resolve_secrets(vars: Vec<EnvVar>, project_id: &str) -> Result<Vec<EnvVar>, String> {
+    vars.into_iter()
+        .map(|var| {
+            let value = resolve_value(&var.value)
+                .map_err(|e| format!("{} ({}, {}): {}", var.key, project_id, var.source, e))?;
+            if secret_ref(&var.value).is_some() || is_secret_key(&var.key) {
+                register_sensitive([&value]);
+            }
+            Ok(EnvVar { value, ..var })
+        })
+        .collect()
+}
+
+/// Écrit un fichier d'environnement par service du projet, sourcé par gmdev au lancement
+///
+/// L'environnement du processus n'y figure pas : gmdev en hérite déjà.
+/// Les références `secret://` sont résolues : `dir` doit être privé à l'invocation.
+pub fn write_runtime_env(project: &ProjectV3, dir: &Path) -> Result<(), String> {
+    check_project_id(&project.id)?;
+    for service in ["backend", "frontend"] {
+        let Ok(vars) = resolve_service_env_vars(project, service, false) else {
+            continue;
+        };
+        let vars = resolve_secrets(vars, &project.id)?;
+        write_env_file(&dir.join(format!("{}.{}.env", project.id, service)), &vars)?;
+    }
+
+    Ok(())
+}
+
+fn write_env_file(path: &Path, vars: &[EnvVar]) -> Result<(), String> {
+    let mut text = String::from("# Généré par GestionMax DevCenter - ne pas éditer\n");
+    for var in vars {
+        text.push_str(&format!(
+            "export {}='{}'\n",
+            var.key,
+            var.value.replace('\'', "'\\''")
+        ));
+    }
+
+    fs::write(path, text).map_err(|e| format!("Failed to write {}: {e}", path.display()))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = fs::set_permissions(path, fs::Permissions::from_mode(0o600));
+    }
+
+    Ok(())
+}
+
+/// Affiche l'environnement effectif d'un service, avec la source de chaque clé
+/// et les valeurs secrètes masquées
+#[tauri::command]
+pub async fn resolve_service_env(
+    project_id: String,
+    service: String,
+) -> Result<ResolvedEnv, String> {
+    let cfg = read_config()?;
+    let project = cfg
+        .projects
+        .iter()
+        .find(|p| p.id == project_id)
+        .ok_or_else(|| "Project not found".to_string())?;
+
+    let vars = resolve_service_env_vars(project, &service, true)?
+        .into_iter()
+        .map(|var| {
+            let (value, masked) = mask_value(&var.key, &var.value);
+            EnvVar {
+                value,
+                masked,
+                ..var
+            }
+        })
+        .collect();
+
+    Ok(ResolvedEnv {
+        project_id,
+        service,
+        profile: project.active_profile.clone(),
+        vars,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_dotenv() {
+        let vars = parse_dotenv(
+            "# commentaire\nexport PORT=3010\nNAME=\"Gestion Max\" \nQUOTED='a # b'\nURL=http://x # fin\nINVALID LINE\n",
+        );
+        assert_eq!(
+            vars,
+            vec![
+                ("PORT".to_string(), "3010".to_string()),
+                ("NAME".to_string(), "Gestion Max".to_string()),
+                ("QUOTED".to_string(), "a # b".to_string()),
+                ("URL".to_string(), "http://x".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_secret_masking() {
+        assert_eq!(
+            mask_value("PAYLOAD_SECRET", "abc"),
+            (MASK.to_string(), true)
+        );
+        assert_eq!(mask_value("PORT", "3010"), ("3010".to_string(), false));
+    }
+
+    #[test]
+    fn test_dotenv_interpolation() {
+        let entries = parse_dotenv_entries(
+            "HOST=localhost\n\nPORT=${GMDEV_TEST_UNSET_PORT:-3010}\nURL=\"http://$HOST:${PORT}/api\"\nRAW='$HOST'\nPRICE=\\$5\n",
+        );
+        let values: Vec<(&str, &str, usize)> = entries
+            .iter()
+            .map(|e| (e.key.as_str(), e.value.as_str(), e.line))
+            .collect();
+        assert_eq!(
+            values,
+            vec![
+                ("HOST", "localhost", 1),
+                ("PORT", "3010", 3),
+                ("URL", "http://localhost:3010/api", 4),
+                ("RAW", "$HOST", 5),
+                ("PRICE", "$5", 6),
+            ]
+        );
+    }
+}
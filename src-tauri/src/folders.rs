@@ -0,0 +1,253 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+use tauri_plugin_dialog::DialogExt;
+
+use crate::autoscan::{collect_repos, expand_parent_path};
+use crate::compose::find_compose_file;
+use crate::detectors::{DetectorRegistry, ServiceRole};
+use crate::indexer::{current_catalog, Catalog};
+use crate::manifest::find_manifest;
+use crate::pairing::DEFAULT_SCAN_DEPTH;
+use crate::settings::{read_settings, remember_workspace};
+use crate::workspace::detect_workspace;
+
+/// Nature d'un dossier choisi par l'utilisateur
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum FolderKind {
+    /// Racine d'un projet : dépôt git, manifeste, monorepo ou stack reconnue
+    ProjectRoot,
+    /// Dossier contenant plusieurs dépôts (à passer à `scan_independent_repos`)
+    WorkspaceParent,
+    Unknown,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PickedFolder {
+    /// Chemin absolu et canonique
+    pub path: String,
+    pub name: String,
+    pub kind: FolderKind,
+    /// Dépôts et projets trouvés sous un dossier parent
+    pub repos: usize,
+}
+
+/// Dossiers récents, sans ceux qui ont disparu du disque
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RecentFolders {
+    pub workspaces: Vec<PickedFolder>,
+    pub clones: Vec<PickedFolder>,
+}
+
+/// Classe un dossier sans le modifier
+pub fn classify_folder(dir: &Path) -> Result<(FolderKind, usize), String> {
+    let registry = DetectorRegistry::with_defaults();
+    let is_project = dir.join(".git").exists()
+        || find_manifest(dir).is_some()
+        || find_compose_file(dir).is_some()
+        // Un pnpm-workspace.yaml illisible désigne quand même la racine d'un monorepo
+        || detect_workspace(dir).map_or(true, |ws| ws.is_some_and(|ws| !ws.apps.is_empty()))
+        || [ServiceRole::Backend, ServiceRole::Frontend]
+            .into_iter()
+            .any(|role| registry.best(dir, role).is_some());
+    if is_project {
+        return Ok((FolderKind::ProjectRoot, 0));
+    }
+
+    let scan = collect_repos(dir, DEFAULT_SCAN_DEPTH, &registry)?;
+    let candidates: Vec<&String> = scan
+        .backends
+        .iter()
+        .chain(&scan.frontends)
+        .map(|c| &c.path)
+        .collect();
+    // Des dépôts distincts font un dossier parent ; de simples dossiers
+    // backend/ et frontend/ sans dépôt propre forment un seul projet
+    let repos = candidates
+        .iter()
+        .filter(|path| Path::new(path.as_str()).join(".git").exists())
+        .count()
+        + scan.projects.len();
+    if repos > 0 {
+        Ok((FolderKind::WorkspaceParent, candidates.len() + scan.projects.len()))
+    } else if !candidates.is_empty() {
+        Ok((FolderKind::ProjectRoot, 0))
+    } else {
+        Ok((FolderKind::Unknown, 0))
+    }
+}
+
+/// Vérifie qu'un chemin (`~/` accepté) est un dossier lisible, puis le classe
+pub fn inspect_path(path: &str) -> Result<PickedFolder, String> {
+    let dir = expand_parent_path(path)?;
+    fs::read_dir(&dir).map_err(|e| format!("Dossier illisible {}: {}", path, e))?;
+    let dir = fs::canonicalize(&dir).map_err(|e| format!("Invalid path {}: {}", path, e))?;
+
+    let (kind, repos) = classify_folder(&dir)?;
+    Ok(PickedFolder {
+        path: dir.to_string_lossy().to_string(),
+        name: dir
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        kind,
+        repos,
+    })
+}
+
+/// Dossier proposé à l'ouverture du dialogue : le plus récent, sinon la première racine
+fn default_directory() -> Option<PathBuf> {
+    let settings = read_settings().ok()?;
+    settings
+        .recent_workspaces
+        .iter()
+        .chain(&settings.workspace_roots)
+        .find_map(|path| expand_parent_path(path).ok())
+}
+
+/// Ouvre le sélecteur de dossier natif ; `None` si l'utilisateur annule
+#[tauri::command]
+pub async fn pick_project_folder(app: AppHandle) -> Result<Option<PickedFolder>, String> {
+    let mut dialog = app
+        .dialog()
+        .file()
+        .set_title("Choisir le dossier du projet");
+    if let Some(dir) = default_directory() {
+        dialog = dialog.set_directory(dir);
+    }
+    let Some(picked) = dialog.blocking_pick_folder() else {
+        return Ok(None);
+    };
+    let path = picked
+        .into_path()
+        .map_err(|e| format!("Chemin du dossier invalide: {}", e))?;
+
+    let folder = inspect_path(&path.to_string_lossy())?;
+    remember_workspace(&folder.path)?;
+    Ok(Some(folder))
+}
+
+/// Valide et classe un chemin saisi ou issu des listes récentes
+#[tauri::command]
+pub async fn inspect_folder(path: String) -> Result<PickedFolder, String> {
+    inspect_path(&path)
+}
+
+/// Classe un dossier d'après le catalogue de l'indexeur, sans parcourir le disque ;
+/// un dossier hors des racines indexées reste `Unknown` jusqu'à `inspect_folder`
+fn catalog_folder(dir: &Path, catalog: &Catalog) -> PickedFolder {
+    let path = dir.to_string_lossy().to_string();
+    let (kind, repos) = if catalog.repos.contains_key(&path) {
+        (FolderKind::ProjectRoot, 0)
+    } else {
+        let repos = catalog
+            .repos
+            .keys()
+            .filter(|repo| Path::new(repo).starts_with(dir))
+            .count();
+        if repos > 0 {
+            (FolderKind::WorkspaceParent, repos)
+        } else {
+            (FolderKind::Unknown, 0)
+        }
+    };
+    PickedFolder {
+        path,
+        name: dir
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        kind,
+        repos,
+    }
+}
+
+#[tauri::command]
+pub async fn list_recent_folders() -> Result<RecentFolders, String> {
+    let settings = read_settings()?;
+    let catalog = current_catalog();
+    let existing = |paths: &[String]| -> Vec<PickedFolder> {
+        paths
+            .iter()
+            .filter_map(|p| expand_parent_path(p).ok())
+            .filter(|dir| dir.is_dir())
+            .map(|dir| catalog_folder(&dir, &catalog))
+            .collect()
+    };
+    Ok(RecentFolders {
+        workspaces: existing(&settings.recent_workspaces),
+        clones: existing(&settings.recent_clones),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indexer::index_repo;
+
+    #[test]
+    fn test_classify_folder() {
+        let root = std::env::temp_dir().join(format!("gmdev-folders-{}", uuid::Uuid::new_v4()));
+        let next = r#"{"dependencies": {"next": "15.0.0"}}"#;
+        let express = r#"{"dependencies": {"express": "4.0.0"}}"#;
+
+        // Monorepo classique : backend/ et frontend/ sans dépôt propre
+        let mono = root.join("site");
+        fs::create_dir_all(mono.join("backend")).unwrap();
+        fs::create_dir_all(mono.join("frontend")).unwrap();
+        fs::write(mono.join("backend/package.json"), express).unwrap();
+        fs::write(mono.join("frontend/package.json"), next).unwrap();
+
+        // Dossier parent : deux dépôts indépendants
+        let parent = root.join("clients");
+        for (name, package) in [("acme-api", express), ("acme-web", next)] {
+            fs::create_dir_all(parent.join(name).join(".git")).unwrap();
+            fs::write(parent.join(name).join("package.json"), package).unwrap();
+        }
+        fs::create_dir_all(root.join("notes")).unwrap();
+
+        assert_eq!(classify_folder(&mono).unwrap().0, FolderKind::ProjectRoot);
+        assert_eq!(
+            classify_folder(&parent.join("acme-web")).unwrap().0,
+            FolderKind::ProjectRoot
+        );
+        assert_eq!(
+            classify_folder(&parent).unwrap(),
+            (FolderKind::WorkspaceParent, 2)
+        );
+        assert_eq!(
+            classify_folder(&root.join("notes")).unwrap().0,
+            FolderKind::Unknown
+        );
+        assert!(inspect_path(&root.join("missing").to_string_lossy()).is_err());
+
+        let mut catalog = Catalog::default();
+        for name in ["acme-api", "acme-web"] {
+            let path = parent.join(name).to_string_lossy().to_string();
+            let repo = index_repo(
+                &parent.join(name),
+                &root.to_string_lossy(),
+                &DetectorRegistry::with_defaults(),
+            )
+            .unwrap();
+            catalog.repos.insert(path, repo);
+        }
+        assert_eq!(
+            catalog_folder(&parent, &catalog).kind,
+            FolderKind::WorkspaceParent
+        );
+        assert_eq!(catalog_folder(&parent, &catalog).repos, 2);
+        assert_eq!(
+            catalog_folder(&parent.join("acme-api"), &catalog).kind,
+            FolderKind::ProjectRoot
+        );
+        assert_eq!(
+            catalog_folder(&root.join("notes"), &catalog).kind,
+            FolderKind::Unknown
+        );
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
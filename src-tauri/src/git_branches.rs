@@ -0,0 +1,674 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::autoscan::{PortsOverride, ProjectV3};
+use crate::commands::{
+    gmdev_service_name, gmdev_service_status, run_service_for_project, ScriptResult,
+};
+use crate::dependencies::install_stale;
+use crate::git_status::{git, locate_repo, repo_status};
+use crate::git_sync::is_dependency_file;
+use crate::gmd::is_gmd_available;
+use crate::ports::{allocate_port, reserved_ports};
+use crate::projects_v3::{config_dir, read_config, write_config, ProjectConfigV3};
+
+/// Services arrêtés avant un changement de branche puis relancés
+const SWITCHED_SERVICES: &[&str] = &["backend", "frontend"];
+
+/// Lien entre une variante et le projet dont elle est issue
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct WorktreeVariant {
+    #[serde(rename = "baseId")]
+    pub base_id: String,
+    pub branch: String,
+    /// Dépôt principal, d'où le worktree est supprimé
+    #[serde(rename = "repoRoot")]
+    pub repo_root: String,
+    #[serde(rename = "worktreePath")]
+    pub worktree_path: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BranchInfo {
+    /// Nom court ("feature/x", "origin/feature/x")
+    pub name: String,
+    pub remote: bool,
+    pub current: bool,
+    pub upstream: Option<String>,
+    pub commit: String,
+    pub summary: String,
+    /// Worktree où la branche est extraite
+    pub worktree: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct WorktreeInfo {
+    pub path: String,
+    pub head: String,
+    pub branch: Option<String>,
+    pub detached: bool,
+    pub locked: bool,
+    /// Dossier supprimé à la main : `git worktree prune` l'oubliera
+    pub prunable: bool,
+    /// Variante de projet enregistrée pour ce worktree
+    pub project_id: Option<String>,
+}
+
+/// Résultat d'un changement de branche d'un projet
+#[derive(Serialize)]
+pub struct BranchSwitchResult {
+    pub project_id: String,
+    pub previous_branch: Option<String>,
+    pub branch: String,
+    pub files: Vec<String>,
+    pub dependencies_changed: bool,
+    /// Dossiers où les dépendances ont été réinstallées
+    pub installed: Vec<String>,
+    /// Services arrêtés avant le changement puis relancés
+    pub restarted: Vec<String>,
+    pub results: Vec<ScriptResult>,
+}
+
+/// Variante créée dans un worktree, avec la sortie de l'installation
+#[derive(Serialize)]
+pub struct WorktreeProject {
+    pub project: ProjectV3,
+    pub results: Vec<ScriptResult>,
+}
+
+/// Sortie de `git for-each-ref` sur les branches locales et distantes
+fn parse_branches(output: &str) -> Vec<BranchInfo> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split('\u{1f}').collect();
+            let [refname, name, head, upstream, commit, summary, worktree] = fields[..] else {
+                return None;
+            };
+            // origin/HEAD n'est qu'un alias
+            if refname.starts_with("refs/remotes/") && refname.ends_with("/HEAD") {
+                return None;
+            }
+            let non_empty = |value: &str| (!value.is_empty()).then(|| value.to_string());
+            Some(BranchInfo {
+                name: name.to_string(),
+                remote: refname.starts_with("refs/remotes/"),
+                current: head == "*",
+                upstream: non_empty(upstream),
+                commit: commit.to_string(),
+                summary: summary.to_string(),
+                worktree: non_empty(worktree),
+            })
+        })
+        .collect()
+}
+
+pub fn list_branches_at(dir: &Path) -> Result<Vec<BranchInfo>, String> {
+    let format = [
+        "%(refname)",
+        "%(refname:short)",
+        "%(HEAD)",
+        "%(upstream:short)",
+        "%(objectname:short)",
+        "%(contents:subject)",
+        "%(worktreepath)",
+    ]
+    .join("%1f");
+    let output = git(
+        dir,
+        &[
+            "for-each-ref",
+            &format!("--format={}", format),
+            "refs/heads",
+            "refs/remotes",
+        ],
+    )?;
+    Ok(parse_branches(&output))
+}
+
+/// Sortie de `git worktree list --porcelain` (un bloc par worktree)
+fn parse_worktrees(output: &str) -> Vec<WorktreeInfo> {
+    let mut worktrees = Vec::new();
+    for block in output.split("\n\n") {
+        let mut info = WorktreeInfo::default();
+        for line in block.lines() {
+            let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+            match key {
+                "worktree" => info.path = value.to_string(),
+                "HEAD" => info.head = value.to_string(),
+                "branch" => info.branch = Some(value.trim_start_matches("refs/heads/").to_string()),
+                "detached" => info.detached = true,
+                "locked" => info.locked = true,
+                "prunable" => info.prunable = true,
+                _ => {}
+            }
+        }
+        if !info.path.is_empty() {
+            worktrees.push(info);
+        }
+    }
+    worktrees
+}
+
+fn check_branch_name(dir: &Path, name: &str) -> Result<(), String> {
+    git(dir, &["check-ref-format", "--branch", name])
+        .map(|_| ())
+        .map_err(|_| format!("Nom de branche invalide: {}", name))
+}
+
+/// Refuse de changer de branche avec des modifications ou une opération en cours
+fn ensure_clean(dir: &Path) -> Result<(), String> {
+    let status = repo_status(dir)?;
+    if let Some(operation) = &status.operation {
+        return Err(format!(
+            "Opération {} en cours dans {}",
+            operation, status.root
+        ));
+    }
+    if status.staged + status.unstaged + status.conflicted > 0 {
+        return Err(format!(
+            "Modifications locales dans {} : validez-les ou mettez-les de côté",
+            status.root
+        ));
+    }
+    Ok(())
+}
+
+/// Nom de dossier et suffixe d'id tirés d'une branche ("feature/Login" -> "feature-login")
+fn branch_slug(branch: &str) -> String {
+    branch
+        .to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+fn variant_id(base_id: &str, branch: &str) -> String {
+    format!("{}--{}", base_id, branch_slug(branch))
+}
+
+/// Reporte un chemin du dépôt principal dans le worktree (inchangé s'il est hors du dépôt)
+fn remap_path(path: &str, repo_root: &Path, worktree: &Path) -> String {
+    if path.trim().is_empty() {
+        return path.to_string();
+    }
+    let path = Path::new(path);
+    let canonical = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    [path, canonical.as_path()]
+        .iter()
+        .find_map(|p| p.strip_prefix(repo_root).ok())
+        .map(|relative| {
+            if relative.as_os_str().is_empty() {
+                worktree.to_path_buf()
+            } else {
+                worktree.join(relative)
+            }
+        })
+        .unwrap_or_else(|| path.to_path_buf())
+        .to_string_lossy()
+        .to_string()
+}
+
+/// Projet temporaire pointant sur un worktree, avec ses propres ports
+///
+/// La variante partage les services compose et le tunnel du projet de base ;
+/// seuls le backend, le frontend et les apps du monorepo tournent en double.
+pub fn worktree_variant(
+    base: &ProjectV3,
+    variant: WorktreeVariant,
+    reserved: &mut BTreeSet<u16>,
+) -> Result<ProjectV3, String> {
+    let repo_root = PathBuf::from(&variant.repo_root);
+    let worktree = PathBuf::from(&variant.worktree_path);
+    let mut project = base.clone();
+
+    project.id = variant_id(&base.id, &variant.branch);
+    project.name = format!("{} ({})", base.name, variant.branch);
+    project.root_path = remap_path(&base.root_path, &repo_root, &worktree);
+    project.backend_path = remap_path(&base.backend_path, &repo_root, &worktree);
+    project.frontend_path = remap_path(&base.frontend_path, &repo_root, &worktree);
+    project.manifest_path = base
+        .manifest_path
+        .as_deref()
+        .map(|path| remap_path(path, &repo_root, &worktree));
+
+    let no_port = || format!("Aucun port libre pour la variante {}", project.id);
+    project.ports.backend = allocate_port(base.ports.backend, reserved).ok_or_else(no_port)?;
+    project.ports.frontend = allocate_port(base.ports.frontend, reserved).ok_or_else(no_port)?;
+    project.ports.services.clear();
+    for app in project.apps.values_mut() {
+        app.path = remap_path(&app.path, &repo_root, &worktree);
+        app.port = allocate_port(app.port, reserved).ok_or_else(no_port)?;
+    }
+    project.compose = None;
+    // Le tunnel du projet de base sert aussi la variante : ses ports locaux sont pris
+    project.tunnel = None;
+    // Les ports attribués priment sur le manifeste et les profils du worktree
+    if project.manifest_path.is_some() {
+        project.overrides.get_or_insert_with(Default::default).ports = Some(PortsOverride {
+            backend: Some(project.ports.backend),
+            frontend: Some(project.ports.frontend),
+        });
+    }
+    for profile in project.profiles.values_mut() {
+        profile.overrides.ports = None;
+    }
+
+    project.enabled = false;
+    project.created_at = Utc::now().to_rfc3339();
+    project.worktree = Some(variant);
+    Ok(project)
+}
+
+/// Arrête les services du projet qui tournent et renvoie leur nom
+fn stop_running_services(
+    cfg: &ProjectConfigV3,
+    project: &ProjectV3,
+    services: &[&'static str],
+    results: &mut Vec<ScriptResult>,
+) -> Vec<String> {
+    let mut stopped = Vec::new();
+    if !is_gmd_available() {
+        return stopped;
+    }
+    for service in services {
+        let gmdev_service = gmdev_service_name(service);
+        if gmdev_service_status(cfg, project, gmdev_service).as_deref() == Ok("RUNNING") {
+            results.push(run_service_for_project("stop", gmdev_service, cfg, project));
+            stopped.push(service.to_string());
+        }
+    }
+    stopped
+}
+
+fn find_project<'a>(cfg: &'a ProjectConfigV3, id: &str) -> Result<&'a ProjectV3, String> {
+    cfg.projects
+        .iter()
+        .find(|p| p.id == id)
+        .ok_or_else(|| "Project not found".to_string())
+}
+
+/// Supprime le worktree d'une variante et la retire de la configuration
+fn remove_variant(
+    cfg: &mut ProjectConfigV3,
+    project_id: &str,
+    force: bool,
+) -> Result<Vec<ScriptResult>, String> {
+    let project = find_project(cfg, project_id)?.clone();
+    let variant = project
+        .worktree
+        .clone()
+        .ok_or_else(|| format!("{} n'est pas une variante de worktree", project_id))?;
+
+    let mut results = Vec::new();
+    stop_running_services(
+        cfg,
+        &project,
+        &["backend", "frontend", "tunnel"],
+        &mut results,
+    );
+
+    let repo_root = Path::new(&variant.repo_root);
+    if Path::new(&variant.worktree_path).exists() {
+        let mut args = vec!["worktree", "remove"];
+        if force {
+            args.push("--force");
+        }
+        args.push(&variant.worktree_path);
+        git(repo_root, &args).map_err(|e| format!("Impossible de supprimer le worktree: {}", e))?;
+    }
+    let _ = git(repo_root, &["worktree", "prune"]);
+
+    cfg.projects.retain(|p| p.id != project_id);
+    Ok(results)
+}
+
+/// Branches locales et distantes du dépôt contenant `path`
+#[tauri::command]
+pub async fn list_branches(path: String) -> Result<Vec<BranchInfo>, String> {
+    list_branches_at(Path::new(&path))
+}
+
+/// Crée une branche (depuis HEAD ou `start_point`) sans l'extraire
+#[tauri::command]
+pub async fn create_branch(
+    path: String,
+    name: String,
+    start_point: Option<String>,
+) -> Result<BranchInfo, String> {
+    let dir = Path::new(&path);
+    check_branch_name(dir, &name)?;
+    let mut args = vec!["branch", "--", name.as_str()];
+    if let Some(start) = &start_point {
+        args.push(start);
+    }
+    git(dir, &args)?;
+    list_branches_at(dir)?
+        .into_iter()
+        .find(|b| !b.remote && b.name == name)
+        .ok_or_else(|| format!("Branche {} introuvable après création", name))
+}
+
+/// Change la branche d'un projet : arrête ses services, extrait la branche,
+/// réinstalle les dépendances si elles ont changé puis relance les services
+///
+/// Une branche distante ("feature/x" présente seulement sur origin) est suivie automatiquement.
+#[tauri::command]
+pub async fn switch_project_branch(
+    project_id: String,
+    branch: String,
+    reinstall: Option<bool>,
+) -> Result<BranchSwitchResult, String> {
+    let cfg = read_config()?;
+    let project = find_project(&cfg, &project_id)?.clone();
+    let (root, _) = locate_repo(Path::new(&project.root_path))?;
+    let root = PathBuf::from(root);
+    ensure_clean(&root)?;
+    check_branch_name(&root, &branch)?;
+
+    let previous_branch = repo_status(&root)?.branch;
+    let before = git(&root, &["rev-parse", "HEAD"])?.trim().to_string();
+
+    let mut results = Vec::new();
+    let restarted = stop_running_services(&cfg, &project, SWITCHED_SERVICES, &mut results);
+
+    // Les erreurs sont remontées après la relance des services
+    let switched = git(&root, &["switch", &branch])
+        .map_err(|e| format!("Impossible d'extraire {}: {}", branch, e))
+        .and_then(|_| {
+            git(&root, &["diff", "--name-only", &before, "HEAD"])
+                .map_err(|e| format!("Impossible de lister les fichiers modifiés: {}", e))
+        });
+    let (files, installed) = match &switched {
+        Ok(diff) => {
+            let files: Vec<String> = diff.lines().map(str::to_string).collect();
+            let installed =
+                if reinstall.unwrap_or(true) && files.iter().any(|f| is_dependency_file(f)) {
+                    let (installed, install_results) = install_stale(&project);
+                    results.extend(install_results);
+                    installed
+                } else {
+                    Vec::new()
+                };
+            (files, installed)
+        }
+        Err(_) => (Vec::new(), Vec::new()),
+    };
+
+    // Les services sont relancés même si le changement a échoué
+    for service in &restarted {
+        let gmdev_service = gmdev_service_name(service);
+        results.push(run_service_for_project(
+            "start",
+            gmdev_service,
+            &cfg,
+            &project,
+        ));
+    }
+    switched?;
+
+    Ok(BranchSwitchResult {
+        project_id,
+        previous_branch,
+        branch,
+        dependencies_changed: files.iter().any(|f| is_dependency_file(f)),
+        files,
+        installed,
+        restarted,
+        results,
+    })
+}
+
+/// Worktrees du dépôt contenant `path`, avec la variante de projet associée
+#[tauri::command]
+pub async fn list_worktrees(path: String) -> Result<Vec<WorktreeInfo>, String> {
+    let output = git(Path::new(&path), &["worktree", "list", "--porcelain"])?;
+    let cfg = read_config()?;
+    let mut worktrees = parse_worktrees(&output);
+    for worktree in &mut worktrees {
+        worktree.project_id = cfg
+            .projects
+            .iter()
+            .find(|p| {
+                p.worktree
+                    .as_ref()
+                    .is_some_and(|w| w.worktree_path == worktree.path)
+            })
+            .map(|p| p.id.clone());
+    }
+    Ok(worktrees)
+}
+
+/// Extrait `branch` dans un worktree et l'enregistre comme variante du projet
+///
+/// Le worktree est créé sous `~/.gestionmax-devcenter/worktrees/<projet>/<branche>`.
+/// Avec `start_point`, la branche est créée à partir de ce commit.
+#[tauri::command]
+pub async fn create_project_worktree(
+    project_id: String,
+    branch: String,
+    start_point: Option<String>,
+    install: Option<bool>,
+) -> Result<WorktreeProject, String> {
+    let mut cfg = read_config()?;
+    let base = find_project(&cfg, &project_id)?.clone();
+    if base.worktree.is_some() {
+        return Err(format!("{} est déjà une variante de worktree", project_id));
+    }
+    let (repo_root, _) = locate_repo(Path::new(&base.root_path))?;
+    let repo_dir = Path::new(&repo_root);
+    check_branch_name(repo_dir, &branch)?;
+
+    let worktree_path = config_dir()
+        .join("worktrees")
+        .join(&base.id)
+        .join(branch_slug(&branch));
+    if worktree_path.exists() {
+        return Err(format!(
+            "Le worktree {} existe déjà",
+            worktree_path.display()
+        ));
+    }
+    let id = variant_id(&base.id, &branch);
+    if cfg.projects.iter().any(|p| p.id == id) {
+        return Err(format!("La variante {} existe déjà", id));
+    }
+
+    let target = worktree_path.to_string_lossy().to_string();
+    let mut args = vec!["worktree", "add"];
+    if let Some(start) = &start_point {
+        args.extend(["-b", branch.as_str(), "--", target.as_str(), start]);
+    } else {
+        // Une branche seulement distante est créée et suivie automatiquement
+        args.extend(["--", target.as_str(), branch.as_str()]);
+    }
+    git(repo_dir, &args).map_err(|e| format!("Impossible de créer le worktree: {}", e))?;
+
+    // git résout les liens symboliques : garder le chemin qu'il rapportera
+    let variant = WorktreeVariant {
+        base_id: base.id.clone(),
+        branch: branch.clone(),
+        repo_root: repo_root.clone(),
+        worktree_path: fs::canonicalize(&worktree_path)
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or(target.clone()),
+    };
+    let saved =
+        worktree_variant(&base, variant, &mut reserved_ports(&cfg.projects)).and_then(|project| {
+            cfg.projects.push(project.clone());
+            write_config(&cfg).map(|_| project)
+        });
+    let project = match saved {
+        Ok(project) => project,
+        Err(e) => {
+            let _ = git(repo_dir, &["worktree", "remove", "--force", &target]);
+            return Err(e);
+        }
+    };
+
+    let results = if install.unwrap_or(true) {
+        install_stale(&project).1
+    } else {
+        Vec::new()
+    };
+    Ok(WorktreeProject { project, results })
+}
+
+/// Arrête les services d'une variante, supprime son worktree et la retire des projets
+#[tauri::command]
+pub async fn remove_project_worktree(
+    project_id: String,
+    force: Option<bool>,
+) -> Result<Vec<ScriptResult>, String> {
+    let mut cfg = read_config()?;
+    let results = remove_variant(&mut cfg, &project_id, force.unwrap_or(false))?;
+    write_config(&cfg)?;
+    Ok(results)
+}
+
+/// Supprime toutes les variantes d'un projet, avant de le retirer par exemple
+///
+/// Renvoie les ids supprimés ; une variante qui échoue est conservée et l'erreur remontée.
+#[tauri::command]
+pub async fn cleanup_project_worktrees(
+    project_id: String,
+    force: Option<bool>,
+) -> Result<Vec<String>, String> {
+    let mut cfg = read_config()?;
+    let variants: Vec<String> = cfg
+        .projects
+        .iter()
+        .filter(|p| p.worktree.as_ref().is_some_and(|w| w.base_id == project_id))
+        .map(|p| p.id.clone())
+        .collect();
+
+    let mut removed = Vec::new();
+    let mut errors = Vec::new();
+    for id in variants {
+        match remove_variant(&mut cfg, &id, force.unwrap_or(false)) {
+            Ok(_) => removed.push(id),
+            Err(e) => errors.push(format!("{}: {}", id, e)),
+        }
+    }
+    write_config(&cfg)?;
+
+    if errors.is_empty() {
+        Ok(removed)
+    } else {
+        Err(format!(
+            "Some worktrees could not be removed: {}",
+            errors.join("; ")
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::autoscan::{test_project, Ports};
+    use std::collections::BTreeMap;
+
+    fn run(dir: &Path, args: &[&str]) {
+        let mut full = vec!["-c", "user.name=Dev", "-c", "user.email=dev@example.com"];
+        full.extend_from_slice(args);
+        git(dir, &full).unwrap();
+    }
+
+    #[test]
+    fn test_branches_and_worktrees() {
+        let root = std::env::temp_dir().join(format!("gmdev-branches-{}", uuid::Uuid::new_v4()));
+        let repo = root.join("site");
+        fs::create_dir_all(&repo).unwrap();
+        run(&repo, &["init", "-q", "-b", "main"]);
+        fs::write(repo.join("README.md"), "site").unwrap();
+        run(&repo, &["add", "."]);
+        run(&repo, &["commit", "-q", "-m", "init"]);
+        run(&repo, &["branch", "feature/Login"]);
+        let worktree = root.join("site-login");
+        run(
+            &repo,
+            &[
+                "worktree",
+                "add",
+                "-q",
+                &worktree.to_string_lossy(),
+                "feature/Login",
+            ],
+        );
+
+        let branches = list_branches_at(&repo).unwrap();
+        let main = branches.iter().find(|b| b.name == "main").unwrap();
+        assert!(main.current && !main.remote);
+        assert_eq!(main.summary, "init");
+        let feature = branches.iter().find(|b| b.name == "feature/Login").unwrap();
+        assert_eq!(
+            feature.worktree.as_deref(),
+            Some(&*worktree.to_string_lossy())
+        );
+
+        let output = git(&repo, &["worktree", "list", "--porcelain"]).unwrap();
+        let worktrees = parse_worktrees(&output);
+        assert_eq!(worktrees.len(), 2);
+        assert_eq!(worktrees[1].branch.as_deref(), Some("feature/Login"));
+        assert!(check_branch_name(&repo, "bad..name").is_err());
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn test_worktree_variant() {
+        let base = ProjectV3 {
+            name: "Site".to_string(),
+            root_path: "/src/site".to_string(),
+            backend_path: "/src/site/api".to_string(),
+            frontend_path: "/src/shared-web".to_string(),
+            ports: Ports {
+                backend: 3000,
+                frontend: 3001,
+                services: BTreeMap::from([("mongo".to_string(), 27017)]),
+            },
+            tunnel: serde_json::from_str(
+                r#"{"enabled":true,"host":"h","user":"u","port":22,"privateKey":"","localMongo":27018,"remoteMongo":27017}"#,
+            )
+            .unwrap(),
+            manifest_path: Some("/src/site/.gmdev.toml".to_string()),
+            ..test_project("site")
+        };
+        let variant = WorktreeVariant {
+            base_id: "site".to_string(),
+            branch: "feature/Login".to_string(),
+            repo_root: "/src/site".to_string(),
+            worktree_path: "/wt/site/feature-login".to_string(),
+        };
+        let mut reserved = reserved_ports(std::slice::from_ref(&base));
+        // 3002 déjà attribué à un autre projet
+        reserved.insert(3002);
+
+        let project = worktree_variant(&base, variant.clone(), &mut reserved).unwrap();
+        assert_eq!(project.id, "site--feature-login");
+        assert_eq!(project.root_path, "/wt/site/feature-login");
+        assert_eq!(project.backend_path, "/wt/site/feature-login/api");
+        // Hors du dépôt : partagé avec le projet de base
+        assert_eq!(project.frontend_path, "/src/shared-web");
+        assert_eq!(
+            project.manifest_path.as_deref(),
+            Some("/wt/site/feature-login/.gmdev.toml")
+        );
+        assert!(project.ports.backend > 3000 && project.ports.backend != 3002);
+        assert!(project.ports.frontend > 3001 && project.ports.frontend != project.ports.backend);
+        assert!(project.ports.services.is_empty());
+        assert!(project.tunnel.is_none());
+        let overrides = project
+            .overrides
+            .as_ref()
+            .and_then(|o| o.ports.as_ref())
+            .unwrap();
+        assert_eq!(overrides.backend, Some(project.ports.backend));
+        assert!(!project.enabled);
+        assert_eq!(project.worktree, Some(variant));
+    }
+}
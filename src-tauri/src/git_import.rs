@@ -1,11 +1,154 @@
-use std::path::PathBuf;
-use std::process::Command;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use tauri::AppHandle;
 
-/// Clone un dépôt Git dans le dossier cible
-#[tauri::command]
-pub async fn clone_git_repo(url: String, target_dir: Option<String>) -> Result<String, String> {
+use crate::autoscan::{project_v3_from_root, ProjectV3};
+use crate::git_url::GitRemote;
+use crate::settings::remember_clone;
+use crate::secrets::{emit_redacted, redact, serialize_redacted_opt};
+
+/// Lignes de stderr gardées pour le message d'erreur d'un clone
+const ERROR_TAIL_LINES: usize = 5;
+
+/// Options de clone ; sans options, clone superficiel (`--depth 1`) de la branche par défaut
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CloneOptions {
+    /// Dossier parent (`~/CascadeProjects` par défaut)
+    pub target_dir: Option<String>,
+    /// Nom du dossier cloné, à la place de celui déduit de l'URL
+    pub name: Option<String>,
+    /// Branche ou tag à extraire
+    pub reference: Option<String>,
+    /// Profondeur de l'historique ; `null` pour un clone complet
+    pub depth: Option<u32>,
+    pub submodules: bool,
+    /// Dossiers à extraire (sparse checkout), tout le dépôt si vide
+    pub sparse_paths: Vec<String>,
+}
+
+impl Default for CloneOptions {
+    fn default() -> Self {
+        Self {
+            target_dir: None,
+            name: None,
+            reference: None,
+            depth: Some(1),
+            submodules: false,
+            sparse_paths: Vec::new(),
+        }
+    }
+}
+
+/// Avancement lu dans la sortie de `git clone --progress`
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CloneProgress {
+    /// "Receiving objects", "Resolving deltas"...
+    pub phase: String,
+    pub percent: Option<u8>,
+    pub current: Option<u64>,
+    pub total: Option<u64>,
+}
+
+/// Payload pour l'event clone:progress
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CloneProgressEvent {
+    pub run_id: String,
+    #[serde(flatten)]
+    pub progress: CloneProgress,
+}
+
+/// Payload pour l'event clone:finished
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CloneFinishedEvent {
+    pub run_id: String,
+    pub path: Option<String>,
+    /// Projet proposé par l'autoscan du dépôt cloné
+    pub project: Option<ProjectV3>,
+    #[serde(serialize_with = "serialize_redacted_opt")]
+    pub error: Option<String>,
+    pub cancelled: bool,
+}
+
+/// Réponse immédiate avec runId, suivie des events clone:progress et clone:finished
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CloneRunId {
+    pub run_id: String,
+}
+
+/// Clone en cours : processus git courant et demande d'annulation
+#[derive(Default)]
+pub struct CloneHandle {
+    child: Mutex<Option<Child>>,
+    cancelled: AtomicBool,
+}
+
+impl CloneHandle {
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        if let Some(child) = self
+            .child
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_mut()
+        {
+            let _ = child.kill();
+        }
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+/// Clones lancés par `start_clone`, par runId
+static CLONES: Mutex<BTreeMap<String, Arc<CloneHandle>>> = Mutex::new(BTreeMap::new());
+
+/// Ligne de progression de git (`Receiving objects:  45% (450/1000), 1.20 MiB | ...`)
+pub fn parse_progress(line: &str) -> Option<CloneProgress> {
+    let line = line.trim();
+    let line = line.strip_prefix("remote:").unwrap_or(line).trim();
+    let (phase, rest) = line.split_once(": ")?;
+    let rest = rest.trim_start();
+    if !rest.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let percent = rest
+        .split_once('%')
+        .and_then(|(n, _)| n.trim().parse().ok());
+    let counts = rest
+        .split_once('(')
+        .and_then(|(_, r)| r.split_once(')'))
+        .and_then(|(inner, _)| inner.split_once('/'));
+    let (current, total) = match counts {
+        Some((current, total)) => (current.parse().ok(), total.parse().ok()),
+        // "Enumerating objects: 5, done."
+        None => (
+            rest.split(|c: char| !c.is_ascii_digit())
+                .next()
+                .and_then(|n| n.parse().ok()),
+            None,
+        ),
+    };
+    Some(CloneProgress {
+        phase: phase.to_string(),
+        percent,
+        current,
+        total,
+    })
+}
+
+/// URL nettoyée et chemin du dossier à créer
+fn clone_destination(url: &str, options: &CloneOptions) -> Result<(String, PathBuf), String> {
     // Déterminer le dossier cible
-    let target = if let Some(dir) = target_dir {
+    let target = if let Some(dir) = &options.target_dir {
         PathBuf::from(dir)
     } else {
         // Par défaut : ~/CascadeProjects/
@@ -13,37 +156,17 @@ pub async fn clone_git_repo(url: String, target_dir: Option<String>) -> Result<S
         PathBuf::from(home).join("CascadeProjects")
     };
 
-    // Créer le dossier s'il n'existe pas
-    if !target.exists() {
-        std::fs::create_dir_all(&target)
-            .map_err(|e| format!("Failed to create target directory: {}", e))?;
-    }
-
-    // Nettoyer l'URL (supprimer les espaces avant/après et normaliser)
-    let mut cleaned_url = url.trim().to_string();
-    
-    // Supprimer les espaces multiples et s'assurer qu'il n'y a pas d'espace avant le protocole
-    cleaned_url = cleaned_url.replace(" https://", "https://")
-        .replace(" http://", "http://")
-        .replace(" git@", "git@")
-        .replace(" ssh://", "ssh://");
-    cleaned_url = cleaned_url.trim().to_string();
-    
-    // Vérifier que l'URL est valide
-    if cleaned_url.is_empty() {
-        return Err("URL Git vide".to_string());
-    }
-    
-    // Vérifier que l'URL commence par un protocole valide
-    if !cleaned_url.starts_with("https://") 
-        && !cleaned_url.starts_with("http://")
-        && !cleaned_url.starts_with("git@")
-        && !cleaned_url.starts_with("ssh://") {
-        return Err(format!("URL Git invalide: doit commencer par https://, http://, git@ ou ssh://. Reçu: '{}'", cleaned_url));
-    }
+    // Valider l'URL : https, ssh, scp (git@hôte:org/repo), git, file ou chemin local
+    let remote = GitRemote::parse(url)?;
 
-    // Extraire le nom du projet depuis l'URL Git
-    let project_name = extract_project_name_from_url(&cleaned_url)?;
+    // Nom du dossier : celui demandé, sinon déduit de l'URL Git
+    let project_name = match options.name.as_deref().map(str::trim) {
+        Some(name) if name.is_empty() || name.contains('/') || name == "." || name == ".." => {
+            return Err(format!("Nom de dossier invalide: '{}'", name));
+        }
+        Some(name) => name.to_string(),
+        None => extract_project_name_from_url(&remote.url)?,
+    };
     let project_path = target.join(&project_name);
 
     // Vérifier si le projet existe déjà
@@ -55,27 +178,223 @@ pub async fn clone_git_repo(url: String, target_dir: Option<String>) -> Result<S
         ));
     }
 
-    // Cloner le dépôt avec depth=1 (shallow clone)
-    let output = Command::new("git")
-        .arg("clone")
-        .arg("--depth")
-        .arg("1")
-        .arg(&cleaned_url)
-        .arg(&project_path)
-        .output()
-        .map_err(|e| {
-            format!(
-                "Failed to execute git clone: {}. Make sure git is installed.",
-                e
-            )
-        })?;
+    // Créer le dossier s'il n'existe pas
+    if !target.exists() {
+        std::fs::create_dir_all(&target)
+            .map_err(|e| format!("Failed to create target directory: {}", e))?;
+    }
 
-    if !output.status.success() {
-        let error_msg = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("Git clone failed: {}", error_msg));
+    Ok((remote.url, project_path))
+}
+
+fn clone_args(url: &str, dest: &Path, options: &CloneOptions) -> Vec<String> {
+    let mut args = vec!["clone".to_string(), "--progress".to_string()];
+    if let Some(depth) = options.depth {
+        args.extend(["--depth".to_string(), depth.to_string()]);
+    }
+    if let Some(reference) = options
+        .reference
+        .as_deref()
+        .filter(|r| !r.trim().is_empty())
+    {
+        args.extend(["--branch".to_string(), reference.trim().to_string()]);
+    }
+    if options.submodules {
+        args.push("--recurse-submodules".to_string());
+        if options.depth.is_some() {
+            args.push("--shallow-submodules".to_string());
+        }
+    }
+    if !options.sparse_paths.is_empty() {
+        args.push("--sparse".to_string());
+    }
+    // Une URL ou un dossier commençant par "-" ne doit pas être lu comme une option
+    args.push("--".to_string());
+    args.push(url.to_string());
+    args.push(dest.to_string_lossy().to_string());
+    args
+}
+
+/// Exécute git en lisant la progression sur stderr ; le processus est annulable via `handle`
+fn run_git_with_progress(
+    args: &[String],
+    handle: &CloneHandle,
+    on_progress: &mut dyn FnMut(CloneProgress),
+) -> Result<(), String> {
+    let mut child = Command::new("git")
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to execute git: {}. Make sure git is installed.", e))?;
+    let stderr = child
+        .stderr
+        .take()
+        .ok_or_else(|| "Impossible de capturer stderr".to_string())?;
+    *handle.child.lock().unwrap_or_else(|e| e.into_inner()) = Some(child);
+    if handle.is_cancelled() {
+        handle.cancel();
     }
 
-    Ok(project_path.to_string_lossy().to_string())
+    // git réécrit la ligne de progression avec \r : découper sur \r et \n
+    let mut tail: Vec<String> = Vec::new();
+    let mut line = Vec::new();
+    for byte in BufReader::new(stderr).bytes() {
+        let Ok(byte) = byte else {
+            break;
+        };
+        if byte != b'\r' && byte != b'\n' {
+            line.push(byte);
+            continue;
+        }
+        let text = String::from_utf8_lossy(&line).to_string();
+        line.clear();
+        match parse_progress(&text) {
+            Some(progress) => on_progress(progress),
+            None if !text.trim().is_empty() => {
+                tail.push(text);
+                if tail.len() > ERROR_TAIL_LINES {
+                    tail.remove(0);
+                }
+            }
+            None => {}
+        }
+    }
+
+    let child = handle
+        .child
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .take();
+    let status = match child {
+        Some(mut child) => child
+            .wait()
+            .map_err(|e| format!("Failed to wait for git: {}", e))?,
+        None => return Err("Processus git introuvable".to_string()),
+    };
+    if handle.is_cancelled() {
+        return Err("Clone annulé".to_string());
+    }
+    if !status.success() {
+        return Err(format!("Git clone failed: {}", tail.join("\n")));
+    }
+    Ok(())
+}
+
+/// Clone un dépôt selon `options` ; le dossier est supprimé en cas d'échec ou d'annulation
+pub fn clone_repo(
+    url: &str,
+    options: &CloneOptions,
+    handle: &CloneHandle,
+    on_progress: &mut dyn FnMut(CloneProgress),
+) -> Result<PathBuf, String> {
+    let (url, dest) = clone_destination(url, options)?;
+
+    let mut result = run_git_with_progress(&clone_args(&url, &dest, options), handle, on_progress);
+    if result.is_ok() && !options.sparse_paths.is_empty() {
+        let mut args = vec![
+            "-C".to_string(),
+            dest.to_string_lossy().to_string(),
+            "sparse-checkout".to_string(),
+            "set".to_string(),
+        ];
+        args.extend(options.sparse_paths.iter().cloned());
+        result = run_git_with_progress(&args, handle, on_progress);
+    }
+
+    match result {
+        Ok(()) => Ok(dest),
+        Err(e) => {
+            let _ = std::fs::remove_dir_all(&dest);
+            Err(e)
+        }
+    }
+}
+
+/// Clone un dépôt Git dans le dossier cible
+#[tauri::command]
+pub async fn clone_git_repo(
+    url: String,
+    target_dir: Option<String>,
+    options: Option<CloneOptions>,
+) -> Result<String, String> {
+    let mut options = options.unwrap_or_default();
+    if target_dir.is_some() {
+        options.target_dir = target_dir;
+    }
+
+    let path = clone_repo(&url, &options, &CloneHandle::default(), &mut |_| {})?;
+    let cloned = path.to_string_lossy().to_string();
+    // Un échec d'écriture des réglages ne doit pas faire échouer le clone
+    let _ = remember_clone(&cloned);
+    Ok(cloned)
+}
+
+/// Lance un clone en arrière-plan : events clone:progress puis clone:finished,
+/// avec le projet proposé par l'autoscan
+#[tauri::command]
+pub async fn start_clone(
+    app: AppHandle,
+    url: String,
+    options: Option<CloneOptions>,
+) -> Result<CloneRunId, String> {
+    let run_id = uuid::Uuid::new_v4().to_string();
+    let handle = Arc::new(CloneHandle::default());
+    CLONES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(run_id.clone(), handle.clone());
+
+    let id = run_id.clone();
+    thread::spawn(move || {
+        let options = options.unwrap_or_default();
+        let result = clone_repo(&url, &options, &handle, &mut |progress| {
+            let event = CloneProgressEvent {
+                run_id: id.clone(),
+                progress,
+            };
+            emit_redacted(&app, "clone:progress", &event);
+        });
+        CLONES.lock().unwrap_or_else(|e| e.into_inner()).remove(&id);
+
+        let event = match result {
+            Ok(path) => {
+                let cloned = path.to_string_lossy().to_string();
+                let _ = remember_clone(&cloned);
+                CloneFinishedEvent {
+                    run_id: id,
+                    project: project_v3_from_root(&path).ok(),
+                    path: Some(cloned),
+                    error: None,
+                    cancelled: false,
+                }
+            }
+            Err(e) => CloneFinishedEvent {
+                run_id: id,
+                path: None,
+                project: None,
+                error: Some(e),
+                cancelled: handle.is_cancelled(),
+            },
+        };
+        emit_redacted(&app, "clone:finished", &event);
+    });
+
+    Ok(CloneRunId { run_id })
+}
+
+/// Annule un clone lancé par `start_clone`
+#[tauri::command]
+pub async fn cancel_clone(run_id: String) -> Result<(), String> {
+    let handle = CLONES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&run_id)
+        .cloned()
+        .ok_or_else(|| format!("Aucun clone en cours pour {}", run_id))?;
+    handle.cancel();
+    Ok(())
 }
 
 /// Met à jour un dépôt Git existant (git pull)
@@ -108,50 +427,17 @@ pub async fn pull_git_repo(project_path: String) -> Result<String, String> {
     
     if !output.status.success() {
         let error_msg = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("Git pull failed: {}", error_msg));
+        return Err(format!("Git pull failed: {}", redact(&error_msg)));
     }
     
     let stdout = String::from_utf8_lossy(&output.stdout);
-    Ok(stdout.to_string())
+    Ok(redact(&stdout))
 }
 
 /// Extrait le nom du projet depuis une URL Git
 fn extract_project_name_from_url(url: &str) -> Result<String, String> {
-    let mut url = url.trim().to_string();
-
-    // Supprimer les protocoles (https://, http://, git@, ssh://)
-    if url.starts_with("https://") {
-        url = url[8..].to_string();
-    } else if url.starts_with("http://") {
-        url = url[7..].to_string();
-    } else if url.starts_with("git@") {
-        // Format: git@github.com:user/repo.git
-        if let Some(colon_pos) = url.find(':') {
-            url = url[colon_pos + 1..].to_string();
-        } else {
-            return Err("Invalid Git URL format (git@)".to_string());
-        }
-    } else if url.starts_with("ssh://") {
-        url = url[6..].to_string();
-    }
-
-    // Supprimer le domaine et le chemin jusqu'au dernier /
-    let parts: Vec<&str> = url.split('/').collect();
-    if parts.is_empty() {
-        return Err("Invalid Git URL format".to_string());
-    }
-
-    // Prendre la dernière partie (nom du repo)
-    let repo_name = parts.last().unwrap().trim_end_matches(".git");
-
-    if repo_name.is_empty() {
-        return Err("Invalid Git URL format: empty repository name".to_string());
-    }
-
     // Formater le nom : gestionmaxops -> "GestionMax Ops"
-    let formatted_name = format_project_name(repo_name);
-
-    Ok(formatted_name)
+    Ok(format_project_name(&GitRemote::parse(url)?.repo))
 }
 
 /// Formate un nom de projet (ex: "gestionmaxops" -> "GestionMax Ops")
@@ -175,6 +461,120 @@ fn format_project_name(name: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(["-c", "user.name=Dev", "-c", "user.email=dev@example.com"])
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {:?}", args);
+    }
+
+    /// Dépôt nu local : api/ et web/ sur main, tag v1 sur le premier commit
+    fn bare_repo(root: &Path) -> String {
+        let work = root.join("work");
+        fs::create_dir_all(work.join("api")).unwrap();
+        fs::create_dir_all(work.join("web")).unwrap();
+        fs::write(
+            work.join("api/package.json"),
+            r#"{"dependencies": {"express": "4"}}"#,
+        )
+        .unwrap();
+        fs::write(
+            work.join("web/package.json"),
+            r#"{"dependencies": {"next": "15"}}"#,
+        )
+        .unwrap();
+        git(&work, &["init", "-q", "-b", "main"]);
+        git(&work, &["add", "."]);
+        git(&work, &["commit", "-q", "-m", "first"]);
+        git(&work, &["tag", "v1"]);
+        fs::write(work.join("README.md"), "second").unwrap();
+        git(&work, &["add", "."]);
+        git(&work, &["commit", "-q", "-m", "second"]);
+        git(root, &["clone", "-q", "--bare", "work", "site.git"]);
+        format!("file://{}", root.join("site.git").display())
+    }
+
+    #[test]
+    fn test_parse_progress() {
+        let progress =
+            parse_progress("Receiving objects:  45% (450/1000), 1.20 MiB | 2.00 MiB/s").unwrap();
+        assert_eq!(progress.phase, "Receiving objects");
+        assert_eq!(progress.percent, Some(45));
+        assert_eq!((progress.current, progress.total), (Some(450), Some(1000)));
+
+        let remote = parse_progress("remote: Enumerating objects: 5, done.").unwrap();
+        assert_eq!(remote.phase, "Enumerating objects");
+        assert_eq!((remote.percent, remote.current), (None, Some(5)));
+
+        assert_eq!(parse_progress("Cloning into 'site'..."), None);
+        assert_eq!(
+            parse_progress("warning: redirecting to https://example.com"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_clone_local_bare_repo() {
+        let root = std::env::temp_dir().join(format!("gmdev-clone-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&root).unwrap();
+        let url = bare_repo(&root);
+        let target = root.join("clones").to_string_lossy().to_string();
+
+        // Tag, historique complet, sparse checkout et nom imposé
+        let options = CloneOptions {
+            target_dir: Some(target.clone()),
+            name: Some("site-api".to_string()),
+            reference: Some("v1".to_string()),
+            depth: None,
+            sparse_paths: vec!["api".to_string()],
+            ..CloneOptions::default()
+        };
+        let mut phases = Vec::new();
+        let path = clone_repo(&url, &options, &CloneHandle::default(), &mut |p| {
+            phases.push(p.phase)
+        })
+        .unwrap();
+        assert_eq!(path, root.join("clones/site-api"));
+        assert!(path.join("api/package.json").exists());
+        assert!(!path.join("web").exists());
+        assert!(!path.join("README.md").exists());
+        assert!(!phases.is_empty());
+
+        // Options par défaut : superficiel, dossier nommé d'après l'URL
+        let defaults = CloneOptions {
+            target_dir: Some(target.clone()),
+            ..CloneOptions::default()
+        };
+        let path = clone_repo(&url, &defaults, &CloneHandle::default(), &mut |_| {}).unwrap();
+        assert_eq!(path, root.join("clones/Site"));
+        assert!(path.join("README.md").exists());
+        assert!(path.join(".git/shallow").exists());
+        let project = project_v3_from_root(&path).unwrap();
+        assert_eq!(project.backend_path, path.join("api").to_string_lossy());
+
+        assert!(clone_repo(&url, &defaults, &CloneHandle::default(), &mut |_| {}).is_err());
+
+        // Annulé : erreur et dossier supprimé
+        let handle = CloneHandle::default();
+        handle.cancel();
+        let cancelled = CloneOptions {
+            name: Some("cancelled".to_string()),
+            ..defaults
+        };
+        assert_eq!(
+            clone_repo(&url, &cancelled, &handle, &mut |_| {}).unwrap_err(),
+            "Clone annulé"
+        );
+        assert!(!root.join("clones/cancelled").exists());
+
+        let _ = fs::remove_dir_all(root);
+    }
 
     #[test]
     fn test_extract_project_name() {
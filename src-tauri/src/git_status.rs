@@ -0,0 +1,423 @@
+use notify_debouncer_mini::notify::RecursiveMode;
+use notify_debouncer_mini::{new_debouncer, DebounceEventResult};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+use tauri::AppHandle;
+
+use crate::autoscan::ProjectV3;
+use crate::projects_v3::{config_file_path, read_config};
+use crate::secrets::{emit_redacted, redact, serialize_redacted_opt};
+
+/// Rafraîchissement périodique (fetch externe, commits faits hors des dossiers surveillés)
+const REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Délai de regroupement des événements (checkout, commit, sauvegarde de fichiers)
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Fichiers de `.git` qui signalent une opération en cours, par ordre de priorité
+const OPERATION_MARKERS: &[(&str, &str)] = &[
+    ("rebase-merge", "rebase"),
+    ("rebase-apply", "rebase"),
+    ("MERGE_HEAD", "merge"),
+    ("CHERRY_PICK_HEAD", "cherry-pick"),
+    ("REVERT_HEAD", "revert"),
+    ("BISECT_LOG", "bisect"),
+];
+
+/// Dernier état connu de chaque projet, pour n'émettre que les changements
+static STATUSES: Mutex<BTreeMap<String, ProjectGitStatus>> = Mutex::new(BTreeMap::new());
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct LastCommit {
+    pub hash: String,
+    pub summary: String,
+    pub author: String,
+    /// Date du commit (RFC 3339)
+    pub date: String,
+}
+
+/// État d'un dépôt git
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct RepoStatus {
+    /// Racine du dépôt (`git rev-parse --show-toplevel`)
+    pub root: String,
+    pub git_dir: String,
+    /// `None` pour une HEAD détachée
+    pub branch: Option<String>,
+    pub upstream: Option<String>,
+    pub ahead: u32,
+    pub behind: u32,
+    pub staged: u32,
+    pub unstaged: u32,
+    pub untracked: u32,
+    pub conflicted: u32,
+    pub stashes: u32,
+    pub last_commit: Option<LastCommit>,
+    /// "merge", "rebase", "cherry-pick", "revert" ou "bisect"
+    pub operation: Option<String>,
+}
+
+/// État git d'un chemin du projet (racine, backend ou frontend)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PathGitStatus {
+    /// "root", "backend" ou "frontend"
+    pub role: String,
+    pub path: String,
+    pub status: Option<RepoStatus>,
+    /// Chemin absent, pas un dépôt git, git indisponible...
+    #[serde(serialize_with = "serialize_redacted_opt")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ProjectGitStatus {
+    pub project_id: String,
+    pub paths: Vec<PathGitStatus>,
+}
+
+/// Payload de l'event `git:error` : suivi git interrompu ou configuration illisible
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct GitWatchError {
+    pub error: String,
+}
+
+fn emit_error(app: &AppHandle, error: String) {
+    emit_redacted(app, "git:error", &GitWatchError { error });
+}
+
+pub(crate) fn git(dir: &Path, args: &[&str]) -> Result<String, String> {
+    // Pas de verrou ni de réécriture de l'index : la surveillance ne se déclenche pas elle-même
+    let output = Command::new("git")
+        .arg("--no-optional-locks")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .map_err(|e| format!("Failed to execute git: {}", e))?;
+    if !output.status.success() {
+        // L'erreur remonte telle quelle à l'interface (URL avec jeton, par exemple)
+        return Err(redact(String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Sortie de `git status --porcelain=v2 --branch`
+fn parse_porcelain(output: &str, status: &mut RepoStatus) {
+    for line in output.lines() {
+        if let Some(header) = line.strip_prefix("# ") {
+            let (key, value) = header.split_once(' ').unwrap_or((header, ""));
+            match key {
+                "branch.head" if value != "(detached)" => status.branch = Some(value.to_string()),
+                "branch.upstream" => status.upstream = Some(value.to_string()),
+                "branch.ab" => {
+                    for part in value.split_whitespace() {
+                        if let Some(n) = part.strip_prefix('+') {
+                            status.ahead = n.parse().unwrap_or(0);
+                        } else if let Some(n) = part.strip_prefix('-') {
+                            status.behind = n.parse().unwrap_or(0);
+                        }
+                    }
+                }
+                _ => {}
+            }
+            continue;
+        }
+
+        let mut fields = line.split(' ');
+        match fields.next() {
+            Some("1") | Some("2") => {
+                let xy = fields.next().unwrap_or("..").as_bytes();
+                if xy.first().is_some_and(|x| *x != b'.') {
+                    status.staged += 1;
+                }
+                if xy.get(1).is_some_and(|y| *y != b'.') {
+                    status.unstaged += 1;
+                }
+            }
+            Some("u") => status.conflicted += 1,
+            Some("?") => status.untracked += 1,
+            _ => {}
+        }
+    }
+}
+
+fn last_commit(dir: &Path) -> Option<LastCommit> {
+    let output = git(dir, &["log", "-1", "--format=%H%x1f%s%x1f%an%x1f%cI"]).ok()?;
+    let mut parts = output.trim_end().split('\u{1f}');
+    Some(LastCommit {
+        hash: parts.next().filter(|h| !h.is_empty())?.to_string(),
+        summary: parts.next().unwrap_or_default().to_string(),
+        author: parts.next().unwrap_or_default().to_string(),
+        date: parts.next().unwrap_or_default().to_string(),
+    })
+}
+
+fn operation(git_dir: &Path) -> Option<String> {
+    OPERATION_MARKERS
+        .iter()
+        .find(|(marker, _)| git_dir.join(marker).exists())
+        .map(|(_, operation)| operation.to_string())
+}
+
+/// Racine et dossier `.git` du dépôt contenant `dir`
+pub(crate) fn locate_repo(dir: &Path) -> Result<(String, String), String> {
+    if !dir.is_dir() {
+        return Err(format!("Dossier introuvable: {}", dir.display()));
+    }
+    let output = git(dir, &["rev-parse", "--show-toplevel", "--absolute-git-dir"])?;
+    let mut lines = output.lines();
+    match (lines.next(), lines.next()) {
+        (Some(root), Some(git_dir)) => Ok((root.to_string(), git_dir.to_string())),
+        _ => Err(format!("Pas un dépôt git: {}", dir.display())),
+    }
+}
+
+/// État complet du dépôt qui contient `dir`
+pub fn repo_status(dir: &Path) -> Result<RepoStatus, String> {
+    let (root, git_dir) = locate_repo(dir)?;
+    let root_dir = PathBuf::from(&root);
+
+    let mut status = RepoStatus {
+        operation: operation(Path::new(&git_dir)),
+        root,
+        git_dir,
+        ..RepoStatus::default()
+    };
+    parse_porcelain(
+        &git(&root_dir, &["status", "--porcelain=v2", "--branch"])?,
+        &mut status,
+    );
+    status.last_commit = last_commit(&root_dir);
+    status.stashes = git(&root_dir, &["stash", "list"])
+        .map(|out| out.lines().count() as u32)
+        .unwrap_or(0);
+    Ok(status)
+}
+
+fn project_paths(project: &ProjectV3) -> Vec<(&'static str, &str)> {
+    [
+        ("root", project.root_path.as_str()),
+        ("backend", project.backend_path.as_str()),
+        ("frontend", project.frontend_path.as_str()),
+    ]
+    .into_iter()
+    .filter(|(_, path)| !path.trim().is_empty())
+    .collect()
+}
+
+/// État git des chemins de chaque projet ; un dépôt partagé n'est interrogé qu'une fois
+pub fn collect_statuses(projects: &[ProjectV3]) -> Vec<ProjectGitStatus> {
+    let mut repos: BTreeMap<String, Result<RepoStatus, String>> = BTreeMap::new();
+
+    projects
+        .iter()
+        .map(|project| {
+            let paths = project_paths(project)
+                .into_iter()
+                .map(|(role, path)| {
+                    let result = locate_repo(Path::new(path)).and_then(|(root, _)| {
+                        repos
+                            .entry(root)
+                            .or_insert_with_key(|root| repo_status(Path::new(root)))
+                            .clone()
+                    });
+                    let (status, error) = match result {
+                        Ok(status) => (Some(status), None),
+                        Err(e) => (None, Some(e)),
+                    };
+                    PathGitStatus {
+                        role: role.to_string(),
+                        path: path.to_string(),
+                        status,
+                        error,
+                    }
+                })
+                .collect();
+            ProjectGitStatus {
+                project_id: project.id.clone(),
+                paths,
+            }
+        })
+        .collect()
+}
+
+/// Met le cache à jour et renvoie les projets dont l'état a changé
+fn refresh() -> Result<(Vec<ProjectGitStatus>, Vec<ProjectGitStatus>), String> {
+    let config = read_config()?;
+    let statuses = collect_statuses(&config.projects);
+
+    let mut cache = STATUSES.lock().unwrap_or_else(|e| e.into_inner());
+    let changed = statuses
+        .iter()
+        .filter(|s| cache.get(&s.project_id) != Some(*s))
+        .cloned()
+        .collect();
+    *cache = statuses
+        .iter()
+        .map(|s| (s.project_id.clone(), s.clone()))
+        .collect();
+    Ok((statuses, changed))
+}
+
+/// Dossiers à surveiller : arbre de travail (premier niveau), `.git` et ses refs
+fn watched_dirs(statuses: &[ProjectGitStatus]) -> BTreeSet<PathBuf> {
+    statuses
+        .iter()
+        .flat_map(|s| &s.paths)
+        .filter_map(|p| p.status.as_ref())
+        .flat_map(|status| {
+            let git_dir = PathBuf::from(&status.git_dir);
+            [PathBuf::from(&status.root), git_dir.join("refs"), git_dir]
+        })
+        .filter(|dir| dir.is_dir())
+        .collect()
+}
+
+/// Suit l'état git des projets : à intervalle régulier et à chaque changement dans
+/// un dépôt ou dans projects-v3.json ; émet `git:status` avec les projets modifiés
+/// et `git:error` quand le suivi échoue
+pub fn start_git_status_watcher(app: AppHandle) {
+    thread::spawn(move || {
+        let (tx, rx) = mpsc::channel::<DebounceEventResult>();
+        let mut debouncer = match new_debouncer(DEBOUNCE, tx) {
+            Ok(debouncer) => debouncer,
+            Err(e) => {
+                emit_error(&app, format!("Suivi git indisponible: {}", e));
+                return;
+            }
+        };
+        let config_file = config_file_path();
+        if let Some(dir) = config_file.parent() {
+            let _ = debouncer.watcher().watch(dir, RecursiveMode::NonRecursive);
+        }
+        let mut dirs: BTreeSet<PathBuf> = BTreeSet::new();
+
+        loop {
+            match refresh() {
+                Ok((statuses, changed)) => {
+                    if !changed.is_empty() {
+                        emit_redacted(&app, "git:status", &changed);
+                    }
+                    let watched = watched_dirs(&statuses);
+                    for dir in dirs.difference(&watched) {
+                        let _ = debouncer.watcher().unwatch(dir);
+                    }
+                    for dir in watched.difference(&dirs) {
+                        let mode = if dir.ends_with("refs") {
+                            RecursiveMode::Recursive
+                        } else {
+                            RecursiveMode::NonRecursive
+                        };
+                        let _ = debouncer.watcher().watch(dir, mode);
+                    }
+                    dirs = watched;
+                }
+                Err(e) => emit_error(&app, format!("Suivi git: {}", e)),
+            }
+
+            // Attendre l'intervalle ou un changement (hors fichiers de verrou de git)
+            loop {
+                match rx.recv_timeout(REFRESH_INTERVAL) {
+                    Ok(Ok(events)) => {
+                        if events.iter().any(|e| {
+                            e.path == config_file
+                                || (e.path.parent() != config_file.parent()
+                                    && e.path.extension().is_none_or(|ext| ext != "lock"))
+                        }) {
+                            break;
+                        }
+                    }
+                    Ok(Err(_)) => continue,
+                    Err(RecvTimeoutError::Timeout) => break,
+                    Err(RecvTimeoutError::Disconnected) => return,
+                }
+            }
+        }
+    });
+}
+
+/// État git de tous les projets (ou de ceux demandés), en un seul appel
+#[tauri::command]
+pub async fn git_status_all(
+    project_ids: Option<Vec<String>>,
+) -> Result<Vec<ProjectGitStatus>, String> {
+    let config = read_config()?;
+    let projects: Vec<ProjectV3> = config
+        .projects
+        .into_iter()
+        .filter(|p| project_ids.as_ref().is_none_or(|ids| ids.contains(&p.id)))
+        .collect();
+    Ok(collect_statuses(&projects))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_porcelain() {
+        let output = "\
+# branch.oid 1f0c2a
+# branch.head feature/login
+# branch.upstream origin/feature/login
+# branch.ab +2 -3
+1 M. N... 100644 100644 100644 aaa bbb src/a.ts
+1 .M N... 100644 100644 100644 aaa bbb src/b.ts
+1 MM N... 100644 100644 100644 aaa bbb src/c.ts
+2 R. N... 100644 100644 100644 aaa bbb R100 src/new.ts\tsrc/old.ts
+u UU N... 100644 100644 100644 100644 aaa bbb ccc src/conflict.ts
+? notes.md
+? tmp/
+";
+        let mut status = RepoStatus::default();
+        parse_porcelain(output, &mut status);
+        assert_eq!(status.branch.as_deref(), Some("feature/login"));
+        assert_eq!(status.upstream.as_deref(), Some("origin/feature/login"));
+        assert_eq!((status.ahead, status.behind), (2, 3));
+        assert_eq!((status.staged, status.unstaged), (3, 2));
+        assert_eq!((status.conflicted, status.untracked), (1, 2));
+
+        let mut detached = RepoStatus::default();
+        parse_porcelain(
+            "# branch.oid 1f0c2a\n# branch.head (detached)\n",
+            &mut detached,
+        );
+        assert_eq!(detached.branch, None);
+        assert_eq!(detached.upstream, None);
+    }
+
+    #[test]
+    fn test_repo_status() {
+        let dir = std::env::temp_dir().join(format!("gmdev-git-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("backend")).unwrap();
+        let run = |args: &[&str]| {
+            let mut full = vec!["-c", "user.name=Dev", "-c", "user.email=dev@example.com"];
+            full.extend_from_slice(args);
+            git(&dir, &full).unwrap();
+        };
+        run(&["init", "-q", "-b", "main"]);
+        std::fs::write(dir.join("backend/app.js"), "1").unwrap();
+        run(&["add", "."]);
+        run(&["commit", "-q", "-m", "Initial commit"]);
+        std::fs::write(dir.join("backend/app.js"), "2").unwrap();
+        std::fs::write(dir.join("notes.md"), "").unwrap();
+        std::fs::write(dir.join(".git/MERGE_HEAD"), "").unwrap();
+
+        let status = repo_status(&dir.join("backend")).unwrap();
+        assert_eq!(status.branch.as_deref(), Some("main"));
+        assert_eq!((status.unstaged, status.untracked), (1, 1));
+        assert_eq!(status.operation.as_deref(), Some("merge"));
+        assert_eq!(
+            status.last_commit.map(|c| c.summary).as_deref(),
+            Some("Initial commit")
+        );
+        assert!(repo_status(&dir.join("missing")).is_err());
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,406 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use std::process::Command;
+use std::thread;
+
+use crate::git_status::{git, locate_repo, repo_status};
+use crate::projects_v3::read_config;
+use crate::secrets::serialize_redacted_opt;
+
+/// Fichiers dont la modification impose de réinstaller les dépendances
+const DEPENDENCY_FILES: &[&str] = &[
+    "package.json",
+    "package-lock.json",
+    "npm-shrinkwrap.json",
+    "pnpm-lock.yaml",
+    "yarn.lock",
+    "bun.lockb",
+    "composer.lock",
+    "Gemfile.lock",
+    "poetry.lock",
+    "requirements.txt",
+    "Cargo.lock",
+    "go.sum",
+];
+
+/// Message des stash créés par la synchronisation
+const AUTO_STASH_MESSAGE: &str = "gmdev: auto-stash avant synchronisation";
+
+/// Manière d'intégrer les commits de la branche amont
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum SyncStrategy {
+    /// Avance rapide uniquement : refuse si la branche locale a divergé
+    #[default]
+    FfOnly,
+    Rebase,
+    Merge,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SyncOptions {
+    pub strategy: SyncStrategy,
+    /// Met de côté les modifications locales et les restaure après la mise à jour
+    pub auto_stash: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SyncedCommit {
+    pub hash: String,
+    pub summary: String,
+    pub author: String,
+}
+
+/// Résultat d'une synchronisation
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct SyncResult {
+    /// Racine du dépôt
+    pub path: String,
+    pub branch: Option<String>,
+    pub upstream: Option<String>,
+    /// "up-to-date", "updated", "dirty", "diverged", "conflict" ou "failed"
+    pub outcome: String,
+    #[serde(serialize_with = "serialize_redacted_opt")]
+    pub message: Option<String>,
+    pub before: Option<String>,
+    pub after: Option<String>,
+    /// Commits récupérés depuis la branche amont
+    pub commits: Vec<SyncedCommit>,
+    pub files: Vec<String>,
+    /// package.json ou un lockfile a changé : dépendances à réinstaller
+    pub dependencies_changed: bool,
+    /// Fichiers en conflit prévus (rien n'est modifié dans ce cas)
+    pub conflicts: Vec<String>,
+    pub stashed: bool,
+    /// `false` si le stash n'a pas pu être réappliqué (il est conservé)
+    pub stash_restored: bool,
+}
+
+/// Synchronisation d'un dépôt partagé par un ou plusieurs projets
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RepoSyncResult {
+    pub path: String,
+    pub project_ids: Vec<String>,
+    pub result: Option<SyncResult>,
+    #[serde(serialize_with = "serialize_redacted_opt")]
+    pub error: Option<String>,
+}
+
+/// Comme `git`, mais renvoie aussi le code de sortie (merge-tree sort en 1 sur conflit)
+fn git_with_code(dir: &Path, args: &[&str]) -> Result<(i32, String), String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .map_err(|e| format!("Failed to execute git: {}", e))?;
+    Ok((
+        output.status.code().unwrap_or(-1),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+    ))
+}
+
+fn lines(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+pub(crate) fn is_dependency_file(file: &str) -> bool {
+    let name = file.rsplit('/').next().unwrap_or(file);
+    DEPENDENCY_FILES.contains(&name)
+}
+
+/// Fichiers en conflit si la branche amont était fusionnée maintenant (sans rien écrire)
+fn predicted_conflicts(root: &Path) -> Result<Vec<String>, String> {
+    let (code, output) = git_with_code(
+        root,
+        &["merge-tree", "--write-tree", "--name-only", "HEAD", "@{u}"],
+    )?;
+    match code {
+        0 => Ok(Vec::new()),
+        // Première ligne : arbre résultant, puis les fichiers en conflit jusqu'à la ligne vide
+        1 => Ok(output
+            .lines()
+            .skip(1)
+            .take_while(|l| !l.is_empty())
+            .map(str::to_string)
+            .collect()),
+        _ => Err("Impossible de prévoir les conflits (git 2.38 ou plus requis)".to_string()),
+    }
+}
+
+/// Intègre la branche amont selon la stratégie ; annule l'opération en cas d'échec
+fn integrate(root: &Path, strategy: SyncStrategy) -> Result<(), String> {
+    let (args, abort): (&[&str], Option<&[&str]>) = match strategy {
+        SyncStrategy::FfOnly => (&["merge", "--ff-only", "@{u}"], None),
+        SyncStrategy::Rebase => (&["rebase", "@{u}"], Some(&["rebase", "--abort"])),
+        SyncStrategy::Merge => (&["merge", "--no-edit", "@{u}"], Some(&["merge", "--abort"])),
+    };
+    git(root, args).map(|_| ()).inspect_err(|_| {
+        if let Some(abort) = abort {
+            let _ = git(root, abort);
+        }
+    })
+}
+
+/// Récupère et intègre la branche amont du dépôt contenant `dir`
+///
+/// Rien n'est modifié si le dépôt a des modifications locales sans `auto_stash`,
+/// si l'avance rapide est impossible en `ff-only`, ou si un conflit est prévu
+/// (entre les branches, ou entre les modifications locales et les fichiers entrants).
+pub fn sync_repo_at(dir: &Path, options: &SyncOptions) -> Result<SyncResult, String> {
+    let status = repo_status(dir)?;
+    let root = Path::new(&status.root);
+    let mut result = SyncResult {
+        path: status.root.clone(),
+        branch: status.branch.clone(),
+        upstream: status.upstream.clone(),
+        ..SyncResult::default()
+    };
+
+    if let Some(operation) = &status.operation {
+        return Err(format!(
+            "Opération {} en cours dans {}",
+            operation, status.root
+        ));
+    }
+    if status.branch.is_none() {
+        return Err(format!("HEAD détachée dans {}", status.root));
+    }
+    if status.upstream.is_none() {
+        return Err(format!("Aucune branche amont pour {}", status.root));
+    }
+
+    git(root, &["fetch", "--quiet"])?;
+    let before = git(root, &["rev-parse", "HEAD"])?.trim().to_string();
+    result.before = Some(before.clone());
+    let counts = git(
+        root,
+        &["rev-list", "--left-right", "--count", "HEAD...@{u}"],
+    )?;
+    let mut counts = counts
+        .split_whitespace()
+        .map(|n| n.parse::<u32>().unwrap_or(0));
+    let (ahead, behind) = (counts.next().unwrap_or(0), counts.next().unwrap_or(0));
+
+    if behind == 0 {
+        result.outcome = "up-to-date".to_string();
+        result.after = Some(before);
+        return Ok(result);
+    }
+
+    let incoming = lines(&git(root, &["diff", "--name-only", "HEAD", "@{u}"])?);
+    let dirty = status.staged + status.unstaged + status.conflicted > 0;
+    if dirty && !options.auto_stash {
+        result.outcome = "dirty".to_string();
+        result.message =
+            Some("Modifications locales : activez l'auto-stash ou validez-les".to_string());
+        return Ok(result);
+    }
+    if ahead > 0 && options.strategy == SyncStrategy::FfOnly {
+        result.outcome = "diverged".to_string();
+        result.message = Some(format!(
+            "{} commit(s) locaux et {} distant(s) : choisissez rebase ou merge",
+            ahead, behind
+        ));
+        return Ok(result);
+    }
+
+    // Conflits prévus : entre les branches, puis entre les modifications locales et l'amont
+    let mut conflicts: BTreeSet<String> = BTreeSet::new();
+    if ahead > 0 {
+        conflicts.extend(predicted_conflicts(root)?);
+    }
+    if dirty {
+        let local = lines(&git(root, &["diff", "--name-only", "HEAD"])?);
+        conflicts.extend(local.into_iter().filter(|f| incoming.contains(f)));
+    }
+    if !conflicts.is_empty() {
+        result.outcome = "conflict".to_string();
+        result.conflicts = conflicts.into_iter().collect();
+        return Ok(result);
+    }
+
+    let log = git(root, &["log", "--format=%H%x1f%s%x1f%an", "HEAD..@{u}"])?;
+    result.commits = log
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split('\u{1f}');
+            Some(SyncedCommit {
+                hash: parts.next()?.to_string(),
+                summary: parts.next().unwrap_or_default().to_string(),
+                author: parts.next().unwrap_or_default().to_string(),
+            })
+        })
+        .collect();
+
+    if dirty || (status.untracked > 0 && options.auto_stash) {
+        git(
+            root,
+            &[
+                "stash",
+                "push",
+                "--include-untracked",
+                "-m",
+                AUTO_STASH_MESSAGE,
+            ],
+        )?;
+        result.stashed = true;
+    }
+
+    let integrated = integrate(root, options.strategy);
+    if result.stashed {
+        result.stash_restored = git(root, &["stash", "pop"]).is_ok();
+    }
+    if let Err(e) = integrated {
+        result.outcome = "failed".to_string();
+        result.message = Some(e);
+        return Ok(result);
+    }
+
+    let after = git(root, &["rev-parse", "HEAD"])?.trim().to_string();
+    result.files = lines(&git(root, &["diff", "--name-only", &before, &after])?);
+    result.dependencies_changed = result.files.iter().any(|f| is_dependency_file(f));
+    result.after = Some(after);
+    result.outcome = "updated".to_string();
+    if result.stashed && !result.stash_restored {
+        result.message = Some(
+            "Modifications locales conservées dans le stash (conflit à la restauration)"
+                .to_string(),
+        );
+    }
+    Ok(result)
+}
+
+/// Synchronise un dépôt (racine, backend ou frontend d'un projet)
+#[tauri::command]
+pub async fn sync_repo(path: String, options: Option<SyncOptions>) -> Result<SyncResult, String> {
+    sync_repo_at(Path::new(&path), &options.unwrap_or_default())
+}
+
+/// Synchronise en parallèle chaque dépôt des projets configurés (une fois par dépôt)
+#[tauri::command]
+pub async fn sync_all_projects(
+    options: Option<SyncOptions>,
+) -> Result<Vec<RepoSyncResult>, String> {
+    let options = options.unwrap_or_default();
+    let config = read_config()?;
+
+    let mut repos: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    for project in &config.projects {
+        for path in [
+            &project.root_path,
+            &project.backend_path,
+            &project.frontend_path,
+        ] {
+            if let Ok((root, _)) = locate_repo(Path::new(path)) {
+                repos.entry(root).or_default().insert(project.id.clone());
+            }
+        }
+    }
+
+    Ok(thread::scope(|scope| {
+        let handles: Vec<_> = repos
+            .into_iter()
+            .map(|(path, ids)| {
+                let options = &options;
+                scope.spawn(move || {
+                    let result = sync_repo_at(Path::new(&path), options);
+                    RepoSyncResult {
+                        project_ids: ids.into_iter().collect(),
+                        result: result.as_ref().ok().cloned(),
+                        error: result.err(),
+                        path,
+                    }
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .filter_map(|handle| handle.join().ok())
+            .collect()
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn run(dir: &Path, args: &[&str]) {
+        let mut full = vec!["-c", "user.name=Dev", "-c", "user.email=dev@example.com"];
+        full.extend_from_slice(args);
+        git(dir, &full).unwrap();
+    }
+
+    fn commit(dir: &Path, file: &str, content: &str, message: &str) {
+        fs::write(dir.join(file), content).unwrap();
+        run(dir, &["add", "."]);
+        run(dir, &["commit", "-q", "-m", message]);
+    }
+
+    #[test]
+    fn test_sync_repo() {
+        let root = std::env::temp_dir().join(format!("gmdev-sync-{}", uuid::Uuid::new_v4()));
+        let (upstream, local) = (root.join("upstream"), root.join("local"));
+        fs::create_dir_all(&upstream).unwrap();
+        run(&upstream, &["init", "-q", "-b", "main"]);
+        commit(&upstream, "app.js", "1\n", "init");
+        run(&root, &["clone", "-q", "upstream", "local"]);
+        // La config de l'utilisateur ne doit pas intervenir dans les commits du test
+        run(&local, &["config", "user.name", "Dev"]);
+        run(&local, &["config", "user.email", "dev@example.com"]);
+
+        let ff = SyncOptions::default();
+        assert_eq!(sync_repo_at(&local, &ff).unwrap().outcome, "up-to-date");
+
+        // Avance rapide : commits et fichiers récupérés, dépendances modifiées
+        commit(&upstream, "package.json", "{}", "add deps");
+        let result = sync_repo_at(&local, &ff).unwrap();
+        assert_eq!(result.outcome, "updated");
+        assert_eq!(result.commits.len(), 1);
+        assert_eq!(result.commits[0].summary, "add deps");
+        assert_eq!(result.files, vec!["package.json"]);
+        assert!(result.dependencies_changed);
+
+        // Modification locale d'un fichier modifié en amont : refus, puis conflit prévu
+        commit(&upstream, "app.js", "2\n", "upstream change");
+        fs::write(local.join("app.js"), "local\n").unwrap();
+        assert_eq!(sync_repo_at(&local, &ff).unwrap().outcome, "dirty");
+        let stash = SyncOptions {
+            strategy: SyncStrategy::FfOnly,
+            auto_stash: true,
+        };
+        let result = sync_repo_at(&local, &stash).unwrap();
+        assert_eq!(result.outcome, "conflict");
+        assert_eq!(result.conflicts, vec!["app.js"]);
+        assert_eq!(fs::read_to_string(local.join("app.js")).unwrap(), "local\n");
+
+        // Modification locale sans rapport : stash puis restauration
+        run(&local, &["checkout", "-q", "--", "app.js"]);
+        fs::write(local.join("notes.md"), "todo").unwrap();
+        let result = sync_repo_at(&local, &stash).unwrap();
+        assert_eq!(result.outcome, "updated");
+        assert!(result.stashed && result.stash_restored);
+        assert!(!result.dependencies_changed);
+        assert!(local.join("notes.md").exists());
+
+        // Branches divergentes : refus en ff-only, rebase sinon
+        commit(&upstream, "server.js", "a", "upstream server");
+        commit(&local, "client.js", "b", "local client");
+        assert_eq!(sync_repo_at(&local, &ff).unwrap().outcome, "diverged");
+        let rebase = SyncOptions {
+            strategy: SyncStrategy::Rebase,
+            auto_stash: true,
+        };
+        let result = sync_repo_at(&local, &rebase).unwrap();
+        assert_eq!(result.outcome, "updated");
+        assert_eq!(result.commits[0].summary, "upstream server");
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
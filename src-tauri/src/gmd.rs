@@ -2,8 +2,9 @@ use std::path::PathBuf;
 use std::process::{Command, Stdio};
 use std::io::{BufRead, BufReader};
 use serde::{Deserialize, Serialize};
-use tauri::{AppHandle, Emitter};
+use tauri::AppHandle;
 use std::thread;
+use crate::secrets::{emit_redacted, serialize_redacted};
 
 /// Structure pour représenter une commande gmdev à exécuter
 #[derive(Debug, Clone)]
@@ -11,14 +12,22 @@ pub struct GmdCommand {
     pub args: Vec<String>,
     pub cwd: Option<PathBuf>,
     pub project_id: Option<String>,
+    /// Variables d'environnement supplémentaires (ex: GMDEV_PROJECTS_CONFIG)
+    pub envs: Vec<(String, String)>,
+    /// Profil d'environnement actif, repris dans les résultats et events
+    pub profile: Option<String>,
 }
 
 /// Résultat d'une exécution de commande gmdev
 #[derive(Debug, Serialize, Deserialize)]
 pub struct GmdResult {
+    // Les secrets du coffre ne doivent jamais remonter à l'interface
+    #[serde(serialize_with = "serialize_redacted")]
     pub stdout: String,
+    #[serde(serialize_with = "serialize_redacted")]
     pub stderr: String,
     pub code: i32,
+    pub profile: Option<String>,
 }
 
 /// Réponse immédiate avec runId pour le streaming
@@ -36,6 +45,7 @@ pub struct GmdLogEvent {
     pub line: String,
     pub cmd: String,
     pub cwd: String,
+    pub profile: Option<String>,
 }
 
 /// Payload pour l'event gmd:exit
@@ -43,6 +53,7 @@ pub struct GmdLogEvent {
 pub struct GmdExitEvent {
     pub run_id: String,
     pub exit_code: i32,
+    pub profile: Option<String>,
 }
 
 /// Vérifie si gmdev est disponible dans le PATH
@@ -88,12 +99,15 @@ pub fn run_gmd(cmd: GmdCommand) -> Result<GmdResult, String> {
         process.current_dir(cwd);
     }
 
+    process.envs(cmd.envs.iter().map(|(k, v)| (k, v)));
+
     // Exécuter la commande
     match process.output() {
         Ok(output) => Ok(GmdResult {
             stdout: String::from_utf8_lossy(&output.stdout).to_string(),
             stderr: String::from_utf8_lossy(&output.stderr).to_string(),
             code: output.status.code().unwrap_or(-1),
+            profile: cmd.profile.clone(),
         }),
         Err(e) => Err(format!(
             "Erreur lors de l'exécution de gmdev: {}. Assurez-vous que gmdev est installé et dans votre PATH.",
@@ -142,6 +156,8 @@ pub fn run_gmd_streaming(
         process.current_dir(cwd);
     }
 
+    process.envs(cmd.envs.iter().map(|(k, v)| (k, v)));
+
     // Configurer les pipes pour stdout/stderr
     process.stdout(Stdio::piped());
     process.stderr(Stdio::piped());
@@ -185,6 +201,9 @@ pub fn run_gmd_streaming(
     let cmd_str_stderr = cmd_str.clone();
     let cwd_str_stdout = cwd_str.clone();
     let cwd_str_stderr = cwd_str.clone();
+    let profile_stdout = cmd.profile.clone();
+    let profile_stderr = cmd.profile.clone();
+    let profile_exit = cmd.profile.clone();
 
     // Thread pour lire stdout ligne par ligne
     thread::spawn(move || {
@@ -197,11 +216,12 @@ pub fn run_gmd_streaming(
                         run_id: run_id_stdout.clone(),
                         ts: ts.clone(),
                         level: "stdout".to_string(),
-                        line: line_content.clone(),
+                        line: line_content,
                         cmd: cmd_str_stdout.clone(),
                         cwd: cwd_str_stdout.clone(),
+                        profile: profile_stdout.clone(),
                     };
-                    let _ = app_stdout.emit("gmd:log", &event);
+                    emit_redacted(&app_stdout, "gmd:log", &event);
                 }
                 Err(_) => break, // EOF ou erreur de lecture
             }
@@ -219,11 +239,12 @@ pub fn run_gmd_streaming(
                         run_id: run_id_stderr.clone(),
                         ts: ts.clone(),
                         level: "stderr".to_string(),
-                        line: line_content.clone(),
+                        line: line_content,
                         cmd: cmd_str_stderr.clone(),
                         cwd: cwd_str_stderr.clone(),
+                        profile: profile_stderr.clone(),
                     };
-                    let _ = app_stderr.emit("gmd:log", &event);
+                    emit_redacted(&app_stderr, "gmd:log", &event);
                 }
                 Err(_) => break, // EOF ou erreur de lecture
             }
@@ -240,8 +261,9 @@ pub fn run_gmd_streaming(
         let event = GmdExitEvent {
             run_id: run_id_exit.clone(),
             exit_code,
+            profile: profile_exit,
         };
-        let _ = app_exit.emit("gmd:exit", &event);
+        emit_redacted(&app_exit, "gmd:exit", &event);
     });
 
     Ok(())
@@ -264,6 +286,8 @@ mod tests {
             args: vec!["--version".to_string()],
             cwd: None,
             project_id: None,
+            envs: Vec::new(),
+            profile: None,
         };
         let result = run_gmd(cmd);
         // Si gmdev est disponible, le résultat devrait être Ok
@@ -0,0 +1,176 @@
+use globset::{GlobBuilder, GlobMatcher};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::settings::read_settings;
+
+/// Dossiers toujours ignorés (nom exact)
+const BUILTIN_IGNORES: &[&str] = &[
+    "_archive",
+    "archive",
+    "docs",
+    "documentation",
+    "frontend-backup",
+    "backend-backup",
+    ".git",
+    ".vscode",
+    ".idea",
+    "__tests__",
+    "dist",
+    "node_modules",
+];
+
+/// Fichiers de règles lus à la racine scannée, le dernier l'emporte
+const IGNORE_FILES: &[&str] = &[".gitignore", ".gmdevignore"];
+
+/// Dossier écarté par le scan et la règle responsable
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SkippedDir {
+    pub path: String,
+    pub pattern: String,
+    /// "builtin", "settings", ".gitignore" ou ".gmdevignore"
+    pub origin: String,
+}
+
+#[derive(Debug, Clone)]
+struct IgnoreRule {
+    pattern: String,
+    origin: String,
+    /// `!motif` : réinclut un dossier ignoré par une règle précédente
+    negated: bool,
+    /// Motif contenant un `/` : comparé au chemin relatif à la racine, sinon au nom
+    anchored: bool,
+    matcher: GlobMatcher,
+}
+
+impl IgnoreRule {
+    /// Ligne au format .gitignore ; `None` pour un commentaire ou un motif invalide
+    fn parse(line: &str, origin: &str) -> Option<Self> {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+        let (negated, body) = match line.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, line.strip_prefix('\\').unwrap_or(line)),
+        };
+        // Seuls des dossiers sont comparés : `dist/` équivaut à `dist`
+        let body = body.trim_end_matches('/');
+        let anchored = body.contains('/');
+        let glob = body.trim_start_matches('/');
+        if glob.is_empty() {
+            return None;
+        }
+
+        let matcher = GlobBuilder::new(glob)
+            .literal_separator(true)
+            .build()
+            .ok()?
+            .compile_matcher();
+        Some(Self {
+            pattern: line.to_string(),
+            origin: origin.to_string(),
+            negated,
+            anchored,
+            matcher,
+        })
+    }
+}
+
+/// Règles d'exclusion d'un scan : intégrées, réglages utilisateur, puis
+/// .gitignore et .gmdevignore de la racine (la dernière règle qui correspond l'emporte)
+#[derive(Debug, Clone)]
+pub struct IgnoreRules {
+    root: PathBuf,
+    rules: Vec<IgnoreRule>,
+}
+
+impl IgnoreRules {
+    pub fn new(root: &Path, user_patterns: &[String]) -> Self {
+        let mut rules: Vec<IgnoreRule> = BUILTIN_IGNORES
+            .iter()
+            .filter_map(|name| IgnoreRule::parse(name, "builtin"))
+            .collect();
+        rules.extend(
+            user_patterns
+                .iter()
+                .filter_map(|pattern| IgnoreRule::parse(pattern, "settings")),
+        );
+        for file in IGNORE_FILES {
+            if let Ok(content) = fs::read_to_string(root.join(file)) {
+                rules.extend(content.lines().filter_map(|l| IgnoreRule::parse(l, file)));
+            }
+        }
+
+        Self {
+            root: root.to_path_buf(),
+            rules,
+        }
+    }
+
+    /// Règles d'une racine avec les motifs des réglages de l'application
+    pub fn for_root(root: &Path) -> Self {
+        let settings = read_settings().unwrap_or_default();
+        Self::new(root, &settings.ignore_patterns)
+    }
+
+    /// Règle qui exclut `dir`, `None` s'il doit être parcouru
+    pub fn skip(&self, dir: &Path) -> Option<SkippedDir> {
+        let name = dir.file_name()?.to_string_lossy();
+        let relative = dir
+            .strip_prefix(&self.root)
+            .map(|p| p.to_string_lossy().replace('\\', "/"))
+            .unwrap_or_else(|_| name.to_string());
+
+        let rule = self.rules.iter().rev().find(|rule| {
+            let candidate = if rule.anchored { &relative } else { &*name };
+            rule.matcher.is_match(candidate)
+        })?;
+        if rule.negated {
+            return None;
+        }
+        Some(SkippedDir {
+            path: dir.to_string_lossy().to_string(),
+            pattern: rule.pattern.clone(),
+            origin: rule.origin.clone(),
+        })
+    }
+
+    pub fn is_ignored(&self, dir: &Path) -> bool {
+        self.skip(dir).is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ignore_rules() {
+        let root = std::env::temp_dir().join(format!("gmdev-ignore-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&root).unwrap();
+        fs::write(root.join(".gitignore"), "# build\n.next/\n/legacy\ntmp-*\n").unwrap();
+        fs::write(root.join(".gmdevignore"), "!docs\nclients/*/old\n").unwrap();
+
+        let rules = IgnoreRules::new(&root, &["*-backup-*".to_string()]);
+        let skip = |path: &str| rules.skip(&root.join(path)).map(|s| s.origin);
+
+        // Correspondance exacte, plus de préfixe
+        assert_eq!(skip("docsite"), None);
+        assert_eq!(skip("distribution-api"), None);
+        assert_eq!(skip("node_modules").as_deref(), Some("builtin"));
+        assert_eq!(skip("docs"), None);
+        assert_eq!(skip("site/.next").as_deref(), Some(".gitignore"));
+        assert_eq!(skip("legacy").as_deref(), Some(".gitignore"));
+        assert_eq!(skip("apps/legacy"), None);
+        assert_eq!(skip("tmp-2024").as_deref(), Some(".gitignore"));
+        assert_eq!(skip("clients/acme/old").as_deref(), Some(".gmdevignore"));
+        assert_eq!(skip("site-backup-v1").as_deref(), Some("settings"));
+
+        let skipped = rules.skip(&root.join("legacy")).unwrap();
+        assert_eq!(skipped.pattern, "/legacy");
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
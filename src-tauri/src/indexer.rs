@@ -0,0 +1,375 @@
+use chrono::Utc;
+use notify_debouncer_mini::notify::RecursiveMode;
+use notify_debouncer_mini::{new_debouncer, DebounceEventResult};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, UNIX_EPOCH};
+use tauri::AppHandle;
+
+use crate::autoscan::expand_parent_path;
+use crate::detectors::{Detection, DetectorRegistry, ServiceRole};
+use crate::ignore_rules::IgnoreRules;
+use crate::manifest::{self, MANIFEST_FILES};
+use crate::projects_v3::config_dir;
+use crate::secrets::emit_redacted;
+use crate::settings::{read_settings, settings_path, AppSettings};
+use crate::workspace::{self, Workspace};
+
+/// Catalogue persistant, relu au démarrage pour répondre avant la première indexation
+const CATALOG_FILE_NAME: &str = "catalog.json";
+
+/// Délai de regroupement des événements (clone, checkout, npm install)
+const DEBOUNCE: Duration = Duration::from_millis(1000);
+
+/// Fichiers dont la date de modification déclenche une nouvelle détection du dépôt
+const FINGERPRINT_FILES: &[&str] = &[
+    "package.json",
+    "pnpm-workspace.yaml",
+    "turbo.json",
+    "nx.json",
+    "requirements.txt",
+    "pyproject.toml",
+    "Gemfile",
+    "go.mod",
+    "manage.py",
+];
+
+/// Dépôt indexé
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CatalogRepo {
+    pub path: String,
+    pub name: String,
+    /// Racine de workspace configurée sous laquelle le dépôt a été trouvé
+    pub root: String,
+    /// "manifest", "monorepo", "backend", "frontend" ou "repository" (stack inconnue)
+    pub kind: String,
+    /// Meilleure stack backend et frontend au-dessus du seuil de confiance
+    pub stacks: Vec<Detection>,
+    pub manifest_path: Option<String>,
+    pub workspace: Option<Workspace>,
+    /// Dates de modification (ms) des fichiers qui décrivent le dépôt
+    pub fingerprint: BTreeMap<String, u64>,
+    pub indexed_at: String,
+}
+
+/// Catalogue des dépôts des racines de workspace
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct Catalog {
+    pub roots: Vec<String>,
+    /// Dépôts par chemin
+    pub repos: BTreeMap<String, CatalogRepo>,
+    pub updated_at: Option<String>,
+}
+
+/// Payload de l'event `catalog:changed`
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct CatalogChangedEvent {
+    pub added: Vec<CatalogRepo>,
+    pub removed: Vec<String>,
+    pub updated: Vec<CatalogRepo>,
+    /// Indexation impossible (réglages illisibles, surveillance indisponible)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<String>,
+}
+
+impl CatalogChangedEvent {
+    fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.updated.is_empty()
+            && self.errors.is_empty()
+    }
+}
+
+fn emit_error(app: &AppHandle, error: String) {
+    emit_redacted(
+        app,
+        "catalog:changed",
+        &CatalogChangedEvent {
+            errors: vec![error],
+            ..Default::default()
+        },
+    );
+}
+
+static CATALOG: Mutex<Option<Catalog>> = Mutex::new(None);
+
+fn catalog_path() -> PathBuf {
+    config_dir().join(CATALOG_FILE_NAME)
+}
+
+fn load_cached_catalog() -> Catalog {
+    fs::read_to_string(catalog_path())
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+fn store_catalog(catalog: &Catalog) {
+    if let Ok(text) = serde_json::to_string_pretty(catalog) {
+        let _ = fs::write(catalog_path(), text);
+    }
+}
+
+/// Catalogue en mémoire, sinon celui du dernier lancement
+pub(crate) fn current_catalog() -> Catalog {
+    let mut guard = CATALOG.lock().unwrap_or_else(|e| e.into_inner());
+    guard.get_or_insert_with(load_cached_catalog).clone()
+}
+
+fn modified_ms(path: &Path) -> Option<u64> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    Some(modified.duration_since(UNIX_EPOCH).ok()?.as_millis() as u64)
+}
+
+fn fingerprint(dir: &Path) -> BTreeMap<String, u64> {
+    FINGERPRINT_FILES
+        .iter()
+        .chain(MANIFEST_FILES)
+        .filter_map(|name| Some((name.to_string(), modified_ms(&dir.join(name))?)))
+        .collect()
+}
+
+/// Détecte ce que contient un dossier ; `None` si ce n'est ni un dépôt ni un service
+pub(crate) fn index_repo(dir: &Path, root: &str, registry: &DetectorRegistry) -> Option<CatalogRepo> {
+    let name = dir.file_name()?.to_string_lossy().to_string();
+    let mut repo = CatalogRepo {
+        path: dir.to_string_lossy().to_string(),
+        name,
+        root: root.to_string(),
+        kind: "repository".to_string(),
+        stacks: Vec::new(),
+        manifest_path: None,
+        workspace: None,
+        fingerprint: fingerprint(dir),
+        indexed_at: Utc::now().to_rfc3339(),
+    };
+
+    if let Some(path) = manifest::find_manifest(dir) {
+        repo.kind = "manifest".to_string();
+        repo.manifest_path = Some(path.to_string_lossy().to_string());
+        return Some(repo);
+    }
+    if let Some(ws) = workspace::detect_workspace(dir)
+        .ok()
+        .flatten()
+        .filter(|w| !w.apps.is_empty())
+    {
+        repo.kind = "monorepo".to_string();
+        repo.workspace = Some(ws);
+        return Some(repo);
+    }
+
+    repo.stacks = [ServiceRole::Backend, ServiceRole::Frontend]
+        .into_iter()
+        .filter_map(|role| registry.best(dir, role))
+        .collect();
+    match repo.stacks.first().map(|d| d.role) {
+        Some(ServiceRole::Backend) => repo.kind = "backend".to_string(),
+        Some(ServiceRole::Frontend) => repo.kind = "frontend".to_string(),
+        None if dir.join(".git").exists() => {}
+        None => return None,
+    }
+    Some(repo)
+}
+
+/// Parcourt les racines ; seuls les dépôts nouveaux ou dont l'empreinte a changé
+/// sont détectés à nouveau. Renvoie aussi les dossiers à surveiller.
+fn reconcile(settings: &AppSettings, previous: &Catalog) -> (Catalog, BTreeSet<PathBuf>) {
+    let registry = DetectorRegistry::with_defaults();
+    let mut catalog = Catalog {
+        roots: settings.workspace_roots.clone(),
+        repos: BTreeMap::new(),
+        updated_at: Some(Utc::now().to_rfc3339()),
+    };
+    let mut watched = BTreeSet::new();
+
+    for root in &settings.workspace_roots {
+        let Ok(root_dir) = expand_parent_path(root) else {
+            continue;
+        };
+        let rules = IgnoreRules::new(&root_dir, &settings.ignore_patterns);
+        let mut pending = vec![(root_dir, settings.scan_depth)];
+
+        while let Some((dir, depth)) = pending.pop() {
+            watched.insert(dir.clone());
+            if depth == 0 {
+                continue;
+            }
+            let Ok(entries) = fs::read_dir(&dir) else {
+                continue;
+            };
+
+            for path in entries.flatten().map(|e| e.path()).filter(|p| p.is_dir()) {
+                if rules.is_ignored(&path) {
+                    continue;
+                }
+
+                let key = path.to_string_lossy().to_string();
+                let repo = match previous.repos.get(&key) {
+                    Some(known) if known.fingerprint == fingerprint(&path) => Some(known.clone()),
+                    _ => index_repo(&path, root, &registry),
+                };
+                match repo {
+                    Some(repo) => {
+                        watched.insert(path);
+                        catalog.repos.insert(key, repo);
+                    }
+                    None => pending.push((path, depth - 1)),
+                }
+            }
+        }
+    }
+
+    (catalog, watched)
+}
+
+/// Différences entre deux catalogues
+fn diff_catalogs(before: &Catalog, after: &Catalog) -> CatalogChangedEvent {
+    let mut event = CatalogChangedEvent::default();
+    for (path, repo) in &after.repos {
+        match before.repos.get(path) {
+            None => event.added.push(repo.clone()),
+            Some(previous) if previous != repo => event.updated.push(repo.clone()),
+            _ => {}
+        }
+    }
+    event.removed = before
+        .repos
+        .keys()
+        .filter(|path| !after.repos.contains_key(*path))
+        .cloned()
+        .collect();
+    event
+}
+
+/// Met le catalogue à jour et renvoie les changements
+fn refresh() -> Result<(Catalog, CatalogChangedEvent, BTreeSet<PathBuf>), String> {
+    let settings = read_settings()?;
+    let previous = current_catalog();
+    let (catalog, watched) = reconcile(&settings, &previous);
+    let event = diff_catalogs(&previous, &catalog);
+
+    store_catalog(&catalog);
+    *CATALOG.lock().unwrap_or_else(|e| e.into_inner()) = Some(catalog.clone());
+    Ok((catalog, event, watched))
+}
+
+/// Indexe les racines de workspace en arrière-plan puis suit les clones, suppressions
+/// et changements de manifestes ; émet `catalog:changed`
+pub fn start_indexer(app: AppHandle) {
+    thread::spawn(move || {
+        let (tx, rx) = mpsc::channel::<DebounceEventResult>();
+        let mut debouncer = match new_debouncer(DEBOUNCE, tx) {
+            Ok(debouncer) => debouncer,
+            Err(e) => {
+                emit_error(
+                    &app,
+                    format!("Indexation des workspaces indisponible: {}", e),
+                );
+                return;
+            }
+        };
+        let mut dirs: BTreeSet<PathBuf> = BTreeSet::new();
+        let settings_file = settings_path();
+        if let Some(dir) = settings_file.parent() {
+            let _ = debouncer.watcher().watch(dir, RecursiveMode::NonRecursive);
+        }
+
+        loop {
+            match refresh() {
+                Ok((_, event, watched)) => {
+                    if !event.is_empty() {
+                        emit_redacted(&app, "catalog:changed", &event);
+                    }
+                    // Surveillance non récursive : racines, dossiers intermédiaires et dépôts
+                    for dir in dirs.difference(&watched) {
+                        let _ = debouncer.watcher().unwatch(dir);
+                    }
+                    for dir in watched.difference(&dirs) {
+                        let _ = debouncer.watcher().watch(dir, RecursiveMode::NonRecursive);
+                    }
+                    dirs = watched;
+                }
+                Err(e) => emit_error(&app, format!("Indexation des workspaces: {}", e)),
+            }
+
+            // Attendre un changement pertinent : dossier surveillé ou réglages
+            loop {
+                let Ok(result) = rx.recv() else {
+                    return;
+                };
+                let Ok(events) = result else {
+                    continue;
+                };
+                if events.iter().any(|e| {
+                    e.path == settings_file
+                        || e.path.parent().is_some_and(|parent| dirs.contains(parent))
+                }) {
+                    break;
+                }
+            }
+        }
+    });
+}
+
+/// Catalogue courant, sans parcourir le disque
+#[tauri::command]
+pub async fn get_catalog() -> Result<Catalog, String> {
+    Ok(current_catalog())
+}
+
+/// Force une mise à jour du catalogue
+#[tauri::command]
+pub async fn refresh_catalog(app: AppHandle) -> Result<Catalog, String> {
+    let (catalog, event, _) = refresh()?;
+    if !event.is_empty() {
+        emit_redacted(&app, "catalog:changed", &event);
+    }
+    Ok(catalog)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reconcile_incremental() {
+        let root = std::env::temp_dir().join(format!("gmdev-index-{}", uuid::Uuid::new_v4()));
+        let site = root.join("clients/acme-site");
+        fs::create_dir_all(&site).unwrap();
+        fs::write(
+            site.join("package.json"),
+            r#"{"dependencies": {"next": "15.0.0"}}"#,
+        )
+        .unwrap();
+        fs::create_dir_all(root.join("notes")).unwrap();
+
+        let settings = AppSettings {
+            workspace_roots: vec![root.to_string_lossy().to_string()],
+            scan_depth: 2,
+            ..AppSettings::default()
+        };
+        let (first, watched) = reconcile(&settings, &Catalog::default());
+        let key = site.to_string_lossy().to_string();
+        assert_eq!(first.repos.len(), 1);
+        assert_eq!(first.repos[&key].kind, "frontend");
+        assert!(watched.contains(&root.join("clients")));
+
+        // Empreinte inchangée : le dépôt n'est pas détecté à nouveau
+        let (second, _) = reconcile(&settings, &first);
+        assert_eq!(second.repos[&key].indexed_at, first.repos[&key].indexed_at);
+        assert!(diff_catalogs(&first, &second).is_empty());
+
+        fs::remove_dir_all(&site).unwrap();
+        let (third, _) = reconcile(&settings, &second);
+        assert_eq!(diff_catalogs(&second, &third).removed, vec![key]);
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
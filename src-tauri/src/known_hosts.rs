@@ -0,0 +1,372 @@
+use base64::{
+    engine::general_purpose::{STANDARD, STANDARD_NO_PAD},
+    Engine,
+};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+
+use crate::projects_v3::{config_dir, read_config};
+use crate::secrets::serialize_redacted;
+use crate::tunnels::{self, TunnelSpec};
+
+const FILE_NAME: &str = "known_hosts";
+
+/// Sérialise les écritures de known_hosts (épinglage et oubli concurrents)
+static KNOWN_HOSTS_LOCK: Mutex<()> = Mutex::new(());
+
+/// Clé d'hôte épinglée, au format d'une ligne known_hosts
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HostKey {
+    /// Hôte tel qu'écrit par ssh : `host` ou `[host]:port`
+    pub host: String,
+    pub key_type: String,
+    /// Clé publique en base64
+    pub key: String,
+    /// Empreinte `SHA256:...`, comme l'affiche ssh
+    pub fingerprint: String,
+}
+
+impl HostKey {
+    pub fn new(host: &str, key_type: &str, key: &str) -> Option<Self> {
+        Some(HostKey {
+            host: host.to_string(),
+            key_type: key_type.to_string(),
+            key: key.to_string(),
+            fingerprint: fingerprint(key)?,
+        })
+    }
+
+    fn to_line(&self) -> String {
+        format!("{} {} {}", self.host, self.key_type, self.key)
+    }
+}
+
+/// Résultat de la vérification de la clé présentée par un serveur
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum HostKeyState {
+    /// La clé présentée correspond à une clé épinglée
+    Pinned,
+    /// Aucune clé épinglée pour cet hôte : empreinte à vérifier puis épingler
+    Unknown,
+    /// La clé présentée diffère de la clé épinglée
+    Changed,
+}
+
+/// Payload de l'event tunnel:host-key et réponse de `check_tunnel_host_key`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HostKeyCheck {
+    pub project_id: String,
+    pub state: HostKeyState,
+    /// Clé présentée par le serveur, à épingler quand elle est inconnue
+    pub offered: Option<HostKey>,
+    /// Empreinte présentée quand elle diffère de la clé épinglée
+    pub offered_fingerprint: Option<String>,
+    /// Clé épinglée contredite par le serveur
+    pub pinned: Option<HostKey>,
+    #[serde(serialize_with = "serialize_redacted")]
+    pub message: String,
+}
+
+pub fn known_hosts_path() -> PathBuf {
+    config_dir().join(FILE_NAME)
+}
+
+/// Options ssh : seules les clés épinglées dans `known_hosts` sont acceptées
+pub fn strict_args(known_hosts: &Path) -> Vec<String> {
+    [
+        "StrictHostKeyChecking=yes".to_string(),
+        format!("UserKnownHostsFile={}", known_hosts.display()),
+        "GlobalKnownHostsFile=/dev/null".to_string(),
+    ]
+    .into_iter()
+    .flat_map(|option| ["-o".to_string(), option])
+    .collect()
+}
+
+/// Empreinte SHA256 d'une clé publique en base64, au format de `ssh-keygen -l`
+pub fn fingerprint(key: &str) -> Option<String> {
+    let blob = STANDARD.decode(key.trim()).ok()?;
+    Some(format!(
+        "SHA256:{}",
+        STANDARD_NO_PAD.encode(Sha256::digest(&blob))
+    ))
+}
+
+pub fn is_host_key_error(error: &str) -> bool {
+    error.contains("Host key verification failed")
+        || error.contains("REMOTE HOST IDENTIFICATION HAS CHANGED")
+}
+
+/// Lignes `host type key` ; commentaires et marqueurs (`@revoked`...) ignorés
+pub fn parse_known_hosts(text: &str) -> Vec<HostKey> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#') && !line.starts_with('@'))
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let (host, key_type, key) = (fields.next()?, fields.next()?, fields.next()?);
+            HostKey::new(host, key_type, key)
+        })
+        .collect()
+}
+
+pub fn read_host_keys() -> Vec<HostKey> {
+    fs::read_to_string(known_hosts_path())
+        .map(|text| parse_known_hosts(&text))
+        .unwrap_or_default()
+}
+
+fn write_host_keys(keys: &[HostKey]) -> Result<(), String> {
+    let text: String = keys.iter().map(|k| k.to_line() + "\n").collect();
+    fs::write(known_hosts_path(), text).map_err(|e| format!("Failed to write known_hosts: {e}"))
+}
+
+/// Empreinte et ligne fautive annoncées par ssh quand la clé d'un hôte a changé
+pub fn parse_changed_key(stderr: &str) -> (Option<String>, Option<usize>) {
+    let fingerprint = Regex::new(r"SHA256:[A-Za-z0-9+/]+")
+        .ok()
+        .and_then(|re| re.find(stderr))
+        .map(|m| m.as_str().to_string());
+    let line = Regex::new(r"Offending \S+ key in .+:(\d+)")
+        .ok()
+        .and_then(|re| re.captures(stderr))
+        .and_then(|caps| caps[1].parse().ok());
+    (fingerprint, line)
+}
+
+/// Se connecte au serveur du tunnel pour comparer sa clé aux clés épinglées
+///
+/// ssh s'exécute avec `StrictHostKeyChecking=accept-new` sur une copie de known_hosts :
+/// une clé inconnue y est ajoutée (et proposée à l'épinglage), une clé changée est
+/// refusée par ssh lui-même. Seule la méthode d'authentification `none` est proposée :
+/// la clé est récupérée sans s'authentifier. Les rebonds `ProxyJump`, qui
+/// exigent une session, restent authentifiés et vérifiés par la configuration ssh
+/// de l'utilisateur.
+pub fn check_host_key(project_id: &str, spec: &TunnelSpec) -> Result<HostKeyCheck, String> {
+    let pinned_text = fs::read_to_string(known_hosts_path()).unwrap_or_default();
+    let pinned = parse_known_hosts(&pinned_text);
+    let scratch = std::env::temp_dir().join(format!("gmdev-known-hosts-{}", uuid::Uuid::new_v4()));
+    let pinned_lines: String = pinned.iter().map(|k| k.to_line() + "\n").collect();
+    fs::write(&scratch, &pinned_lines)
+        .map_err(|e| format!("Failed to write temporary known_hosts: {e}"))?;
+
+    let output = Command::new("ssh")
+        .args([
+            "-o",
+            "BatchMode=yes",
+            "-o",
+            "StrictHostKeyChecking=accept-new",
+            "-o",
+            "HashKnownHosts=no",
+            "-o",
+            "GlobalKnownHostsFile=/dev/null",
+            "-o",
+            "ConnectTimeout=10",
+            "-o",
+            "ControlMaster=no",
+            "-o",
+            "ControlPath=none",
+            "-o",
+            "PreferredAuthentications=none",
+            "-o",
+            "PubkeyAuthentication=no",
+        ])
+        .arg("-o")
+        .arg(format!("UserKnownHostsFile={}", scratch.display()))
+        .args(spec.connection_args())
+        .arg(spec.target())
+        .arg("true")
+        .stdin(Stdio::null())
+        .output();
+    let scanned = fs::read_to_string(&scratch).unwrap_or_default();
+    let _ = fs::remove_file(&scratch);
+    let output = output.map_err(|e| format!("Failed to execute ssh: {}", e))?;
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    let destination = spec.destination();
+
+    if stderr.contains("REMOTE HOST IDENTIFICATION HAS CHANGED")
+        || stderr.contains("has changed and you have requested strict checking")
+    {
+        let (offered_fingerprint, line) = parse_changed_key(&stderr);
+        let previous = line
+            .and_then(|n| n.checked_sub(1))
+            .and_then(|n| parse_known_hosts(&scanned).into_iter().nth(n));
+        let message = format!(
+            "La clé d'hôte de {} a changé : épinglée {}, présentée {}. Possible interception \
+             (man-in-the-middle) ; n'oubliez l'ancienne clé que si le changement est attendu.",
+            destination,
+            previous
+                .as_ref()
+                .map(|k| k.fingerprint.as_str())
+                .unwrap_or("?"),
+            offered_fingerprint.as_deref().unwrap_or("?"),
+        );
+        return Ok(HostKeyCheck {
+            project_id: project_id.to_string(),
+            state: HostKeyState::Changed,
+            offered: None,
+            offered_fingerprint,
+            pinned: previous,
+            message,
+        });
+    }
+
+    if let Some(offered) = parse_known_hosts(&scanned)
+        .into_iter()
+        .find(|k| !pinned.contains(k))
+    {
+        let message = format!(
+            "Clé d'hôte de {} non épinglée ({} {}) : vérifiez l'empreinte puis épinglez-la",
+            destination, offered.key_type, offered.fingerprint
+        );
+        return Ok(HostKeyCheck {
+            project_id: project_id.to_string(),
+            state: HostKeyState::Unknown,
+            offered_fingerprint: Some(offered.fingerprint.clone()),
+            offered: Some(offered),
+            pinned: None,
+            message,
+        });
+    }
+
+    // La vérification de clé est passée si ssh est allé jusqu'à l'authentification
+    if output.status.success() || stderr.contains("Permission denied") {
+        return Ok(HostKeyCheck {
+            project_id: project_id.to_string(),
+            state: HostKeyState::Pinned,
+            offered: None,
+            offered_fingerprint: None,
+            pinned: None,
+            message: format!("Clé d'hôte de {} épinglée", destination),
+        });
+    }
+
+    Err(format!(
+        "Impossible de vérifier la clé d'hôte de {}: {}",
+        destination,
+        stderr.trim()
+    ))
+}
+
+/// Clés d'hôte épinglées pour les tunnels
+#[tauri::command]
+pub async fn list_host_keys() -> Result<Vec<HostKey>, String> {
+    Ok(read_host_keys())
+}
+
+/// Récupère la clé présentée par le serveur du tunnel d'un projet, sans l'épingler
+#[tauri::command]
+pub async fn check_tunnel_host_key(project_id: String) -> Result<HostKeyCheck, String> {
+    let cfg = read_config()?;
+    let project = cfg
+        .projects
+        .iter()
+        .find(|p| p.id == project_id)
+        .ok_or_else(|| "Project not found".to_string())?;
+    let spec = TunnelSpec::for_project(project)?;
+    let check = check_host_key(&project_id, &spec);
+    tunnels::release_tunnel_key(&project_id);
+    check
+}
+
+/// Épingle une clé d'hôte (remplace la clé de même type pour cet hôte)
+///
+/// L'empreinte est recalculée : elle doit être celle que l'utilisateur a vérifiée.
+#[tauri::command]
+pub async fn pin_host_key(host_key: HostKey) -> Result<Vec<HostKey>, String> {
+    let computed = HostKey::new(&host_key.host, &host_key.key_type, &host_key.key)
+        .ok_or_else(|| format!("Clé d'hôte invalide pour {}", host_key.host))?;
+    if computed.fingerprint != host_key.fingerprint {
+        return Err(format!(
+            "Empreinte inattendue pour {}: {} au lieu de {}",
+            host_key.host, computed.fingerprint, host_key.fingerprint
+        ));
+    }
+    if host_key.host.trim().is_empty() || host_key.host.contains(char::is_whitespace) {
+        return Err(format!("Hôte invalide: '{}'", host_key.host));
+    }
+
+    let _guard = KNOWN_HOSTS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut keys = read_host_keys();
+    keys.retain(|k| !(k.host == computed.host && k.key_type == computed.key_type));
+    keys.push(computed);
+    write_host_keys(&keys)?;
+    Ok(keys)
+}
+
+/// Oublie les clés épinglées d'un hôte (toutes, ou seulement `key_type`)
+#[tauri::command]
+pub async fn forget_host_key(
+    host: String,
+    key_type: Option<String>,
+) -> Result<Vec<HostKey>, String> {
+    let _guard = KNOWN_HOSTS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut keys = read_host_keys();
+    let before = keys.len();
+    keys.retain(|k| k.host != host || key_type.as_ref().is_some_and(|t| *t != k.key_type));
+    if keys.len() == before {
+        return Err(format!("Aucune clé épinglée pour {}", host));
+    }
+    write_host_keys(&keys)?;
+    Ok(keys)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "AAAAC3NzaC1lZDI1NTE5AAAAIAt2Xql0VK4CbzTwc9XlA74GqFmV0EacWTVYG8w/iy+f";
+
+    #[test]
+    fn test_parses_known_hosts_and_fingerprints() {
+        let text = format!(
+            "# épinglées depuis le devcenter\n\
+             [db.example.com]:2222 ssh-ed25519 {KEY}\n\
+             @revoked bad.example.com ssh-ed25519 {KEY}\n\
+             broken-line\n"
+        );
+        let keys = parse_known_hosts(&text);
+
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].host, "[db.example.com]:2222");
+        assert_eq!(keys[0].key_type, "ssh-ed25519");
+        // Valeur donnée par `ssh-keygen -lf`
+        assert_eq!(
+            keys[0].fingerprint,
+            "SHA256:aY06k/xs+evvNnRMXoEodVatjShbVvLvXFLvdYT/dzc"
+        );
+        assert_eq!(parse_known_hosts(&keys[0].to_line()), keys);
+    }
+
+    #[test]
+    fn test_reads_changed_key_diagnostic() {
+        let stderr = "\
+@    WARNING: REMOTE HOST IDENTIFICATION HAS CHANGED!     @
+IT IS POSSIBLE THAT SOMEONE IS DOING SOMETHING NASTY!
+The fingerprint for the ED25519 key sent by the remote host is
+SHA256:Qm9ndXNGaW5nZXJwcmludEZvclRlc3Q.
+Please contact your system administrator.
+Offending ED25519 key in /tmp/gmdev-known-hosts-1:3
+Host key for [db.example.com]:2222 has changed and you have requested strict checking.
+Host key verification failed.";
+
+        assert!(is_host_key_error(stderr));
+        assert_eq!(
+            parse_changed_key(stderr),
+            (
+                Some("SHA256:Qm9ndXNGaW5nZXJwcmludEZvclRlc3Q".to_string()),
+                Some(3)
+            )
+        );
+        assert!(strict_args(Path::new("/tmp/known_hosts"))
+            .contains(&"StrictHostKeyChecking=yes".to_string()));
+    }
+}
@@ -1,17 +1,59 @@
 mod autoscan;
 mod commands;
+mod compose;
+mod config_watcher;
+mod dependencies;
+mod detectors;
+mod env;
+mod folders;
+mod git_branches;
 mod git_import;
+mod git_status;
+mod git_sync;
+mod git_url;
 mod gmd;
+mod ignore_rules;
+mod indexer;
+mod known_hosts;
+mod manifest;
+mod package_json;
+mod pairing;
+mod ports;
+mod profiles;
 mod projects;
 mod projects_v3;
+mod scan_report;
+mod secrets;
+mod settings;
+mod ssh_keys;
 mod state;
+mod tunnels;
+mod workspace;
 
 use autoscan::*;
 use commands::*;
+use compose::*;
+use dependencies::*;
+use detectors::*;
+use env::*;
+use folders::*;
+use git_branches::*;
 use git_import::*;
+use git_status::*;
+use git_sync::*;
+use git_url::*;
+use indexer::*;
+use known_hosts::*;
+use pairing::*;
 use projects::*;
 use projects_v3::*;
+use scan_report::*;
+use secrets::*;
+use settings::*;
+use ssh_keys::*;
 use state::AppState;
+use tunnels::*;
+use workspace::*;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -19,7 +61,15 @@ pub fn run() {
 
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_dialog::init())
         .manage(app_state)
+        .setup(|app| {
+            config_watcher::start_config_watcher(app.handle().clone());
+            indexer::start_indexer(app.handle().clone());
+            git_status::start_git_status_watcher(app.handle().clone());
+            tunnels::init_tunnels(app.handle().clone());
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             run_command,
             start_service,
@@ -39,7 +89,11 @@ pub fn run() {
             update_project,
             delete_project,
             pick_project_folder,
+            inspect_folder,
+            list_recent_folders,
             autoscan_project,
+            autoscan_report,
+            apply_scan_changes,
             start_service_v3,
             stop_service_v3,
             status_service_v3,
@@ -48,11 +102,55 @@ pub fn run() {
             load_projects_v3,
             save_projects_v3,
             clone_git_repo,
+            start_clone,
+            cancel_clone,
             pull_git_repo,
+            git_status_all,
+            sync_repo,
+            sync_all_projects,
+            list_branches,
+            create_branch,
+            switch_project_branch,
+            list_worktrees,
+            create_project_worktree,
+            remove_project_worktree,
+            cleanup_project_worktrees,
+            parse_git_url,
+            check_dependencies,
+            install_dependencies,
+            get_tunnel_status,
+            list_tunnels,
+            list_host_keys,
+            check_tunnel_host_key,
+            pin_host_key,
+            forget_host_key,
+            list_ssh_keys,
+            fix_ssh_key_permissions,
+            test_tunnel_connection,
+            test_all_tunnel_connections,
             scan_independent_repos,
+            rank_repo_pairings,
             get_gmdev_status,
             get_gmdev_logs,
             restart_service_v3,
+            switch_profile,
+            resolve_service_env,
+            detect_stacks,
+            scan_workspace,
+            scan_compose,
+            compose_up,
+            compose_down,
+            compose_status,
+            compose_logs,
+            get_catalog,
+            refresh_catalog,
+            get_settings,
+            save_settings,
+            vault_status,
+            unlock_vault,
+            lock_vault,
+            set_secret,
+            delete_secret,
             run_gmd_command
         ])
         .run(tauri::generate_context!())
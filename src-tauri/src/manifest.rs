@@ -0,0 +1,566 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use chrono::Utc;
+
+use crate::autoscan::{
+    HealthCheck, PortForward, ProjectCommands, ProjectEnvironment, ProjectOverrides,
+    ProjectServiceConfig, ProjectV3, Tunnel,
+};
+use crate::projects_v3::{project_id_from_name, validate_project};
+
+/// Noms de fichiers de manifeste reconnus, par ordre de priorité
+pub const MANIFEST_FILES: &[&str] = &[".gmdev.toml", "gmdev.json"];
+
+/// Services qu'un manifeste peut déclarer
+const KNOWN_SERVICES: &[&str] = &["backend", "frontend"];
+
+/// Manifeste de projet versionné dans le dépôt
+///
+/// Exemple `.gmdev.toml` :
+/// ```toml
+/// id = "gestionmaxopps"
+/// name = "GestionMax Ops"
+/// backend_type = "payload"
+///
+/// [services.backend]
+/// path = "backend"
+/// command = "pnpm dev"
+/// port = 3010
+/// env_files = [".env", ".env.local"]
+/// health_check = { url = "/api/health", interval_secs = 10 }
+///
+/// [services.frontend]
+/// path = "frontend"
+/// command = "pnpm dev"
+/// port = 3000
+///
+/// [tunnel]
+/// host = "91.99.22.54"
+/// user = "root"
+/// local_mongo = 27017
+///
+/// [[tunnel.forwards]]
+/// name = "redis"
+/// local_port = 6380
+/// remote_port = 6379
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ProjectManifest {
+    pub id: Option<String>,
+    pub name: Option<String>,
+    #[serde(alias = "backendType")]
+    pub backend_type: Option<String>,
+    #[serde(default)]
+    pub services: BTreeMap<String, ManifestService>,
+    pub tunnel: Option<ManifestTunnel>,
+}
+
+/// Service déclaré dans le manifeste (chemins relatifs à la racine du dépôt)
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ManifestService {
+    pub path: Option<String>,
+    pub command: Option<String>,
+    pub stop: Option<String>,
+    pub port: Option<u16>,
+    #[serde(default, alias = "envFiles")]
+    pub env_files: Vec<String>,
+    #[serde(alias = "healthCheck")]
+    pub health_check: Option<HealthCheck>,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+}
+
+/// Tunnel déclaré dans le manifeste (la clé privée reste en général locale)
+///
+/// Sans `forwards`, `local_mongo`/`remote_mongo` décrivent l'unique transfert MongoDB.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ManifestTunnel {
+    pub enabled: Option<bool>,
+    #[serde(default)]
+    pub host: String,
+    pub user: Option<String>,
+    pub port: Option<u16>,
+    #[serde(alias = "privateKey")]
+    pub private_key: Option<String>,
+    #[serde(alias = "localMongo")]
+    pub local_mongo: Option<u16>,
+    #[serde(alias = "remoteMongo")]
+    pub remote_mongo: Option<u16>,
+    #[serde(alias = "sshAlias")]
+    pub ssh_alias: Option<String>,
+    #[serde(default, alias = "proxyJump")]
+    pub proxy_jump: Vec<String>,
+    #[serde(default)]
+    pub forwards: Vec<PortForward>,
+}
+
+/// Cherche un manifeste à la racine d'un dépôt
+pub fn find_manifest(root: &Path) -> Option<PathBuf> {
+    MANIFEST_FILES
+        .iter()
+        .map(|name| root.join(name))
+        .find(|path| path.is_file())
+}
+
+/// Lit et parse un manifeste (TOML ou JSON selon l'extension)
+pub fn read_manifest(path: &Path) -> Result<ProjectManifest, String> {
+    let text = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read manifest {}: {e}", path.display()))?;
+
+    let manifest: ProjectManifest = if path.extension().and_then(|e| e.to_str()) == Some("json") {
+        serde_json::from_str(&text)
+            .map_err(|e| format!("Invalid manifest {}: {e}", path.display()))?
+    } else {
+        toml::from_str(&text).map_err(|e| format!("Invalid manifest {}: {e}", path.display()))?
+    };
+
+    let unknown: Vec<&String> = manifest
+        .services
+        .keys()
+        .filter(|name| !KNOWN_SERVICES.contains(&name.as_str()))
+        .collect();
+    if !unknown.is_empty() {
+        return Err(format!(
+            "Invalid manifest {}: service(s) inconnu(s) {:?} (attendus: {:?})",
+            path.display(),
+            unknown,
+            KNOWN_SERVICES
+        ));
+    }
+
+    let errors = validate_manifest(&manifest);
+    if !errors.is_empty() {
+        return Err(format!(
+            "Invalid manifest {}: {}",
+            path.display(),
+            errors.join("; ")
+        ));
+    }
+
+    Ok(manifest)
+}
+
+/// Chemin relatif qui reste dans le dépôt : ni absolu, ni `..`
+fn is_inside_repo(path: &str) -> bool {
+    Path::new(path)
+        .components()
+        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+}
+
+/// Vérifie les ports et commandes déclarés, avant toute fusion avec le projet
+fn validate_manifest(manifest: &ProjectManifest) -> Vec<String> {
+    let mut errors = Vec::new();
+    for (name, service) in &manifest.services {
+        if service.port == Some(0) {
+            errors.push(format!("port du service {name} invalide (0)"));
+        }
+        for (field, value) in [("command", &service.command), ("stop", &service.stop)] {
+            if value.as_ref().is_some_and(|v| v.trim().is_empty()) {
+                errors.push(format!("{field} du service {name} vide"));
+            }
+        }
+        if service.path.as_ref().is_some_and(|p| p.trim().is_empty()) {
+            errors.push(format!("path du service {name} vide"));
+        }
+        for path in service.path.iter().chain(&service.env_files) {
+            if !is_inside_repo(path) {
+                errors.push(format!(
+                    "chemin {path} du service {name} hors du dépôt (absolu ou avec ..)"
+                ));
+            }
+        }
+    }
+    if let Some(tunnel) = &manifest.tunnel {
+        if tunnel.port == Some(0) {
+            errors.push("port SSH du tunnel invalide (0)".to_string());
+        }
+        if tunnel.local_mongo == Some(0) || tunnel.remote_mongo == Some(0) {
+            errors.push("port MongoDB du tunnel invalide (0)".to_string());
+        }
+    }
+    errors
+}
+
+/// Charge le manifeste d'un dépôt s'il existe
+pub fn load_manifest(root: &Path) -> Result<Option<(PathBuf, ProjectManifest)>, String> {
+    match find_manifest(root) {
+        Some(path) => read_manifest(&path).map(|m| Some((path, m))),
+        None => Ok(None),
+    }
+}
+
+/// Applique le manifeste sur un projet (le manifeste l'emporte sur les heuristiques)
+pub fn apply_manifest(project: &mut ProjectV3, root: &Path, manifest: &ProjectManifest) {
+    if let Some(id) = &manifest.id {
+        project.id = id.clone();
+    }
+    if let Some(name) = &manifest.name {
+        project.name = name.clone();
+    }
+    if manifest.backend_type.is_some() {
+        project.backend_type = manifest.backend_type.clone();
+    }
+
+    let mut commands = project.commands.clone().unwrap_or_default();
+
+    if let Some(service) = manifest.services.get("backend") {
+        if let Some(path) = &service.path {
+            project.backend_path = root.join(path).to_string_lossy().to_string();
+        }
+        if let Some(port) = service.port {
+            project.ports.backend = port;
+        }
+        if service.command.is_some() {
+            commands.backend = service.command.clone();
+        }
+        project.backend = Some(service_config(service));
+    }
+
+    if let Some(service) = manifest.services.get("frontend") {
+        if let Some(path) = &service.path {
+            project.frontend_path = root.join(path).to_string_lossy().to_string();
+        }
+        if let Some(port) = service.port {
+            project.ports.frontend = port;
+        }
+        if service.command.is_some() {
+            commands.frontend = service.command.clone();
+        }
+        project.frontend = Some(service_config(service));
+    }
+
+    project.commands = Some(commands);
+
+    if let Some(tunnel) = &manifest.tunnel {
+        // Un champ absent du manifeste garde la valeur enregistrée, sinon le défaut
+        let current = project.tunnel.clone();
+        let current = current.as_ref();
+        let current_mongo = current.and_then(|t| t.mongo_forward());
+        project.tunnel = Some(Tunnel {
+            enabled: tunnel
+                .enabled
+                .or(current.map(|t| t.enabled))
+                .unwrap_or(true),
+            host: Some(tunnel.host.clone())
+                .filter(|host| !host.is_empty())
+                .or_else(|| current.map(|t| t.host.clone()))
+                .unwrap_or_default(),
+            user: tunnel
+                .user
+                .clone()
+                .or_else(|| current.map(|t| t.user.clone()))
+                .unwrap_or_else(|| "root".to_string()),
+            port: tunnel.port.or(current.map(|t| t.port)).unwrap_or(22),
+            private_key: tunnel
+                .private_key
+                .clone()
+                .or_else(|| current.map(|t| t.private_key.clone()))
+                .unwrap_or_default(),
+            ssh_alias: tunnel
+                .ssh_alias
+                .clone()
+                .or_else(|| current.and_then(|t| t.ssh_alias.clone())),
+            proxy_jump: if tunnel.proxy_jump.is_empty() {
+                current.map(|t| t.proxy_jump.clone()).unwrap_or_default()
+            } else {
+                tunnel.proxy_jump.clone()
+            },
+            forwards: if tunnel.forwards.is_empty() {
+                vec![PortForward::local(
+                    "mongo",
+                    tunnel
+                        .local_mongo
+                        .or(current_mongo.map(|f| f.local_port))
+                        .unwrap_or(27017),
+                    tunnel
+                        .remote_mongo
+                        .or(current_mongo.map(|f| f.remote_port))
+                        .unwrap_or(27017),
+                )]
+            } else {
+                tunnel.forwards.clone()
+            },
+        });
+    }
+}
+
+fn service_config(service: &ManifestService) -> ProjectServiceConfig {
+    ProjectServiceConfig {
+        start: service.command.clone(),
+        stop: service.stop.clone(),
+        port: service.port,
+        env_files: service.env_files.clone(),
+        health_check: service.health_check.clone(),
+        env: service.env.clone(),
+    }
+}
+
+/// Applique les surcharges locales de projects-v3.json par-dessus le manifeste
+pub fn apply_overrides(project: &mut ProjectV3, overrides: &ProjectOverrides) {
+    if let Some(ports) = &overrides.ports {
+        if let Some(port) = ports.backend {
+            project.ports.backend = port;
+        }
+        if let Some(port) = ports.frontend {
+            project.ports.frontend = port;
+        }
+    }
+
+    if let Some(over) = &overrides.commands {
+        let commands = project
+            .commands
+            .get_or_insert_with(ProjectCommands::default);
+        if over.backend.is_some() {
+            commands.backend = over.backend.clone();
+        }
+        if over.frontend.is_some() {
+            commands.frontend = over.frontend.clone();
+        }
+        if over.tunnel.is_some() {
+            commands.tunnel = over.tunnel.clone();
+        }
+        if over.netdata.is_some() {
+            commands.netdata = over.netdata.clone();
+        }
+    }
+
+    if let Some(over) = &overrides.environment {
+        let env = project
+            .environment
+            .get_or_insert_with(ProjectEnvironment::default);
+        env.backend_env
+            .extend(over.backend_env.iter().map(|(k, v)| (k.clone(), v.clone())));
+        env.frontend_env.extend(
+            over.frontend_env
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone())),
+        );
+    }
+
+    if let Some(tunnel) = &overrides.tunnel {
+        project.tunnel = Some(tunnel.clone());
+    }
+}
+
+/// Construit un projet uniquement à partir d'un manifeste
+pub fn project_from_manifest(
+    root: &Path,
+    manifest_path: &Path,
+    manifest: &ProjectManifest,
+) -> Result<ProjectV3, String> {
+    let dir_name = root
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("project")
+        .to_string();
+
+    let mut project = ProjectV3 {
+        id: project_id_from_name(&dir_name),
+        name: dir_name,
+        root_path: root.to_string_lossy().to_string(),
+        created_at: Utc::now().to_rfc3339(),
+        enabled: true,
+        manifest_path: Some(manifest_path.to_string_lossy().to_string()),
+        ..Default::default()
+    };
+
+    apply_manifest(&mut project, root, manifest);
+    check_project(&project, manifest_path)?;
+
+    Ok(project)
+}
+
+/// Recalcule un projet issu d'un manifeste : manifeste puis surcharges locales
+pub fn resolve_project(project: &mut ProjectV3) -> Result<(), String> {
+    let Some(manifest_path) = project.manifest_path.clone() else {
+        return Ok(());
+    };
+    let manifest_path = PathBuf::from(manifest_path);
+    let manifest = read_manifest(&manifest_path)?;
+    let root = manifest_path
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from(&project.root_path));
+
+    // L'id local fait foi : le manifeste ne doit pas renommer un projet déjà enregistré
+    let id = project.id.clone();
+    apply_manifest(project, &root, &manifest);
+    project.id = id;
+
+    if let Some(overrides) = project.overrides.clone() {
+        apply_overrides(project, &overrides);
+    }
+
+    check_project(project, &manifest_path)
+}
+
+fn check_project(project: &ProjectV3, manifest_path: &Path) -> Result<(), String> {
+    let errors = validate_project(project);
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid manifest {}: {}",
+            manifest_path.display(),
+            errors.join("; ")
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("gmdev-manifest-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_toml_manifest_overrides_heuristics() {
+        let root = temp_dir("toml");
+        fs::write(
+            root.join(".gmdev.toml"),
+            r#"
+id = "ops"
+backend_type = "payload"
+
+[services.backend]
+path = "api"
+command = "pnpm dev"
+port = 4010
+health_check = { url = "/api/health", interval_secs = 5 }
+
+[tunnel]
+host = "db.example.com"
+local_mongo = 27020
+proxy_jump = ["deploy@bastion.example.com"]
+"#,
+        )
+        .unwrap();
+
+        let (path, manifest) = load_manifest(&root).unwrap().unwrap();
+        let project = project_from_manifest(&root, &path, &manifest).unwrap();
+
+        assert_eq!(project.id, "ops");
+        assert_eq!(project.ports.backend, 4010);
+        assert!(project.backend_path.ends_with("api"));
+        assert_eq!(
+            project.commands.as_ref().unwrap().backend.as_deref(),
+            Some("pnpm dev")
+        );
+        let backend = project.backend.as_ref().unwrap();
+        assert_eq!(
+            backend.health_check.as_ref().unwrap().interval_secs,
+            Some(5)
+        );
+        let tunnel = project.tunnel.as_ref().unwrap();
+        assert_eq!(tunnel.mongo_forward().unwrap().local_port, 27020);
+        assert_eq!(tunnel.proxy_jump, ["deploy@bastion.example.com"]);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_local_overrides_win_over_manifest() {
+        let root = temp_dir("overrides");
+        fs::write(
+            root.join("gmdev.json"),
+            r#"{ "services": { "frontend": { "path": "web", "port": 3000 } } }"#,
+        )
+        .unwrap();
+
+        let (path, manifest) = load_manifest(&root).unwrap().unwrap();
+        let mut project = project_from_manifest(&root, &path, &manifest).unwrap();
+        project.overrides = Some(ProjectOverrides {
+            ports: Some(crate::autoscan::PortsOverride {
+                backend: None,
+                frontend: Some(3005),
+            }),
+            ..Default::default()
+        });
+
+        resolve_project(&mut project).unwrap();
+        assert_eq!(project.ports.frontend, 3005);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_manifest_tunnel_keeps_stored_fields() {
+        let root = temp_dir("tunnel");
+        fs::write(
+            root.join(".gmdev.toml"),
+            "[services.backend]\nport = 4010\n\n[tunnel]\nhost = \"db.example.com\"\n",
+        )
+        .unwrap();
+        let (path, manifest) = load_manifest(&root).unwrap().unwrap();
+        let mut project = project_from_manifest(&root, &path, &manifest).unwrap();
+
+        let mut stored = project.tunnel.clone().unwrap();
+        stored.enabled = false;
+        stored.user = "deploy".to_string();
+        stored.port = 2222;
+        stored.forwards = vec![PortForward::local("mongo", 27030, 27018)];
+        project.tunnel = Some(stored);
+
+        resolve_project(&mut project).unwrap();
+        let tunnel = project.tunnel.as_ref().unwrap();
+        assert!(!tunnel.enabled);
+        assert_eq!(tunnel.host, "db.example.com");
+        assert_eq!(tunnel.user, "deploy");
+        assert_eq!(tunnel.port, 2222);
+        let mongo = tunnel.mongo_forward().unwrap();
+        assert_eq!((mongo.local_port, mongo.remote_port), (27030, 27018));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_manifest_validation_errors() {
+        let root = temp_dir("invalid");
+        fs::write(root.join(".gmdev.toml"), "[services.backend]\nport = 0\n").unwrap();
+        assert!(load_manifest(&root).is_err());
+
+        fs::write(root.join(".gmdev.toml"), "backend_type = \"rails\"\n").unwrap();
+        let (path, manifest) = load_manifest(&root).unwrap().unwrap();
+        assert!(project_from_manifest(&root, &path, &manifest).is_err());
+
+        fs::write(root.join(".gmdev.toml"), "id = \"../ops\"\n").unwrap();
+        let (path, manifest) = load_manifest(&root).unwrap().unwrap();
+        assert!(project_from_manifest(&root, &path, &manifest).is_err());
+
+        for path in ["/srv/other", "../../elsewhere", "backend/../../x"] {
+            fs::write(
+                root.join(".gmdev.toml"),
+                format!("[services.backend]\npath = \"{path}\"\n"),
+            )
+            .unwrap();
+            assert!(load_manifest(&root).is_err(), "{path}");
+        }
+        fs::write(
+            root.join(".gmdev.toml"),
+            "[services.frontend]\nenv_files = [\"../secrets.env\"]\n",
+        )
+        .unwrap();
+        assert!(load_manifest(&root).is_err());
+
+        fs::write(root.join(".gmdev.toml"), "[services.worker]\nport = 4000\n").unwrap();
+        assert!(load_manifest(&root).is_err());
+
+        fs::write(
+            root.join(".gmdev.toml"),
+            "[services.backend]\ncommand = \" \"\n",
+        )
+        .unwrap();
+        assert!(load_manifest(&root).is_err());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,233 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Nombre maximal de dossiers parents remontés pour trouver un lockfile (monorepo)
+const LOCKFILE_SEARCH_DEPTH: usize = 3;
+
+/// Scripts essayés, dans l'ordre, pour la commande de développement
+pub(crate) const DEV_SCRIPTS: &[&str] = &["dev", "develop", "start:dev", "serve"];
+
+/// Scripts essayés, dans l'ordre, pour la commande de production
+const START_SCRIPTS: &[&str] = &["start", "start:prod", "preview"];
+
+/// Champs de package.json utilisés par l'autoscan
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PackageJson {
+    pub name: Option<String>,
+    pub dependencies: BTreeMap<String, String>,
+    pub dev_dependencies: BTreeMap<String, String>,
+    pub peer_dependencies: BTreeMap<String, String>,
+    pub scripts: BTreeMap<String, String>,
+    /// Champ corepack, ex. "pnpm@9.1.0"
+    pub package_manager: Option<String>,
+    /// Membres d'un monorepo npm / yarn / bun
+    pub workspaces: Option<Workspaces>,
+}
+
+/// Champ `workspaces` : liste de motifs ou objet `{ packages: [...] }` (yarn)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum Workspaces {
+    Patterns(Vec<String>),
+    Object {
+        #[serde(default)]
+        packages: Vec<String>,
+    },
+}
+
+impl Workspaces {
+    pub fn patterns(&self) -> &[String] {
+        match self {
+            Self::Patterns(patterns) => patterns,
+            Self::Object { packages } => packages,
+        }
+    }
+}
+
+impl PackageJson {
+    /// Dépendance déclarée (dependencies, devDependencies ou peerDependencies)
+    pub fn has_dependency(&self, name: &str) -> bool {
+        self.dependencies.contains_key(name)
+            || self.dev_dependencies.contains_key(name)
+            || self.peer_dependencies.contains_key(name)
+    }
+
+    fn first_script<'a>(&self, candidates: &[&'a str]) -> Option<&'a str> {
+        candidates
+            .iter()
+            .copied()
+            .find(|name| self.scripts.contains_key(*name))
+    }
+}
+
+/// Lit et désérialise le package.json d'un dossier
+pub fn read_package_json(dir: &Path) -> Result<Option<PackageJson>, String> {
+    let path = dir.join("package.json");
+    if !path.exists() {
+        return Ok(None);
+    }
+    let text = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    serde_json::from_str(&text)
+        .map(Some)
+        .map_err(|e| format!("Invalid package.json ({}): {}", path.display(), e))
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PackageManager {
+    #[default]
+    Npm,
+    Pnpm,
+    Yarn,
+    Bun,
+}
+
+impl PackageManager {
+    const LOCKFILES: &'static [(&'static str, PackageManager)] = &[
+        ("pnpm-lock.yaml", PackageManager::Pnpm),
+        ("yarn.lock", PackageManager::Yarn),
+        ("bun.lockb", PackageManager::Bun),
+        ("bun.lock", PackageManager::Bun),
+        ("package-lock.json", PackageManager::Npm),
+        // Pas un lockfile, mais propre à pnpm (monorepo pas encore installé)
+        ("pnpm-workspace.yaml", PackageManager::Pnpm),
+    ];
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "npm" => Some(Self::Npm),
+            "pnpm" => Some(Self::Pnpm),
+            "yarn" => Some(Self::Yarn),
+            "bun" => Some(Self::Bun),
+            _ => None,
+        }
+    }
+
+    /// Exécutable du gestionnaire (`<exécutable> install` installe les dépendances)
+    pub fn program(&self) -> &'static str {
+        match self {
+            Self::Npm => "npm",
+            Self::Pnpm => "pnpm",
+            Self::Yarn => "yarn",
+            Self::Bun => "bun",
+        }
+    }
+
+    /// Commande qui lance un script package.json
+    pub fn run(&self, script: &str) -> String {
+        match self {
+            Self::Npm => format!("npm run {}", script),
+            Self::Pnpm => format!("pnpm {}", script),
+            Self::Yarn => format!("yarn {}", script),
+            Self::Bun => format!("bun run {}", script),
+        }
+    }
+}
+
+/// Lockfile du dossier ou d'un parent (racine de monorepo), sans sortir du dépôt
+pub fn find_lockfile(dir: &Path) -> Option<PathBuf> {
+    for ancestor in dir.ancestors().take(LOCKFILE_SEARCH_DEPTH + 1) {
+        let lockfile = PackageManager::LOCKFILES
+            .iter()
+            // pnpm-workspace.yaml indique le gestionnaire mais ne fige aucune version
+            .filter(|(name, _)| name.contains("lock"))
+            .map(|(name, _)| ancestor.join(name))
+            .find(|path| path.exists());
+        if lockfile.is_some() || ancestor.join(".git").exists() {
+            return lockfile;
+        }
+    }
+    None
+}
+
+/// Gestionnaire de paquets d'un dossier : champ `packageManager`, sinon lockfile
+/// du dossier ou d'un parent (racine de monorepo), sinon npm
+pub fn detect_package_manager(dir: &Path, package: Option<&PackageJson>) -> PackageManager {
+    let declared = package
+        .and_then(|p| p.package_manager.as_deref())
+        .and_then(|field| field.split('@').next())
+        .and_then(PackageManager::from_name);
+    if let Some(manager) = declared {
+        return manager;
+    }
+
+    for ancestor in dir.ancestors().take(LOCKFILE_SEARCH_DEPTH + 1) {
+        if let Some((_, manager)) = PackageManager::LOCKFILES
+            .iter()
+            .find(|(lockfile, _)| ancestor.join(lockfile).exists())
+        {
+            return *manager;
+        }
+        // Ne pas sortir du dépôt
+        if ancestor.join(".git").exists() {
+            break;
+        }
+    }
+
+    PackageManager::Npm
+}
+
+/// Commandes dérivées des scripts de package.json
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct ScriptCommands {
+    pub package_manager: PackageManager,
+    pub dev: Option<String>,
+    pub build: Option<String>,
+    pub start: Option<String>,
+}
+
+pub fn script_commands(dir: &Path, package: &PackageJson) -> ScriptCommands {
+    let manager = detect_package_manager(dir, Some(package));
+    ScriptCommands {
+        package_manager: manager,
+        dev: package.first_script(DEV_SCRIPTS).map(|s| manager.run(s)),
+        build: package.first_script(&["build"]).map(|s| manager.run(s)),
+        start: package.first_script(START_SCRIPTS).map(|s| manager.run(s)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_package_manager_and_scripts() {
+        let dir = std::env::temp_dir().join(format!("gmdev-pkg-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("package.json"),
+            r#"{
+                "description": "Un site sans payload",
+                "dependencies": {"next": "15.0.0"},
+                "scripts": {"develop": "next dev -p 3001", "build": "next build"}
+            }"#,
+        )
+        .unwrap();
+        fs::write(dir.join("pnpm-lock.yaml"), "").unwrap();
+
+        let package = read_package_json(&dir).unwrap().unwrap();
+        assert!(package.has_dependency("next"));
+        assert!(!package.has_dependency("payload"));
+
+        let commands = script_commands(&dir, &package);
+        assert_eq!(commands.package_manager, PackageManager::Pnpm);
+        assert_eq!(commands.dev.as_deref(), Some("pnpm develop"));
+        assert_eq!(commands.build.as_deref(), Some("pnpm build"));
+        assert_eq!(commands.start, None);
+
+        let declared = PackageJson {
+            package_manager: Some("yarn@4.1.0".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            detect_package_manager(&dir, Some(&declared)),
+            PackageManager::Yarn
+        );
+
+        let _ = fs::remove_dir_all(dir);
+    }
+}
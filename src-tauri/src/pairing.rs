@@ -0,0 +1,333 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use crate::autoscan::{collect_repos, expand_parent_path, RepoCandidate};
+use crate::detectors::{DetectorRegistry, ServiceRole};
+use crate::env::parse_dotenv_entries;
+use crate::git_url::origin_remote;
+use crate::ports::detect_service_port;
+
+/// Profondeur de recherche par défaut (enfants directs du dossier parent)
+pub const DEFAULT_SCAN_DEPTH: u32 = 1;
+
+/// Score minimal pour proposer une association backend / frontend
+const MIN_PAIRING_SCORE: f32 = 0.4;
+
+/// Poids des signaux dans le score d'une association
+const NAME_WEIGHT: f32 = 0.45;
+const ENV_WEIGHT: f32 = 0.3;
+const GIT_WEIGHT: f32 = 0.15;
+const SIBLING_WEIGHT: f32 = 0.1;
+
+/// Mots qui désignent le rôle d'un dépôt plutôt que le projet
+const ROLE_WORDS: &[&str] = &[
+    "backend", "back", "api", "server", "cms", "admin", "frontend", "front", "web", "site", "app",
+    "client", "www", "ui",
+];
+
+/// Fichiers .env du frontend où chercher l'URL du backend
+const FRONTEND_ENV_FILES: &[&str] = &[".env.local", ".env", ".env.development"];
+
+/// Fragments de clés susceptibles de contenir l'URL du backend
+const BACKEND_URL_MARKERS: &[&str] = &["URL", "API", "SERVER", "BACKEND", "PAYLOAD", "CMS"];
+
+/// Indice en faveur d'une association
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PairingSignal {
+    /// "name", "env", "git" ou "siblings"
+    pub kind: String,
+    /// Entre 0 et 1, avant pondération
+    pub score: f32,
+    pub detail: String,
+}
+
+/// Association backend / frontend proposée à l'utilisateur
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PairingCandidate {
+    pub backend_path: String,
+    pub backend_name: String,
+    pub frontend_path: String,
+    pub frontend_name: String,
+    /// Somme pondérée des signaux, entre 0 et 1
+    pub score: f32,
+    pub signals: Vec<PairingSignal>,
+}
+
+/// Association confirmée par l'utilisateur
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RepoPair {
+    pub backend_path: String,
+    pub frontend_path: String,
+}
+
+/// Mots d'un nom de dossier, sans les mots de rôle (`gestionmax-api` -> `gestionmax`)
+fn base_tokens(name: &str) -> Vec<String> {
+    let lower = name.to_lowercase();
+    lower
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|token| !token.is_empty() && !ROLE_WORDS.contains(token))
+        .map(|token| {
+            // Suffixe collé : "gestionmaxback" -> "gestionmax"
+            ROLE_WORDS
+                .iter()
+                .find_map(|word| token.strip_suffix(word).filter(|rest| rest.len() >= 3))
+                .unwrap_or(token)
+                .to_string()
+        })
+        .collect()
+}
+
+/// Similarité de deux noms de dépôts, entre 0 et 1
+pub fn name_similarity(a: &str, b: &str) -> f32 {
+    let (left, right) = (base_tokens(a), base_tokens(b));
+    if left.is_empty() || right.is_empty() {
+        return 0.0;
+    }
+    if left == right {
+        return 1.0;
+    }
+
+    let common = left.iter().filter(|t| right.contains(t)).count();
+    let union = left.len() + right.len() - common;
+    let jaccard = common as f32 / union as f32;
+
+    // Préfixe commun des noms concaténés : "acmeshop" / "acmestore"
+    let (joined_left, joined_right) = (left.concat(), right.concat());
+    let prefix = joined_left
+        .chars()
+        .zip(joined_right.chars())
+        .take_while(|(l, r)| l == r)
+        .count();
+    let prefix_score = if prefix >= 4 {
+        2.0 * prefix as f32 / (joined_left.len() + joined_right.len()) as f32
+    } else {
+        0.0
+    };
+
+    jaccard.max(prefix_score)
+}
+
+/// Variable du frontend qui pointe vers le port du backend
+fn env_reference(frontend: &Path, backend_port: u16) -> Option<String> {
+    let port_re = Regex::new(&format!(r":{}(\D|$)", backend_port)).ok()?;
+    FRONTEND_ENV_FILES.iter().find_map(|name| {
+        let content = fs::read_to_string(frontend.join(name)).ok()?;
+        parse_dotenv_entries(&content)
+            .into_iter()
+            .filter(|entry| {
+                let key = entry.key.to_ascii_uppercase();
+                BACKEND_URL_MARKERS
+                    .iter()
+                    .any(|marker| key.contains(marker))
+            })
+            .find(|entry| port_re.is_match(&entry.value))
+            .map(|entry| {
+                format!(
+                    "{}={} ({} ligne {})",
+                    entry.key, entry.value, name, entry.line
+                )
+            })
+    })
+}
+
+/// Hôte et organisation du remote `origin` (`github.com`, `gestionmax`)
+///
+/// Pour un sous-groupe GitLab ou un projet Azure, seul le premier niveau compte.
+pub fn remote_owner(repo: &Path) -> Option<(String, String)> {
+    let remote = origin_remote(repo)?;
+    let owner = remote.owner?.split('/').next()?.to_lowercase();
+    Some((remote.host?, owner))
+}
+
+/// Score d'une association et signaux qui la justifient
+pub fn score_pair(backend: &RepoCandidate, frontend: &RepoCandidate) -> PairingCandidate {
+    let backend_dir = Path::new(&backend.path);
+    let frontend_dir = Path::new(&frontend.path);
+    let mut signals = Vec::new();
+
+    let dir_name = |path: &Path| {
+        path.file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default()
+            .to_string()
+    };
+    let (backend_dir_name, frontend_dir_name) = (dir_name(backend_dir), dir_name(frontend_dir));
+    let similarity = name_similarity(&backend_dir_name, &frontend_dir_name);
+    if similarity > 0.0 {
+        signals.push(PairingSignal {
+            kind: "name".to_string(),
+            score: similarity,
+            detail: format!("{} / {}", backend_dir_name, frontend_dir_name),
+        });
+    }
+
+    let backend_port = detect_service_port(backend_dir, ServiceRole::Backend, "payload");
+    if let Some(detail) = backend_port.and_then(|p| env_reference(frontend_dir, p.port)) {
+        signals.push(PairingSignal {
+            kind: "env".to_string(),
+            score: 1.0,
+            detail,
+        });
+    }
+
+    if let (Some(left), Some(right)) = (remote_owner(backend_dir), remote_owner(frontend_dir)) {
+        if left == right {
+            signals.push(PairingSignal {
+                kind: "git".to_string(),
+                score: 1.0,
+                detail: format!("{}/{}", left.0, left.1),
+            });
+        }
+    }
+
+    if backend_dir.parent() == frontend_dir.parent() {
+        signals.push(PairingSignal {
+            kind: "siblings".to_string(),
+            score: 1.0,
+            detail: backend_dir
+                .parent()
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or_default(),
+        });
+    }
+
+    let score = signals
+        .iter()
+        .map(|signal| {
+            let weight = match signal.kind.as_str() {
+                "name" => NAME_WEIGHT,
+                "env" => ENV_WEIGHT,
+                "git" => GIT_WEIGHT,
+                _ => SIBLING_WEIGHT,
+            };
+            weight * signal.score
+        })
+        .sum();
+
+    PairingCandidate {
+        backend_path: backend.path.clone(),
+        backend_name: backend.name.clone(),
+        frontend_path: frontend.path.clone(),
+        frontend_name: frontend.name.clone(),
+        score,
+        signals,
+    }
+}
+
+/// Associations possibles au-dessus du seuil, de la plus probable à la moins probable
+pub fn rank_pairings(
+    backends: &[RepoCandidate],
+    frontends: &[RepoCandidate],
+) -> Vec<PairingCandidate> {
+    let mut candidates: Vec<PairingCandidate> = backends
+        .iter()
+        .flat_map(|backend| frontends.iter().map(move |frontend| (backend, frontend)))
+        .map(|(backend, frontend)| score_pair(backend, frontend))
+        .filter(|candidate| candidate.score >= MIN_PAIRING_SCORE)
+        .collect();
+    candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+    candidates
+}
+
+/// Meilleures associations, chaque dépôt n'étant utilisé qu'une fois
+pub fn best_pairs(candidates: &[PairingCandidate]) -> Vec<RepoPair> {
+    let mut pairs: Vec<RepoPair> = Vec::new();
+    for candidate in candidates {
+        let taken = pairs.iter().any(|pair| {
+            pair.backend_path == candidate.backend_path
+                || pair.frontend_path == candidate.frontend_path
+        });
+        if !taken {
+            pairs.push(RepoPair {
+                backend_path: candidate.backend_path.clone(),
+                frontend_path: candidate.frontend_path.clone(),
+            });
+        }
+    }
+    pairs
+}
+
+/// Associations backend / frontend proposées pour les dépôts d'un dossier
+#[tauri::command]
+pub async fn rank_repo_pairings(
+    parent_path: String,
+    depth: Option<u32>,
+) -> Result<Vec<PairingCandidate>, String> {
+    let parent = expand_parent_path(&parent_path)?;
+    let registry = DetectorRegistry::with_defaults();
+    let repos = collect_repos(&parent, depth.unwrap_or(DEFAULT_SCAN_DEPTH), &registry)?;
+    Ok(rank_pairings(&repos.backends, &repos.frontends))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detectors::Detection;
+
+    #[test]
+    fn test_name_similarity() {
+        assert_eq!(name_similarity("gestionmax-api", "gestionmax-web"), 1.0);
+        assert_eq!(name_similarity("acme-cms", "acme-site"), 1.0);
+        assert_eq!(name_similarity("gestionmaxback", "gestionmaxfront"), 1.0);
+        assert!(name_similarity("acme-shop-api", "acme-store") > 0.3);
+        assert_eq!(name_similarity("acme-api", "globex-web"), 0.0);
+    }
+
+    #[test]
+    fn test_rank_pairings() {
+        let root = std::env::temp_dir().join(format!("gmdev-pairing-{}", uuid::Uuid::new_v4()));
+        let repo = |name: &str, remote: &str| {
+            let dir = root.join(name);
+            fs::create_dir_all(dir.join(".git")).unwrap();
+            fs::write(
+                dir.join(".git/config"),
+                format!(
+                    "[core]\n\tbare = false\n[remote \"origin\"]\n\turl = {}\n",
+                    remote
+                ),
+            )
+            .unwrap();
+            RepoCandidate {
+                path: dir.to_string_lossy().to_string(),
+                name: name.to_string(),
+                stack: Detection {
+                    stack: "payload".to_string(),
+                    label: String::new(),
+                    role: ServiceRole::Backend,
+                    confidence: 1.0,
+                    dev_command: String::new(),
+                    build_command: None,
+                    start_command: None,
+                    package_manager: None,
+                    port_sources: Vec::new(),
+                    health_endpoint: None,
+                },
+            }
+        };
+
+        let cms = repo("content-hub", "git@github.com:Acme/content-hub.git");
+        fs::write(root.join("content-hub/.env"), "PORT=3010\n").unwrap();
+        let site = repo("marketing", "https://github.com/acme/marketing.git");
+        fs::write(
+            root.join("marketing/.env.local"),
+            "NEXT_PUBLIC_PORT=3000\nNEXT_PUBLIC_SERVER_URL=http://localhost:3010\n",
+        )
+        .unwrap();
+        let other = repo("globex-web", "git@gitlab.com:globex/web.git");
+
+        let candidates = rank_pairings(&[cms], &[other, site]);
+        assert_eq!(candidates.len(), 1);
+        let kinds: Vec<&str> = candidates[0]
+            .signals
+            .iter()
+            .map(|s| s.kind.as_str())
+            .collect();
+        assert_eq!(kinds, vec!["env", "git", "siblings"]);
+        assert!(candidates[0].frontend_path.ends_with("marketing"));
+        assert_eq!(best_pairs(&candidates).len(), 1);
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
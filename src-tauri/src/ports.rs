@@ -0,0 +1,295 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fs;
+use std::net::TcpListener;
+use std::path::Path;
+
+use crate::autoscan::ProjectV3;
+use crate::detectors::{DetectorRegistry, PortSource, ServiceRole};
+use crate::env::parse_dotenv_entries;
+use crate::package_json::{read_package_json, DEV_SCRIPTS};
+
+/// Fichiers .env lus pour le port, par ordre de priorité
+const PORT_ENV_FILES: &[&str] = &[".env.local", ".env"];
+
+/// Extensions des fichiers de configuration dont les commentaires sont ignorés
+const SCRIPT_EXTENSIONS: &[&str] = &["js", "mjs", "cjs", "ts", "mts", "cts"];
+
+/// Port détecté et endroit où il a été lu
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PortDetection {
+    pub port: u16,
+    /// Fichier relatif au dossier du service (".env.local", "package.json"...)
+    pub file: String,
+    /// Ligne (à partir de 1) quand elle est connue
+    pub line: Option<usize>,
+    /// Provenance lisible, ex. ".env.local ligne 4" ou "script \"dev\" de package.json"
+    pub source: String,
+    /// Règle appliquée : "env:<CLÉ>", "config" ou "script"
+    pub rule: String,
+}
+
+impl PortDetection {
+    fn at_line(port: u16, file: &str, line: usize, rule: String) -> Self {
+        Self {
+            port,
+            file: file.to_string(),
+            line: Some(line),
+            source: format!("{} ligne {}", file, line),
+            rule,
+        }
+    }
+
+    /// Une variable d'environnement est plus fiable qu'une option de script ou un fichier de config
+    pub fn confidence(&self) -> f32 {
+        if self.rule.starts_with("env:") {
+            0.9
+        } else {
+            0.8
+        }
+    }
+}
+
+/// Lit un port d'après les sources déclarées par une stack, dans l'ordre
+///
+/// Les ports par défaut des stacks ne sont pas utilisés : `None` signifie que
+/// rien dans le dossier ne définit le port.
+pub fn resolve_port(dir: &Path, sources: &[PortSource]) -> Option<PortDetection> {
+    sources.iter().find_map(|source| match source {
+        PortSource::Env { keys } => port_from_env_files(dir, keys),
+        PortSource::File { file, pattern } => port_from_file(dir, file, pattern),
+        PortSource::Scripts => port_from_scripts(dir),
+        PortSource::Default { .. } => None,
+    })
+}
+
+/// Port d'un service selon la stack détectée (sources Payload / Next.js sinon)
+pub fn detect_service_port(
+    dir: &Path,
+    role: ServiceRole,
+    fallback_stack: &str,
+) -> Option<PortDetection> {
+    let registry = DetectorRegistry::with_defaults();
+    let sources = registry
+        .best(dir, role)
+        .map(|detection| detection.port_sources)
+        .unwrap_or_else(|| registry.port_sources(fallback_stack));
+    resolve_port(dir, &sources)
+}
+
+/// Ports déjà attribués aux projets configurés (services, compose, tunnel)
+pub fn reserved_ports(projects: &[ProjectV3]) -> BTreeSet<u16> {
+    let mut ports = BTreeSet::new();
+    for project in projects {
+        ports.extend([project.ports.backend, project.ports.frontend]);
+        ports.extend(project.ports.services.values());
+        ports.extend(project.apps.values().map(|app| app.port));
+        if let Some(tunnel) = &project.tunnel {
+            ports.extend(tunnel.forwards.iter().filter_map(|f| f.listen_port()));
+        }
+    }
+    ports.remove(&0);
+    ports
+}
+
+/// Premier port après `after` qui n'est ni réservé ni déjà en écoute ; il est réservé
+pub fn allocate_port(after: u16, reserved: &mut BTreeSet<u16>) -> Option<u16> {
+    let port = (after.saturating_add(1)..=u16::MAX)
+        .find(|port| !reserved.contains(port) && TcpListener::bind(("127.0.0.1", *port)).is_ok())?;
+    reserved.insert(port);
+    Some(port)
+}
+
+/// `.env.local` est prioritaire sur `.env` ; dans un fichier, la dernière définition l'emporte
+fn port_from_env_files(dir: &Path, keys: &[String]) -> Option<PortDetection> {
+    PORT_ENV_FILES.iter().find_map(|name| {
+        let content = fs::read_to_string(dir.join(name)).ok()?;
+        let entries = parse_dotenv_entries(&content);
+        keys.iter().find_map(|key| {
+            let entry = entries.iter().rev().find(|entry| &entry.key == key)?;
+            let port = entry.value.trim().parse::<u16>().ok()?;
+            Some(PortDetection::at_line(
+                port,
+                name,
+                entry.line,
+                format!("env:{}", key),
+            ))
+        })
+    })
+}
+
+fn port_from_file(dir: &Path, file: &str, pattern: &str) -> Option<PortDetection> {
+    let content = fs::read_to_string(dir.join(file)).ok()?;
+    let is_script = Path::new(file)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| SCRIPT_EXTENSIONS.contains(&ext));
+    let content = if is_script {
+        strip_js_comments(&content)
+    } else {
+        content
+    };
+
+    let re = Regex::new(pattern).ok()?;
+    let group = re.captures(&content)?.get(1)?;
+    let port = group.as_str().parse::<u16>().ok()?;
+    let line = content[..group.start()].matches('\n').count() + 1;
+    Some(PortDetection::at_line(
+        port,
+        file,
+        line,
+        "config".to_string(),
+    ))
+}
+
+/// Scripts de développement d'abord, puis les autres par ordre alphabétique
+fn port_from_scripts(dir: &Path) -> Option<PortDetection> {
+    let package = read_package_json(dir).ok()??;
+    let preferred = DEV_SCRIPTS
+        .iter()
+        .filter_map(|name| package.scripts.get_key_value(*name));
+    let others = package
+        .scripts
+        .iter()
+        .filter(|(name, _)| !DEV_SCRIPTS.contains(&name.as_str()));
+
+    preferred.chain(others).find_map(|(name, command)| {
+        let port = port_from_command(command)?;
+        Some(PortDetection {
+            port,
+            file: "package.json".to_string(),
+            line: None,
+            source: format!("script \"{}\" de package.json", name),
+            rule: "script".to_string(),
+        })
+    })
+}
+
+/// Port passé à une commande : `-p 3001`, `--port 3001`, `--port=3001` ou `PORT=3001 cmd`
+pub fn port_from_command(command: &str) -> Option<u16> {
+    let mut tokens = command.split_whitespace().peekable();
+    while let Some(token) = tokens.next() {
+        let value = match token {
+            "-p" | "--port" => tokens.peek().copied(),
+            _ => token
+                .strip_prefix("--port=")
+                .or_else(|| token.strip_prefix("-p="))
+                .or_else(|| token.strip_prefix("PORT=")),
+        };
+        if let Some(port) = value.and_then(|v| v.trim_matches(['"', '\'']).parse::<u16>().ok()) {
+            return Some(port);
+        }
+    }
+    None
+}
+
+/// Retire les commentaires `//` et `/* */` en gardant les retours à la ligne
+/// (numéros de ligne inchangés) et le contenu des chaînes
+fn strip_js_comments(source: &str) -> String {
+    let mut out = String::with_capacity(source.len());
+    let mut chars = source.chars().peekable();
+    let mut quote: Option<char> = None;
+
+    while let Some(c) = chars.next() {
+        if let Some(q) = quote {
+            out.push(c);
+            if c == '\\' {
+                if let Some(escaped) = chars.next() {
+                    out.push(escaped);
+                }
+            } else if c == q {
+                quote = None;
+            }
+            continue;
+        }
+
+        match (c, chars.peek()) {
+            ('/', Some('/')) => {
+                while chars.peek().is_some_and(|next| *next != '\n') {
+                    chars.next();
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut previous = '\0';
+                for next in chars.by_ref() {
+                    if next == '\n' {
+                        out.push('\n');
+                    }
+                    if previous == '*' && next == '/' {
+                        break;
+                    }
+                    previous = next;
+                }
+            }
+            ('"' | '\'' | '`', _) => {
+                quote = Some(c);
+                out.push(c);
+            }
+            _ => out.push(c),
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_port_from_command() {
+        assert_eq!(port_from_command("next dev -p 3001"), Some(3001));
+        assert_eq!(port_from_command("vite --port=5174 --host"), Some(5174));
+        assert_eq!(port_from_command("PORT=3010 payload dev"), Some(3010));
+        assert_eq!(port_from_command("astro dev --port \"4322\""), Some(4322));
+        assert_eq!(port_from_command("next dev --turbo"), None);
+    }
+
+    #[test]
+    fn test_resolve_port_provenance() {
+        let dir = std::env::temp_dir().join(format!("gmdev-ports-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join(".env"),
+            "# PORT=4000\nNEXT_PUBLIC_PORT=5000\nBASE=3000\nexport PORT=${BASE}\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("next.config.mjs"),
+            "export default {\n  // port: 9999\n  /* port: 8888 */\n  server: { port: 3002 },\n};\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("package.json"),
+            r#"{"scripts": {"build": "next build", "preview": "next start -p 4100"}}"#,
+        )
+        .unwrap();
+
+        let env = PortSource::Env {
+            keys: vec!["PORT".to_string()],
+        };
+        let detection = resolve_port(&dir, &[env]).unwrap();
+        assert_eq!(detection.port, 3000);
+        assert_eq!(detection.source, ".env ligne 4");
+
+        let config = PortSource::File {
+            file: "next.config.mjs".to_string(),
+            pattern: r"port:\s*(\d+)".to_string(),
+        };
+        let detection = resolve_port(&dir, &[config]).unwrap();
+        assert_eq!((detection.port, detection.line), (3002, Some(4)));
+
+        let detection = resolve_port(&dir, &[PortSource::Scripts]).unwrap();
+        assert_eq!(detection.port, 4100);
+        assert_eq!(detection.source, "script \"preview\" de package.json");
+
+        assert_eq!(
+            resolve_port(&dir, &[PortSource::Default { port: 3000 }]),
+            None
+        );
+
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -4,26 +4,236 @@ use std::{fs, path::PathBuf};
 
 use tauri::command;
 
-use crate::autoscan::ProjectV3;
+use crate::autoscan::{ForwardKind, ProjectV3};
+use crate::config_watcher;
+use crate::manifest;
 
 const FILE_NAME: &str = "projects-v3.json";
 
-// Resolve ~/.gestionmax-devcenter/projects-v3.json
-fn config_file_path() -> PathBuf {
+// Resolve ~/.gestionmax-devcenter
+pub fn config_dir() -> PathBuf {
     let mut path = dirs::home_dir().unwrap();
     path.push(".gestionmax-devcenter");
     fs::create_dir_all(&path).ok();
-    path.push(FILE_NAME);
     path
 }
 
-#[derive(Serialize, Deserialize, Default)]
+// Resolve ~/.gestionmax-devcenter/projects-v3.json
+pub fn config_file_path() -> PathBuf {
+    config_dir().join(FILE_NAME)
+}
+
+#[derive(Serialize, Deserialize, Default, Clone)]
 pub struct ProjectConfigV3 {
     pub projects: Vec<ProjectV3>,
 }
 
-#[command]
-pub async fn load_projects_v3() -> Result<ProjectConfigV3, String> {
+/// Types de backend reconnus
+const BACKEND_TYPES: &[&str] = &["payload", "directus"];
+
+/// Un id de projet sert de nom de fichier (env, clés, sockets ssh) : a-z, 0-9 et tirets
+pub fn is_valid_project_id(id: &str) -> bool {
+    !id.is_empty()
+        && id
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-')
+}
+
+/// Refuse un id qui sortirait du dossier runtime une fois placé dans un chemin
+pub fn check_project_id(id: &str) -> Result<(), String> {
+    if is_valid_project_id(id) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Id de projet invalide '{id}' (attendus: a-z, 0-9, -)"
+        ))
+    }
+}
+
+/// Id de projet tiré d'un nom de dossier ("GestionMax_Back" -> "gestionmax-back")
+pub fn project_id_from_name(name: &str) -> String {
+    let id = name
+        .to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    if id.is_empty() {
+        "project".to_string()
+    } else {
+        id
+    }
+}
+
+/// Valide un projet V3 (règles communes à projects-v3.json et aux manifestes)
+pub fn validate_project(project: &ProjectV3) -> Vec<String> {
+    let mut errors = Vec::new();
+    let label = if project.id.is_empty() {
+        "<sans id>"
+    } else {
+        project.id.as_str()
+    };
+
+    if project.id.trim().is_empty() {
+        errors.push("id manquant".to_string());
+    } else if !is_valid_project_id(&project.id) {
+        errors.push(format!("{label}: id invalide (attendus: a-z, 0-9, -)"));
+    }
+    if project.name.trim().is_empty() {
+        errors.push(format!("{label}: nom manquant"));
+    }
+    if project.ports.backend == 0 {
+        errors.push(format!("{label}: port backend invalide (0)"));
+    }
+    if project.ports.frontend == 0 {
+        errors.push(format!("{label}: port frontend invalide (0)"));
+    }
+    let mut used = std::collections::BTreeMap::new();
+    for (owner, port) in [
+        ("backend".to_string(), project.ports.backend),
+        ("frontend".to_string(), project.ports.frontend),
+    ]
+    .into_iter()
+    .chain(
+        project
+            .ports
+            .services
+            .iter()
+            .map(|(name, port)| (format!("compose:{name}"), *port)),
+    ) {
+        if port == 0 {
+            if owner.starts_with("compose:") {
+                errors.push(format!("{label}: port {owner} invalide (0)"));
+            }
+            continue;
+        }
+        if let Some(other) = used.insert(port, owner.clone()) {
+            errors.push(format!(
+                "{label}: port {port} utilisé par {other} et {owner}"
+            ));
+        }
+    }
+    if let Some(backend_type) = &project.backend_type {
+        if !BACKEND_TYPES.contains(&backend_type.as_str()) {
+            errors.push(format!(
+                "{label}: backendType '{backend_type}' inconnu (attendus: {})",
+                BACKEND_TYPES.join(", ")
+            ));
+        }
+    }
+    if let Some(tunnel) = project.tunnel.as_ref().filter(|t| t.enabled) {
+        // Avec un alias, hôte, utilisateur et port viennent de ~/.ssh/config
+        if tunnel.ssh_alias.is_none() {
+            if tunnel.host.trim().is_empty() {
+                errors.push(format!("{label}: tunnel activé sans hôte"));
+            }
+            if tunnel.user.trim().is_empty() {
+                errors.push(format!("{label}: tunnel activé sans utilisateur"));
+            }
+            if tunnel.port == 0 {
+                errors.push(format!("{label}: port SSH du tunnel invalide (0)"));
+            }
+        }
+        if tunnel.forwards.is_empty() {
+            errors.push(format!("{label}: tunnel activé sans transfert de port"));
+        }
+        for (index, forward) in tunnel.forwards.iter().enumerate() {
+            let name = if forward.name.is_empty() {
+                format!("#{}", index + 1)
+            } else {
+                forward.name.clone()
+            };
+            let needs_remote = forward.kind != ForwardKind::Dynamic;
+            if forward.local_port == 0 || (needs_remote && forward.remote_port == 0) {
+                errors.push(format!("{label}: ports du transfert {name} invalides (0)"));
+                continue;
+            }
+            if let Some(port) = forward.listen_port() {
+                let owner = format!("tunnel:{name}");
+                if let Some(other) = used.insert(port, owner.clone()) {
+                    errors.push(format!(
+                        "{label}: port {port} utilisé par {other} et {owner}"
+                    ));
+                }
+            }
+        }
+    }
+    if let Some(active) = &project.active_profile {
+        if !project.profiles.contains_key(active) {
+            errors.push(format!("{label}: profil actif '{active}' introuvable"));
+        }
+    }
+    for (name, profile) in &project.profiles {
+        if name.trim().is_empty() {
+            errors.push(format!("{label}: profil sans nom"));
+        }
+        if let Some(ports) = &profile.overrides.ports {
+            if ports.backend == Some(0) || ports.frontend == Some(0) {
+                errors.push(format!(
+                    "{label}: profil '{name}' avec un port invalide (0)"
+                ));
+            }
+        }
+    }
+    for (service, config) in [
+        ("backend", &project.backend),
+        ("frontend", &project.frontend),
+    ] {
+        if let Some(check) = config.as_ref().and_then(|c| c.health_check.as_ref()) {
+            if check.url.trim().is_empty() {
+                errors.push(format!("{label}: healthCheck {service} sans URL"));
+            }
+        }
+    }
+
+    errors
+}
+
+/// Valide la configuration complète (règles par projet + unicité des ids)
+pub fn validate_config(config: &ProjectConfigV3) -> Result<(), String> {
+    let mut errors: Vec<String> = config.projects.iter().flat_map(validate_project).collect();
+
+    let mut seen = std::collections::HashSet::new();
+    for project in &config.projects {
+        if !seen.insert(project.id.as_str()) {
+            errors.push(format!("id de projet dupliqué: {}", project.id));
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("Invalid V3 config: {}", errors.join("; ")))
+    }
+}
+
+/// Convertit les ids hérités (points, majuscules, espaces) au format a-z, 0-9, - ;
+/// un id converti déjà pris reçoit un suffixe numérique. Persisté au prochain enregistrement.
+fn migrate_legacy_ids(config: &mut ProjectConfigV3) {
+    let mut taken: std::collections::HashSet<String> = config
+        .projects
+        .iter()
+        .filter(|p| is_valid_project_id(&p.id))
+        .map(|p| p.id.clone())
+        .collect();
+    for project in config.projects.iter_mut() {
+        if is_valid_project_id(&project.id) {
+            continue;
+        }
+        let base = project_id_from_name(&project.id);
+        let mut id = base.clone();
+        let mut n = 2;
+        while taken.contains(&id) {
+            id = format!("{base}-{n}");
+            n += 1;
+        }
+        taken.insert(id.clone());
+        project.id = id;
+    }
+}
+
+/// Lit projects-v3.json et résout les projets issus d'un manifeste
+pub fn read_config() -> Result<ProjectConfigV3, String> {
     let path = config_file_path();
 
     if !path.exists() {
@@ -32,20 +242,75 @@ pub async fn load_projects_v3() -> Result<ProjectConfigV3, String> {
 
     let text = fs::read_to_string(&path).map_err(|e| format!("Failed to read V3 config: {e}"))?;
 
-    let cfg: ProjectConfigV3 =
+    let mut cfg: ProjectConfigV3 =
         serde_json::from_str(&text).map_err(|e| format!("Invalid V3 JSON: {e}"))?;
+    migrate_legacy_ids(&mut cfg);
+
+    // Les projets issus d'un manifeste sont recalculés : manifeste puis surcharges locales.
+    // Un manifeste absent ou invalide ne bloque pas les autres projets : le projet
+    // enregistré est conservé et l'erreur lui est attachée.
+    for project in cfg.projects.iter_mut() {
+        let stored = project.clone();
+        match manifest::resolve_project(project) {
+            Ok(()) => project.manifest_error = None,
+            Err(e) => {
+                *project = stored;
+                project.manifest_error = Some(e);
+            }
+        }
+    }
 
     Ok(cfg)
 }
 
-#[command]
-pub async fn save_projects_v3(config: ProjectConfigV3) -> Result<(), String> {
+/// Valide puis écrit projects-v3.json
+pub fn write_config(config: &ProjectConfigV3) -> Result<(), String> {
+    validate_config(config)?;
+
     let path = config_file_path();
 
+    // L'erreur de manifeste est recalculée à chaque lecture
+    let mut config = config.clone();
+    for project in config.projects.iter_mut() {
+        project.manifest_error = None;
+    }
+
     let text = serde_json::to_string_pretty(&config)
         .map_err(|e| format!("Failed to serialize V3 JSON: {e}"))?;
 
     fs::write(&path, text).map_err(|e| format!("Failed to write V3 config: {e}"))?;
+    config_watcher::record_config_write();
 
     Ok(())
 }
+
+#[command]
+pub async fn load_projects_v3() -> Result<ProjectConfigV3, String> {
+    read_config()
+}
+
+#[command]
+pub async fn save_projects_v3(config: ProjectConfigV3) -> Result<(), String> {
+    write_config(&config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_legacy_ids_are_migrated_on_read() {
+        let legacy = r#"{"projects": [
+            {"id": "gestionmax.site", "name": "Site", "rootPath": "/tmp/a", "backendPath": "", "frontendPath": "", "ports": {"backend": 3010, "frontend": 3000}, "createdAt": ""},
+            {"id": "GestionMax.Site", "name": "Site 2", "rootPath": "/tmp/b", "backendPath": "", "frontendPath": "", "ports": {"backend": 3110, "frontend": 3100}, "createdAt": ""},
+            {"id": "ops", "name": "Ops", "rootPath": "/tmp/c", "backendPath": "", "frontendPath": "", "ports": {"backend": 3210, "frontend": 3200}, "createdAt": ""}
+        ]}"#;
+        let mut config: ProjectConfigV3 = serde_json::from_str(legacy).unwrap();
+        assert!(validate_config(&config).is_err());
+
+        migrate_legacy_ids(&mut config);
+        let ids: Vec<&str> = config.projects.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(ids, ["gestionmax-site", "gestionmax-site-2", "ops"]);
+        assert!(validate_config(&config).is_ok());
+    }
+}
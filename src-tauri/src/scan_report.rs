@@ -0,0 +1,302 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+use crate::autoscan::{scan_project, ProjectScanResult, ProjectV3};
+use crate::ignore_rules::SkippedDir;
+use crate::projects_v3::{read_config, write_config, ProjectConfigV3};
+
+/// Champs de projects-v3.json qu'un nouveau scan peut modifier
+const PROJECT_FIELDS: &[&str] = &[
+    "name",
+    "backendPath",
+    "frontendPath",
+    "ports.backend",
+    "ports.frontend",
+    "commands.backend",
+    "commands.frontend",
+    "manifestPath",
+    "ports.services",
+    "compose",
+];
+
+/// Valeur retenue par l'autoscan et la règle qui l'a produite
+///
+/// `field` est un chemin pointé dans le JSON d'un `ProjectV3` (`ports.backend`),
+/// ou `backendStack` / `frontendStack` pour la stack détectée.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ScanField {
+    pub field: String,
+    pub value: Value,
+    /// Fichier ou dossier d'où vient la valeur
+    pub source: Option<String>,
+    /// "manifest", "workspace", "stack:<id>", "env:<CLÉ>", "script"...
+    pub rule: String,
+    /// Entre 0 et 1
+    pub confidence: f32,
+}
+
+/// Candidat écarté au profit de la valeur retenue
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RejectedCandidate {
+    pub field: String,
+    pub value: Value,
+    pub rule: String,
+    pub confidence: f32,
+    pub reason: String,
+}
+
+/// Journal des décisions d'un scan
+#[derive(Debug, Default)]
+pub struct ScanTrace {
+    pub fields: Vec<ScanField>,
+    pub rejected: Vec<RejectedCandidate>,
+    pub skipped: Vec<SkippedDir>,
+}
+
+impl ScanTrace {
+    pub fn record(
+        &mut self,
+        field: &str,
+        value: impl Serialize,
+        source: Option<String>,
+        rule: impl Into<String>,
+        confidence: f32,
+    ) {
+        self.fields.push(ScanField {
+            field: field.to_string(),
+            value: serde_json::to_value(value).unwrap_or(Value::Null),
+            source,
+            rule: rule.into(),
+            confidence,
+        });
+    }
+
+    pub fn reject(
+        &mut self,
+        field: &str,
+        value: impl Serialize,
+        rule: impl Into<String>,
+        confidence: f32,
+        reason: impl Into<String>,
+    ) {
+        self.rejected.push(RejectedCandidate {
+            field: field.to_string(),
+            value: serde_json::to_value(value).unwrap_or(Value::Null),
+            rule: rule.into(),
+            confidence,
+            reason: reason.into(),
+        });
+    }
+
+    /// Dossier écarté par une règle d'ignore (une seule fois par chemin)
+    pub fn skip(&mut self, skipped: SkippedDir) {
+        if !self.skipped.iter().any(|s| s.path == skipped.path) {
+            self.skipped.push(skipped);
+        }
+    }
+}
+
+/// Champ qu'appliquer le scan modifierait dans le projet existant
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FieldChange {
+    pub field: String,
+    /// `None` si le champ est absent de la configuration actuelle
+    pub current: Option<Value>,
+    pub proposed: Value,
+    pub rule: String,
+    pub source: Option<String>,
+    pub confidence: f32,
+}
+
+/// Rapport détaillé d'un scan, comparé au projet déjà configuré s'il existe
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ScanReport {
+    pub scan: ProjectScanResult,
+    pub fields: Vec<ScanField>,
+    pub rejected: Vec<RejectedCandidate>,
+    /// Dossiers non parcourus et règle responsable
+    pub skipped: Vec<SkippedDir>,
+    /// Projet de projects-v3.json ayant la même racine
+    pub project_id: Option<String>,
+    pub changes: Vec<FieldChange>,
+}
+
+fn same_path(a: &str, b: &Path) -> bool {
+    match (fs::canonicalize(a), fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => Path::new(a) == b,
+    }
+}
+
+fn find_project<'a>(config: &'a ProjectConfigV3, root: &Path) -> Option<&'a ProjectV3> {
+    config
+        .projects
+        .iter()
+        .find(|p| same_path(&p.root_path, root))
+}
+
+fn field_pointer(field: &str) -> String {
+    format!("/{}", field.replace('.', "/"))
+}
+
+/// Différences champ par champ entre le projet configuré et les valeurs scannées
+pub fn diff_project(project: &ProjectV3, fields: &[ScanField]) -> Vec<FieldChange> {
+    let current = serde_json::to_value(project).unwrap_or(Value::Null);
+
+    fields
+        .iter()
+        .filter(|f| PROJECT_FIELDS.contains(&f.field.as_str()))
+        .filter_map(|f| {
+            let existing = current
+                .pointer(&field_pointer(&f.field))
+                .filter(|v| !v.is_null())
+                .cloned();
+            if existing.as_ref() == Some(&f.value) {
+                return None;
+            }
+            Some(FieldChange {
+                field: f.field.clone(),
+                current: existing,
+                proposed: f.value.clone(),
+                rule: f.rule.clone(),
+                source: f.source.clone(),
+                confidence: f.confidence,
+            })
+        })
+        .collect()
+}
+
+fn set_field(target: &mut Value, field: &str, value: Value) {
+    let mut current = target;
+    let mut parts = field.split('.').peekable();
+    while let Some(part) = parts.next() {
+        if !current.is_object() {
+            *current = Value::Object(Default::default());
+        }
+        let Value::Object(map) = current else {
+            return;
+        };
+        if parts.peek().is_none() {
+            map.insert(part.to_string(), value);
+            return;
+        }
+        current = map.entry(part.to_string()).or_insert(Value::Null);
+    }
+}
+
+/// Applique les changements choisis au projet
+pub fn apply_changes(
+    project: &ProjectV3,
+    changes: &[FieldChange],
+    selected: &[String],
+) -> Result<ProjectV3, String> {
+    let mut value = serde_json::to_value(project)
+        .map_err(|e| format!("Failed to serialize project {}: {}", project.id, e))?;
+
+    for field in selected {
+        let change = changes
+            .iter()
+            .find(|c| &c.field == field)
+            .ok_or_else(|| format!("Aucun changement proposé pour le champ {}", field))?;
+        set_field(&mut value, &change.field, change.proposed.clone());
+    }
+
+    serde_json::from_value(value)
+        .map_err(|e| format!("Projet {} invalide après application: {}", project.id, e))
+}
+
+fn build_report(root: &Path) -> Result<(ScanReport, ProjectConfigV3), String> {
+    let mut trace = ScanTrace::default();
+    let scan = scan_project(root, &mut trace)?;
+    let config = read_config()?;
+
+    let existing = find_project(&config, root);
+    let changes = existing
+        .map(|project| diff_project(project, &trace.fields))
+        .unwrap_or_default();
+
+    let report = ScanReport {
+        scan,
+        fields: trace.fields,
+        rejected: trace.rejected,
+        skipped: trace.skipped,
+        project_id: existing.map(|p| p.id.clone()),
+        changes,
+    };
+    Ok((report, config))
+}
+
+/// Scan d'un dossier avec provenance des champs et différences avec la configuration (sans écrire)
+#[tauri::command]
+pub async fn autoscan_report(root_path: String) -> Result<ScanReport, String> {
+    build_report(Path::new(&root_path)).map(|(report, _)| report)
+}
+
+/// Relance le scan et applique uniquement les champs choisis au projet existant
+#[tauri::command]
+pub async fn apply_scan_changes(
+    root_path: String,
+    fields: Vec<String>,
+) -> Result<ProjectV3, String> {
+    let (report, mut config) = build_report(Path::new(&root_path))?;
+    let project_id = report
+        .project_id
+        .ok_or_else(|| format!("Aucun projet configuré pour {}", root_path))?;
+
+    let project = config
+        .projects
+        .iter_mut()
+        .find(|p| p.id == project_id)
+        .ok_or_else(|| format!("Project {} not found", project_id))?;
+    if let Some(manifest) = &project.manifest_path {
+        return Err(format!(
+            "Le projet {} est issu du manifeste {} : modifiez le manifeste",
+            project_id, manifest
+        ));
+    }
+
+    *project = apply_changes(project, &report.changes, &fields)?;
+    let updated = project.clone();
+    write_config(&config)?;
+    Ok(updated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::autoscan::test_project;
+
+    #[test]
+    fn test_diff_and_apply() {
+        let project = ProjectV3 {
+            name: "Site".to_string(),
+            root_path: "/tmp/site".to_string(),
+            ..test_project("site")
+        };
+
+        let mut trace = ScanTrace::default();
+        trace.record("name", "Site", None, "folder-name", 0.5);
+        trace.record("ports.frontend", 3001, None, "script", 0.8);
+        trace.record("commands.frontend", "pnpm dev", None, "stack:next", 0.9);
+        trace.record("frontendStack", "next", None, "stack:next", 0.9);
+
+        let changes = diff_project(&project, &trace.fields);
+        let fields: Vec<&str> = changes.iter().map(|c| c.field.as_str()).collect();
+        assert_eq!(fields, vec!["ports.frontend", "commands.frontend"]);
+        assert_eq!(changes[1].current, None);
+
+        let updated =
+            apply_changes(&project, &changes, &["commands.frontend".to_string()]).unwrap();
+        assert_eq!(updated.ports.frontend, 3000);
+        assert_eq!(
+            updated
+                .commands
+                .as_ref()
+                .and_then(|c| c.frontend.as_deref()),
+            Some("pnpm dev")
+        );
+        assert!(apply_changes(&project, &changes, &["tunnel.host".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,98 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::pairing::DEFAULT_SCAN_DEPTH;
+use crate::projects_v3::config_dir;
+
+/// Réglages de l'application, à côté de projects-v3.json
+const SETTINGS_FILE_NAME: &str = "settings.json";
+
+/// Racine indexée par défaut
+const DEFAULT_WORKSPACE_ROOT: &str = "~/CascadeProjects";
+
+/// Nombre d'entrées conservées dans les listes récentes
+const RECENT_LIMIT: usize = 10;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AppSettings {
+    /// Dossiers dont les dépôts sont indexés en arrière-plan (`~/` accepté)
+    pub workspace_roots: Vec<String>,
+    /// Profondeur de recherche des dépôts sous chaque racine
+    pub scan_depth: u32,
+    /// Motifs glob de dossiers ignorés par l'autoscan (syntaxe .gitignore)
+    pub ignore_patterns: Vec<String>,
+    /// Dossiers choisis récemment, du plus récent au plus ancien
+    pub recent_workspaces: Vec<String>,
+    /// Dépôts clonés récemment
+    pub recent_clones: Vec<String>,
+    /// Réinstalle les dépendances périmées avant de démarrer un service
+    pub auto_install_dependencies: bool,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            workspace_roots: vec![DEFAULT_WORKSPACE_ROOT.to_string()],
+            scan_depth: DEFAULT_SCAN_DEPTH,
+            ignore_patterns: Vec::new(),
+            recent_workspaces: Vec::new(),
+            recent_clones: Vec::new(),
+            auto_install_dependencies: false,
+        }
+    }
+}
+
+pub fn settings_path() -> PathBuf {
+    config_dir().join(SETTINGS_FILE_NAME)
+}
+
+/// Réglages enregistrés, ou réglages par défaut si le fichier n'existe pas
+pub fn read_settings() -> Result<AppSettings, String> {
+    let path = settings_path();
+    if !path.exists() {
+        return Ok(AppSettings::default());
+    }
+    let text = fs::read_to_string(&path).map_err(|e| format!("Failed to read settings: {e}"))?;
+    serde_json::from_str(&text).map_err(|e| format!("Invalid settings JSON: {e}"))
+}
+
+pub fn write_settings(settings: &AppSettings) -> Result<(), String> {
+    if settings.scan_depth == 0 {
+        return Err("La profondeur de recherche doit être au moins 1".to_string());
+    }
+    let text = serde_json::to_string_pretty(settings)
+        .map_err(|e| format!("Failed to serialize settings: {e}"))?;
+    fs::write(settings_path(), text).map_err(|e| format!("Failed to write settings: {e}"))
+}
+
+fn push_recent(list: &mut Vec<String>, path: &str) {
+    list.retain(|p| p != path);
+    list.insert(0, path.to_string());
+    list.truncate(RECENT_LIMIT);
+}
+
+/// Ajoute un dossier en tête des dossiers récents
+pub fn remember_workspace(path: &str) -> Result<(), String> {
+    let mut settings = read_settings()?;
+    push_recent(&mut settings.recent_workspaces, path);
+    write_settings(&settings)
+}
+
+/// Ajoute un dépôt en tête des clones récents
+pub fn remember_clone(path: &str) -> Result<(), String> {
+    let mut settings = read_settings()?;
+    push_recent(&mut settings.recent_clones, path);
+    write_settings(&settings)
+}
+
+#[tauri::command]
+pub async fn get_settings() -> Result<AppSettings, String> {
+    read_settings()
+}
+
+#[tauri::command]
+pub async fn save_settings(settings: AppSettings) -> Result<(), String> {
+    write_settings(&settings)
+}
@@ -0,0 +1,847 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::fs;
+use std::io::{BufRead, BufReader, ErrorKind, Read};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+use sysinfo::{Pid, System};
+use tauri::AppHandle;
+
+use crate::autoscan::{ForwardCheck, PortForward, ProjectV3, Tunnel};
+use crate::known_hosts::{self, HostKeyState};
+use crate::profiles::{effective_project, remove_tunnel_key, resolve_tunnel_key, tunnel_keys_dir};
+use crate::projects_v3::{check_project_id, config_dir};
+use crate::secrets::{emit_redacted, serialize_redacted_opt};
+use crate::ssh_keys::check_private_key;
+
+/// Délai laissé à ssh pour établir le transfert
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(15);
+/// Intervalle entre deux vérifications d'un tunnel connecté
+const HEALTH_INTERVAL: Duration = Duration::from_secs(10);
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+const BACKOFF_BASE: Duration = Duration::from_secs(1);
+const BACKOFF_MAX: Duration = Duration::from_secs(60);
+/// Lignes de stderr gardées pour le message d'erreur
+const ERROR_TAIL_LINES: usize = 5;
+
+/// Erreurs ssh qu'une reconnexion ne corrigera pas
+const FATAL_ERRORS: &[&str] = &[
+    "Permission denied",
+    "Host key verification failed",
+    "REMOTE HOST IDENTIFICATION HAS CHANGED",
+];
+
+/// État d'un tunnel géré par l'application
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum TunnelState {
+    Connecting,
+    Connected,
+    Reconnecting,
+    /// Erreur définitive (authentification, clé d'hôte) : pas de nouvelle tentative
+    Failed,
+    Stopped,
+}
+
+/// Transfert d'un tunnel et résultat de sa dernière vérification
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ForwardStatus {
+    #[serde(flatten)]
+    pub forward: PortForward,
+    pub ready: bool,
+}
+
+/// Statut d'un tunnel, aussi payload de l'event tunnel:status
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TunnelStatus {
+    pub project_id: String,
+    pub state: TunnelState,
+    /// `user@host:port` ou alias ssh
+    pub destination: Option<String>,
+    pub forwards: Vec<ForwardStatus>,
+    pub pid: Option<u32>,
+    /// Tentatives de reconnexion depuis la dernière connexion réussie
+    pub attempt: u32,
+    pub connected_since: Option<String>,
+    /// Secondes avant la prochaine tentative
+    pub retry_in: Option<u64>,
+    #[serde(serialize_with = "serialize_redacted_opt")]
+    pub last_error: Option<String>,
+}
+
+impl TunnelStatus {
+    fn stopped(project_id: &str) -> Self {
+        Self {
+            project_id: project_id.to_string(),
+            state: TunnelState::Stopped,
+            destination: None,
+            forwards: Vec::new(),
+            pid: None,
+            attempt: 0,
+            connected_since: None,
+            retry_in: None,
+            last_error: None,
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        matches!(
+            self.state,
+            TunnelState::Connecting | TunnelState::Connected | TunnelState::Reconnecting
+        )
+    }
+}
+
+/// Paramètres ssh d'un tunnel, clé privée résolue
+#[derive(Debug, Clone, PartialEq)]
+pub struct TunnelSpec {
+    pub host: String,
+    pub user: String,
+    pub port: u16,
+    pub private_key: Option<String>,
+    /// Alias de ~/.ssh/config : remplace host, user, port et clé
+    pub ssh_alias: Option<String>,
+    pub proxy_jump: Vec<String>,
+    pub forwards: Vec<PortForward>,
+    /// Socket ControlMaster propre au projet : jamais partagé avec un autre tunnel
+    pub control_path: PathBuf,
+}
+
+impl TunnelSpec {
+    pub fn new(project_id: &str, tunnel: &Tunnel) -> Self {
+        Self {
+            host: tunnel.host.trim().to_string(),
+            user: tunnel.user.trim().to_string(),
+            port: if tunnel.port == 0 { 22 } else { tunnel.port },
+            private_key: Some(tunnel.private_key.trim())
+                .filter(|k| !k.is_empty())
+                .map(str::to_string),
+            ssh_alias: tunnel
+                .ssh_alias
+                .as_deref()
+                .map(str::trim)
+                .map(str::to_string),
+            proxy_jump: tunnel
+                .proxy_jump
+                .iter()
+                .map(|jump| jump.trim().to_string())
+                .filter(|jump| !jump.is_empty())
+                .collect(),
+            forwards: tunnel.forwards.clone(),
+            control_path: runtime_dir().join(format!("{project_id}.sock")),
+        }
+    }
+
+    /// Tunnel du projet avec le profil actif appliqué et la clé du coffre résolue
+    pub fn for_project(project: &ProjectV3) -> Result<Self, String> {
+        check_project_id(&project.id)?;
+        let mut effective = effective_project(project);
+        resolve_tunnel_key(&mut effective, &tunnel_keys_dir())?;
+        let tunnel = effective
+            .tunnel
+            .filter(|t| t.enabled)
+            .ok_or_else(|| format!("Aucun tunnel actif pour le projet {}", project.id))?;
+        if tunnel.ssh_alias.is_none() && tunnel.host.trim().is_empty() {
+            return Err(format!(
+                "Hôte du tunnel manquant pour le projet {}",
+                project.id
+            ));
+        }
+        if tunnel.ssh_alias.is_none() && !tunnel.private_key.trim().is_empty() {
+            check_private_key(tunnel.private_key.trim())?;
+        }
+        if tunnel.forwards.is_empty() {
+            return Err(format!(
+                "Aucun transfert de port pour le tunnel du projet {}",
+                project.id
+            ));
+        }
+        Ok(Self::new(&project.id, &tunnel))
+    }
+
+    pub fn destination(&self) -> String {
+        if let Some(alias) = &self.ssh_alias {
+            alias.clone()
+        } else if self.user.is_empty() {
+            format!("{}:{}", self.host, self.port)
+        } else {
+            format!("{}@{}:{}", self.user, self.host, self.port)
+        }
+    }
+
+    /// Port, clé et rebonds ; communs au tunnel et à la vérification de la clé d'hôte
+    pub fn connection_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if self.ssh_alias.is_none() {
+            args.extend(["-p".into(), self.port.to_string()]);
+            if let Some(key) = &self.private_key {
+                args.extend([
+                    "-i".into(),
+                    key.clone(),
+                    "-o".into(),
+                    "IdentitiesOnly=yes".into(),
+                ]);
+            }
+        }
+        if !self.proxy_jump.is_empty() {
+            args.extend(["-J".into(), self.proxy_jump.join(",")]);
+        }
+        args
+    }
+
+    /// Destination passée à ssh : alias ou `user@host`
+    pub fn target(&self) -> String {
+        match &self.ssh_alias {
+            Some(alias) => alias.clone(),
+            None if self.user.is_empty() => self.host.clone(),
+            None => format!("{}@{}", self.user, self.host),
+        }
+    }
+
+    pub fn ssh_args(&self) -> Vec<String> {
+        let mut args: Vec<String> = vec!["-N".into()];
+        for option in [
+            "ExitOnForwardFailure=yes",
+            "ServerAliveInterval=15",
+            "ServerAliveCountMax=3",
+            "BatchMode=yes",
+            "ControlMaster=auto",
+            "ControlPersist=no",
+        ] {
+            args.extend(["-o".into(), option.into()]);
+        }
+        args.extend(known_hosts::strict_args(&known_hosts::known_hosts_path()));
+        args.extend([
+            "-o".into(),
+            format!("ControlPath={}", self.control_path.display()),
+        ]);
+        args.extend(self.connection_args());
+        for forward in &self.forwards {
+            args.extend(forward.ssh_args());
+        }
+        args.push(self.target());
+        args
+    }
+}
+
+/// Tunnel en cours : processus ssh courant et demande d'arrêt
+struct TunnelHandle {
+    spec: TunnelSpec,
+    child: Mutex<Option<Child>>,
+    /// pid du dernier ssh lancé (0 avant le premier)
+    pid: AtomicU32,
+    stopped: AtomicBool,
+    status: Mutex<TunnelStatus>,
+}
+
+impl TunnelHandle {
+    fn stop(&self) {
+        self.stopped.store(true, Ordering::SeqCst);
+        self.kill_child();
+    }
+
+    fn is_stopped(&self) -> bool {
+        self.stopped.load(Ordering::SeqCst)
+    }
+
+    fn kill_child(&self) {
+        if let Some(mut child) = self.child.lock().unwrap_or_else(|e| e.into_inner()).take() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+
+    /// Code de sortie de ssh s'il s'est terminé
+    fn exited(&self) -> Option<Option<i32>> {
+        let mut guard = self.child.lock().unwrap_or_else(|e| e.into_inner());
+        let status = guard.as_mut().map(|child| child.try_wait())?;
+        match status {
+            Ok(Some(status)) => {
+                guard.take();
+                Some(status.code())
+            }
+            Ok(None) => None,
+            Err(_) => Some(None),
+        }
+    }
+
+    fn status(&self) -> TunnelStatus {
+        self.status
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    fn update(&self, change: impl FnOnce(&mut TunnelStatus)) {
+        let status = {
+            let mut status = self.status.lock().unwrap_or_else(|e| e.into_inner());
+            change(&mut status);
+            status.clone()
+        };
+        if let Some(app) = APP.get() {
+            emit_redacted(app, "tunnel:status", &status);
+        }
+    }
+}
+
+/// Tunnels lancés par l'application, par projet
+static TUNNELS: Mutex<BTreeMap<String, Arc<TunnelHandle>>> = Mutex::new(BTreeMap::new());
+
+/// Handle utilisé pour émettre tunnel:status depuis les threads de supervision
+static APP: OnceLock<AppHandle> = OnceLock::new();
+
+fn runtime_dir() -> PathBuf {
+    config_dir().join("runtime").join("ssh")
+}
+
+fn pid_file(project_id: &str) -> PathBuf {
+    runtime_dir().join(format!("{project_id}.pid"))
+}
+
+/// Délai avant la tentative `attempt` (1, 2, 4... secondes, plafonné)
+pub fn backoff(attempt: u32) -> Duration {
+    let factor = 1u32 << attempt.saturating_sub(1).min(16);
+    (BACKOFF_BASE * factor).min(BACKOFF_MAX)
+}
+
+pub fn is_fatal(error: &str) -> bool {
+    FATAL_ERRORS.iter().any(|e| error.contains(e))
+}
+
+/// Adresse à laquelle joindre un transfert local (`0.0.0.0` se joint par la boucle locale)
+fn forward_addr(forward: &PortForward, port: u16) -> SocketAddr {
+    let ip = forward
+        .bind_address
+        .parse::<IpAddr>()
+        .ok()
+        .filter(|ip| !ip.is_unspecified())
+        .unwrap_or(IpAddr::V4(Ipv4Addr::LOCALHOST));
+    SocketAddr::new(ip, port)
+}
+
+fn port_in_use(addr: SocketAddr) -> bool {
+    TcpListener::bind(addr).is_err()
+}
+
+/// Vérifie que le port local transfère réellement vers l'hôte distant
+///
+/// ssh accepte la connexion locale puis la ferme aussitôt quand l'ouverture du
+/// canal distant échoue ; une connexion qui reste ouverte est considérée active.
+pub fn probe_forward(addr: SocketAddr) -> bool {
+    let Ok(mut stream) = TcpStream::connect_timeout(&addr, Duration::from_secs(2)) else {
+        return false;
+    };
+    let _ = stream.set_read_timeout(Some(Duration::from_millis(1500)));
+    let mut buf = [0u8; 1];
+    match stream.read(&mut buf) {
+        Ok(0) => false,
+        Ok(_) => true,
+        Err(e) => matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut),
+    }
+}
+
+/// Vérifie un transfert selon son `check` ; un transfert remote n'est pas vérifiable ici
+pub fn forward_ready(forward: &PortForward) -> bool {
+    let Some(port) = forward.listen_port() else {
+        return true;
+    };
+    let addr = forward_addr(forward, port);
+    match forward.check {
+        ForwardCheck::Forward => probe_forward(addr),
+        ForwardCheck::Listen => TcpStream::connect_timeout(&addr, Duration::from_secs(2)).is_ok(),
+        ForwardCheck::None => true,
+    }
+}
+
+fn forward_label(forward: &PortForward) -> String {
+    if forward.name.is_empty() {
+        forward.local_port.to_string()
+    } else {
+        format!("{} ({})", forward.name, forward.local_port)
+    }
+}
+
+/// Vérifie tous les transferts et publie leur état ; renvoie ceux qui ne sont pas prêts
+fn check_forwards(handle: &TunnelHandle) -> Vec<String> {
+    let ready: Vec<bool> = handle.spec.forwards.iter().map(forward_ready).collect();
+    let failing = handle
+        .spec
+        .forwards
+        .iter()
+        .zip(&ready)
+        .filter(|(_, ready)| !**ready)
+        .map(|(forward, _)| forward_label(forward))
+        .collect();
+    let previous: Vec<bool> = handle.status().forwards.iter().map(|f| f.ready).collect();
+    if previous != ready {
+        handle.update(|s| {
+            for (status, ready) in s.forwards.iter_mut().zip(ready) {
+                status.ready = ready;
+            }
+        });
+    }
+    failing
+}
+
+/// Arrête un ssh laissé par une session précédente de l'application pour ce projet
+///
+/// Seul un processus ssh utilisant le socket de contrôle du projet est visé.
+fn kill_leftover(project_id: &str, spec: &TunnelSpec) {
+    let path = pid_file(project_id);
+    let Some(pid) = fs::read_to_string(&path)
+        .ok()
+        .and_then(|text| text.trim().parse::<u32>().ok())
+    else {
+        return;
+    };
+    let mut system = System::new();
+    system.refresh_processes();
+    if let Some(process) = system.process(Pid::from_u32(pid)) {
+        let control = spec.control_path.to_string_lossy();
+        if process.name().contains("ssh") && process.cmd().join(" ").contains(control.as_ref()) {
+            process.kill();
+        }
+    }
+    let _ = fs::remove_file(path);
+    let _ = fs::remove_file(&spec.control_path);
+}
+
+/// Lance ssh et garde la fin de son stderr
+fn spawn_ssh(
+    handle: &TunnelHandle,
+    project_id: &str,
+) -> Result<Arc<Mutex<VecDeque<String>>>, String> {
+    let spec = &handle.spec;
+    for forward in &spec.forwards {
+        if let Some(port) = forward.listen_port() {
+            if port_in_use(forward_addr(forward, port)) {
+                return Err(format!(
+                    "Le port local {} ({}) est déjà utilisé",
+                    port,
+                    forward_label(forward)
+                ));
+            }
+        }
+    }
+    let _ = fs::remove_file(&spec.control_path);
+
+    let mut child = Command::new("ssh")
+        .args(spec.ssh_args())
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to execute ssh: {}", e))?;
+
+    let tail = Arc::new(Mutex::new(VecDeque::new()));
+    if let Some(stderr) = child.stderr.take() {
+        let tail = tail.clone();
+        thread::spawn(move || {
+            for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                let mut tail = tail.lock().unwrap_or_else(|e| e.into_inner());
+                if tail.len() == ERROR_TAIL_LINES {
+                    tail.pop_front();
+                }
+                tail.push_back(line);
+            }
+        });
+    }
+
+    let pid = child.id();
+    handle.pid.store(pid, Ordering::SeqCst);
+    let _ = fs::write(pid_file(project_id), pid.to_string());
+    *handle.child.lock().unwrap_or_else(|e| e.into_inner()) = Some(child);
+    handle.update(|s| s.pid = Some(pid));
+    Ok(tail)
+}
+
+/// Suit un processus ssh jusqu'à sa fin ; renvoie s'il a été connecté et l'erreur
+fn watch(handle: &TunnelHandle) -> (bool, Option<String>) {
+    let started = Instant::now();
+    let mut connected = false;
+    let mut last_probe = Instant::now();
+
+    loop {
+        if handle.is_stopped() {
+            return (connected, None);
+        }
+        if let Some(code) = handle.exited() {
+            let error = match code {
+                Some(code) => format!("ssh s'est arrêté (code {})", code),
+                None => "ssh s'est arrêté".to_string(),
+            };
+            return (connected, Some(error));
+        }
+
+        if !connected {
+            let failing = check_forwards(handle);
+            if failing.is_empty() {
+                connected = true;
+                last_probe = Instant::now();
+                handle.update(|s| {
+                    s.state = TunnelState::Connected;
+                    s.attempt = 0;
+                    s.retry_in = None;
+                    s.last_error = None;
+                    s.connected_since = Some(chrono::Utc::now().to_rfc3339());
+                });
+            } else if started.elapsed() > CONNECT_TIMEOUT {
+                handle.kill_child();
+                return (
+                    false,
+                    Some(format!("Transfert(s) non prêt(s): {}", failing.join(", "))),
+                );
+            }
+        } else if last_probe.elapsed() > HEALTH_INTERVAL {
+            last_probe = Instant::now();
+            let failing = check_forwards(handle);
+            if !failing.is_empty() {
+                handle.kill_child();
+                return (
+                    true,
+                    Some(format!("Transfert interrompu: {}", failing.join(", "))),
+                );
+            }
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Attend `delay` en restant interruptible par un arrêt
+fn wait_unless_stopped(handle: &TunnelHandle, delay: Duration) {
+    let until = Instant::now() + delay;
+    while !handle.is_stopped() && Instant::now() < until {
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Supprime le fichier pid et le socket de contrôle s'ils appartiennent encore au ssh
+/// de ce handle : un tunnel relancé entre-temps les a réécrits pour son propre ssh
+fn remove_runtime_files(project_id: &str, handle: &TunnelHandle) {
+    let path = pid_file(project_id);
+    let owner = fs::read_to_string(&path)
+        .ok()
+        .and_then(|text| text.trim().parse::<u32>().ok());
+    if owner.is_none() || owner != Some(handle.pid.load(Ordering::SeqCst)) {
+        return;
+    }
+    let _ = fs::remove_file(path);
+    let _ = fs::remove_file(&handle.spec.control_path);
+}
+
+/// Boucle de supervision : un ssh par tentative, reconnexion avec backoff exponentiel
+fn supervise(project_id: String, handle: Arc<TunnelHandle>) {
+    let mut attempt = 0;
+    while !handle.is_stopped() {
+        let (connected, error) = match spawn_ssh(&handle, &project_id) {
+            Ok(tail) => {
+                let (connected, error) = watch(&handle);
+                let stderr = tail
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .iter()
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                let error = match (error, stderr.trim()) {
+                    (Some(e), "") => Some(e),
+                    (Some(e), stderr) => Some(format!("{}: {}", e, stderr)),
+                    (None, _) => None,
+                };
+                (connected, error)
+            }
+            Err(e) => (false, Some(e)),
+        };
+        if handle.is_stopped() {
+            break;
+        }
+
+        if connected {
+            attempt = 0;
+        }
+        if !connected && error.as_deref().is_some_and(is_fatal) {
+            // Clé d'hôte inconnue ou changée : diagnostic avec l'empreinte présentée
+            let error = match error {
+                Some(e) if known_hosts::is_host_key_error(&e) => {
+                    let check = known_hosts::check_host_key(&project_id, &handle.spec);
+                    if let (Ok(check), Some(app)) = (&check, APP.get()) {
+                        if check.state != HostKeyState::Pinned {
+                            emit_redacted(app, "tunnel:host-key", check);
+                        }
+                    }
+                    Some(check.map(|c| c.message).unwrap_or(e))
+                }
+                error => error,
+            };
+            handle.update(|s| {
+                s.state = TunnelState::Failed;
+                s.pid = None;
+                s.retry_in = None;
+                s.last_error = error;
+            });
+            remove_runtime_files(&project_id, &handle);
+            return;
+        }
+
+        attempt += 1;
+        let delay = backoff(attempt);
+        handle.update(|s| {
+            s.state = TunnelState::Reconnecting;
+            s.pid = None;
+            s.forwards.iter_mut().for_each(|f| f.ready = false);
+            s.attempt = attempt;
+            s.connected_since = None;
+            s.retry_in = Some(delay.as_secs());
+            s.last_error = error;
+        });
+        wait_unless_stopped(&handle, delay);
+        handle.update(|s| s.retry_in = None);
+    }
+
+    handle.kill_child();
+    remove_runtime_files(&project_id, &handle);
+    handle.update(|s| {
+        s.state = TunnelState::Stopped;
+        s.pid = None;
+        s.retry_in = None;
+        s.connected_since = None;
+    });
+}
+
+/// Enregistre le handle de l'application pour les events tunnel:status
+pub fn init_tunnels(app: AppHandle) {
+    let _ = APP.set(app);
+}
+
+/// Démarre le tunnel d'un projet et attend la première connexion (ou son échec)
+pub fn start_project_tunnel(project: &ProjectV3) -> Result<TunnelStatus, String> {
+    // Verrou gardé de la vérification à l'enregistrement : deux démarrages
+    // simultanés ne lancent pas deux ssh sur les mêmes ports
+    let mut tunnels = TUNNELS.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(handle) = tunnels.get(&project.id) {
+        if handle.status().is_running() {
+            return Ok(handle.status());
+        }
+    }
+
+    let spec = TunnelSpec::for_project(project)?;
+    fs::create_dir_all(runtime_dir())
+        .map_err(|e| format!("Failed to create ssh runtime directory: {e}"))?;
+    kill_leftover(&project.id, &spec);
+
+    let mut status = TunnelStatus::stopped(&project.id);
+    status.state = TunnelState::Connecting;
+    status.destination = Some(spec.destination());
+    status.forwards = spec
+        .forwards
+        .iter()
+        .map(|forward| ForwardStatus {
+            forward: forward.clone(),
+            ready: false,
+        })
+        .collect();
+    let handle = Arc::new(TunnelHandle {
+        spec,
+        child: Mutex::new(None),
+        pid: AtomicU32::new(0),
+        stopped: AtomicBool::new(false),
+        status: Mutex::new(status),
+    });
+    tunnels.insert(project.id.clone(), handle.clone());
+    drop(tunnels);
+
+    let id = project.id.clone();
+    let supervised = handle.clone();
+    thread::spawn(move || supervise(id, supervised));
+
+    let deadline = Instant::now() + CONNECT_TIMEOUT + Duration::from_secs(1);
+    while handle.status().state == TunnelState::Connecting && Instant::now() < deadline {
+        thread::sleep(POLL_INTERVAL);
+    }
+    let status = handle.status();
+    match status.state {
+        TunnelState::Failed => Err(status
+            .last_error
+            .unwrap_or_else(|| "Échec du tunnel SSH".to_string())),
+        _ => Ok(status),
+    }
+}
+
+/// Arrête le tunnel d'un projet, sans toucher à ceux des autres ; `false` s'il ne tournait pas
+pub fn stop_project_tunnel(project_id: &str) -> bool {
+    let handle = TUNNELS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(project_id);
+    let Some(handle) = handle else {
+        return false;
+    };
+    let running = handle.status().is_running();
+    handle.stop();
+    remove_tunnel_key(project_id);
+    running
+}
+
+/// Supprime la clé tirée du coffre après un usage ponctuel (test, empreinte),
+/// sauf si le tunnel du projet tourne et en a encore besoin
+pub(crate) fn release_tunnel_key(project_id: &str) {
+    if !project_tunnel_status(project_id).is_running() {
+        remove_tunnel_key(project_id);
+    }
+}
+
+pub fn project_tunnel_status(project_id: &str) -> TunnelStatus {
+    TUNNELS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(project_id)
+        .map(|handle| handle.status())
+        .unwrap_or_else(|| TunnelStatus::stopped(project_id))
+}
+
+/// Statut du tunnel d'un projet
+#[tauri::command]
+pub async fn get_tunnel_status(project_id: String) -> Result<TunnelStatus, String> {
+    Ok(project_tunnel_status(&project_id))
+}
+
+/// Statut de tous les tunnels lancés par l'application
+#[tauri::command]
+pub async fn list_tunnels() -> Result<Vec<TunnelStatus>, String> {
+    Ok(TUNNELS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .values()
+        .map(|handle| handle.status())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::autoscan::ForwardKind;
+
+    fn tunnel() -> Tunnel {
+        Tunnel {
+            enabled: true,
+            host: "db.example.com".into(),
+            user: "deploy".into(),
+            port: 2222,
+            private_key: "/keys/id_ed25519".into(),
+            ssh_alias: None,
+            proxy_jump: Vec::new(),
+            forwards: vec![PortForward::local("mongo", 27018, 27017)],
+        }
+    }
+
+    #[test]
+    fn test_ssh_args_forward_only_the_project_ports() {
+        let spec = TunnelSpec::new("shop", &tunnel());
+        let args = spec.ssh_args().join(" ");
+
+        assert!(args.starts_with("-N "));
+        assert!(args.contains("-o ExitOnForwardFailure=yes"));
+        assert!(args.contains("-o ControlMaster=auto"));
+        assert!(args.contains(&format!("ControlPath={}", spec.control_path.display())));
+        assert!(spec.control_path.ends_with("shop.sock"));
+        assert!(args.contains("-p 2222 -i /keys/id_ed25519"));
+        assert!(args.ends_with("-L 127.0.0.1:27018:127.0.0.1:27017 deploy@db.example.com"));
+        assert!(args.contains("-o StrictHostKeyChecking=yes"));
+        assert!(args.contains(&format!(
+            "UserKnownHostsFile={}",
+            known_hosts::known_hosts_path().display()
+        )));
+        assert_eq!(spec.destination(), "deploy@db.example.com:2222");
+
+        assert_eq!(backoff(1), Duration::from_secs(1));
+        assert_eq!(backoff(4), Duration::from_secs(8));
+        assert_eq!(backoff(40), BACKOFF_MAX);
+        assert!(is_fatal(
+            "deploy@db.example.com: Permission denied (publickey)."
+        ));
+        assert!(!is_fatal("Connection refused"));
+    }
+
+    #[test]
+    fn test_ssh_args_with_alias_jumps_and_several_forwards() {
+        let mut tunnel = tunnel();
+        tunnel.ssh_alias = Some("prod-db".into());
+        tunnel.proxy_jump = vec!["ops@bastion:2200".into(), "jump2".into()];
+        tunnel.forwards.push(PortForward {
+            name: "socks".into(),
+            kind: ForwardKind::Dynamic,
+            bind_address: "127.0.0.1".into(),
+            local_port: 1080,
+            remote_host: String::new(),
+            remote_port: 0,
+            check: ForwardCheck::Listen,
+        });
+        tunnel.forwards.push(PortForward {
+            name: "webhooks".into(),
+            kind: ForwardKind::Remote,
+            bind_address: "127.0.0.1".into(),
+            local_port: 3010,
+            remote_host: "127.0.0.1".into(),
+            remote_port: 9010,
+            check: ForwardCheck::Forward,
+        });
+        let spec = TunnelSpec::new("shop", &tunnel);
+        let args = spec.ssh_args().join(" ");
+
+        assert!(!args.contains("-p 2222"));
+        assert!(!args.contains("-i /keys"));
+        assert!(args.ends_with(
+            "-J ops@bastion:2200,jump2 -L 127.0.0.1:27018:127.0.0.1:27017 \
+             -D 127.0.0.1:1080 -R 127.0.0.1:9010:127.0.0.1:3010 prod-db"
+        ));
+        assert_eq!(spec.destination(), "prod-db");
+    }
+
+    #[test]
+    fn test_legacy_mongo_ports_migrate_to_a_forward() {
+        let tunnel: Tunnel = serde_json::from_str(
+            r#"{"enabled":true,"host":"h","user":"u","port":22,"privateKey":"","localMongo":27019,"remoteMongo":27017}"#,
+        )
+        .unwrap();
+        assert_eq!(tunnel.forwards, [PortForward::local("mongo", 27019, 27017)]);
+
+        let saved = serde_json::to_value(&tunnel).unwrap();
+        assert!(saved.get("localMongo").is_none());
+        assert_eq!(saved["forwards"][0]["localPort"], 27019);
+    }
+
+    #[test]
+    fn test_probe_detects_closed_forwards() {
+        // Un ssh dont le canal distant échoue ferme aussitôt la connexion locale
+        let closing = TcpListener::bind("127.0.0.1:0").unwrap();
+        let closing_addr = closing.local_addr().unwrap();
+        thread::spawn(move || {
+            for stream in closing.incoming() {
+                drop(stream);
+            }
+        });
+
+        let open = TcpListener::bind("127.0.0.1:0").unwrap();
+        let open_addr = open.local_addr().unwrap();
+        thread::spawn(move || {
+            let held: Vec<_> = open.incoming().take(1).collect();
+            thread::sleep(Duration::from_secs(3));
+            drop(held);
+        });
+
+        assert!(!probe_forward(closing_addr));
+        assert!(probe_forward(open_addr));
+
+        let unused = TcpListener::bind("127.0.0.1:0").unwrap();
+        let unused_addr = unused.local_addr().unwrap();
+        drop(unused);
+        assert!(!probe_forward(unused_addr));
+    }
+}
@@ -0,0 +1,425 @@
+use chrono::Utc;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::autoscan::{AppService, Ports, ProjectCommands, ProjectV3};
+use crate::detectors::{Detection, DetectorRegistry, ServiceRole};
+use crate::ignore_rules::IgnoreRules;
+use crate::package_json::{read_package_json, script_commands, PackageJson};
+use crate::ports::{allocate_port, resolve_port};
+use crate::projects_v3::project_id_from_name;
+
+/// Profondeur maximale des membres d'un workspace (`apps/web`, `packages/ui/core`...)
+const MAX_MEMBER_DEPTH: usize = 3;
+
+/// Dossiers de membres par défaut pour Nx / Turborepo sans déclaration de workspaces
+const DEFAULT_MEMBER_PATTERNS: &[&str] = &["apps/*", "packages/*", "libs/*"];
+
+/// App exécutable d'un monorepo
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct WorkspaceApp {
+    pub name: String,
+    pub path: String,
+    /// Rôle déduit de la stack détectée
+    #[serde(default)]
+    pub role: Option<ServiceRole>,
+    #[serde(default)]
+    pub stack: Option<String>,
+    #[serde(default)]
+    pub confidence: f32,
+    #[serde(rename = "devCommand")]
+    #[serde(default)]
+    pub dev_command: Option<String>,
+    #[serde(default)]
+    pub port: Option<u16>,
+    /// Paquets du workspace à construire avant de lancer l'app
+    #[serde(rename = "dependsOn")]
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+}
+
+/// Paquet partagé (non exécutable) d'un monorepo
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct WorkspacePackage {
+    pub name: String,
+    pub path: String,
+    #[serde(rename = "buildCommand")]
+    #[serde(default)]
+    pub build_command: Option<String>,
+}
+
+/// Structure d'un monorepo (pnpm, npm/yarn workspaces, Turborepo, Nx)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct Workspace {
+    /// Outils détectés : "pnpm", "workspaces", "turbo", "nx"
+    pub tools: Vec<String>,
+    pub apps: Vec<WorkspaceApp>,
+    pub packages: Vec<WorkspacePackage>,
+}
+
+impl Workspace {
+    /// App la plus probable pour un rôle
+    pub fn best_app(&self, role: ServiceRole) -> Option<&WorkspaceApp> {
+        self.apps
+            .iter()
+            .filter(|app| app.role == Some(role))
+            .max_by(|a, b| a.confidence.total_cmp(&b.confidence))
+    }
+}
+
+#[derive(Deserialize)]
+struct PnpmWorkspace {
+    #[serde(default)]
+    packages: Vec<String>,
+}
+
+/// Motifs de membres déclarés par pnpm-workspace.yaml et package.json `workspaces`
+fn member_patterns(
+    root: &Path,
+    package: Option<&PackageJson>,
+) -> Result<(Vec<String>, Vec<String>), String> {
+    let mut tools = Vec::new();
+    let mut patterns = Vec::new();
+
+    let pnpm_file = root.join("pnpm-workspace.yaml");
+    if pnpm_file.exists() {
+        let text = fs::read_to_string(&pnpm_file)
+            .map_err(|e| format!("Failed to read pnpm-workspace.yaml: {}", e))?;
+        let pnpm: PnpmWorkspace = serde_yaml::from_str(&text)
+            .map_err(|e| format!("Invalid pnpm-workspace.yaml: {}", e))?;
+        tools.push("pnpm".to_string());
+        patterns.extend(pnpm.packages);
+    }
+
+    if let Some(declared) = package.and_then(|p| p.workspaces.as_ref()) {
+        tools.push("workspaces".to_string());
+        patterns.extend(declared.patterns().iter().cloned());
+    }
+
+    for (file, tool) in [("turbo.json", "turbo"), ("nx.json", "nx")] {
+        if root.join(file).exists() {
+            tools.push(tool.to_string());
+        }
+    }
+
+    if patterns.is_empty() && !tools.is_empty() {
+        patterns.extend(DEFAULT_MEMBER_PATTERNS.iter().map(|p| p.to_string()));
+    }
+
+    Ok((tools, patterns))
+}
+
+fn build_globset(patterns: &[&str]) -> Result<GlobSet, String> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let pattern = pattern.trim_start_matches("./").trim_end_matches('/');
+        builder.add(
+            Glob::new(pattern)
+                .map_err(|e| format!("Motif de workspace invalide '{}': {}", pattern, e))?,
+        );
+    }
+    builder
+        .build()
+        .map_err(|e| format!("Motifs de workspace invalides: {}", e))
+}
+
+/// Dossiers membres : correspondent à un motif (hors `!exclusions`) et contiennent
+/// un package.json ou un project.json (Nx)
+fn find_members(root: &Path, patterns: &[String]) -> Result<Vec<PathBuf>, String> {
+    let (excludes, includes): (Vec<&str>, Vec<&str>) = patterns
+        .iter()
+        .map(String::as_str)
+        .partition(|p| p.starts_with('!'));
+    let includes = build_globset(&includes)?;
+    let excludes = build_globset(&excludes.iter().map(|p| &p[1..]).collect::<Vec<_>>())?;
+
+    let rules = IgnoreRules::for_root(root);
+    let mut members = Vec::new();
+    let mut stack = vec![(root.to_path_buf(), 0)];
+    while let Some((dir, depth)) = stack.pop() {
+        if depth >= MAX_MEMBER_DEPTH {
+            continue;
+        }
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for path in entries.flatten().map(|e| e.path()).filter(|p| p.is_dir()) {
+            let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            if rules.is_ignored(&path) || name.starts_with('.') {
+                continue;
+            }
+            let Ok(relative) = path.strip_prefix(root) else {
+                continue;
+            };
+            let relative = relative.to_string_lossy().replace('\\', "/");
+            let is_project =
+                path.join("package.json").exists() || path.join("project.json").exists();
+            if is_project && includes.is_match(&relative) && !excludes.is_match(&relative) {
+                members.push(path.clone());
+            }
+            stack.push((path, depth + 1));
+        }
+    }
+
+    members.sort();
+    Ok(members)
+}
+
+/// Meilleure détection tous rôles confondus
+fn best_detection(registry: &DetectorRegistry, dir: &Path) -> Option<Detection> {
+    [ServiceRole::Backend, ServiceRole::Frontend]
+        .into_iter()
+        .filter_map(|role| registry.best(dir, role))
+        .max_by(|a, b| a.confidence.total_cmp(&b.confidence))
+}
+
+/// Cible `serve` d'un project.json Nx
+fn nx_serve_command(dir: &Path, name: &str) -> Option<String> {
+    let text = fs::read_to_string(dir.join("project.json")).ok()?;
+    let project: serde_json::Value = serde_json::from_str(&text).ok()?;
+    project
+        .get("targets")?
+        .get("serve")
+        .map(|_| format!("npx nx serve {}", name))
+}
+
+/// Détecte un monorepo et énumère ses apps et paquets partagés
+pub fn detect_workspace(root: &Path) -> Result<Option<Workspace>, String> {
+    let root_package = read_package_json(root).ok().flatten();
+    let (tools, patterns) = member_patterns(root, root_package.as_ref())?;
+    if tools.is_empty() {
+        return Ok(None);
+    }
+
+    let registry = DetectorRegistry::with_defaults();
+    let mut members = Vec::new();
+    for dir in find_members(root, &patterns)? {
+        let package = read_package_json(&dir).ok().flatten();
+        let name = package
+            .as_ref()
+            .and_then(|p| p.name.clone())
+            .or_else(|| dir.file_name().and_then(|n| n.to_str()).map(str::to_string))
+            .unwrap_or_default();
+        members.push((dir, name, package));
+    }
+    let member_names: BTreeSet<&str> = members.iter().map(|(_, name, _)| name.as_str()).collect();
+
+    let mut workspace = Workspace {
+        tools,
+        ..Default::default()
+    };
+    for (dir, name, package) in &members {
+        let path = dir.to_string_lossy().to_string();
+        let commands = package.as_ref().map(|p| script_commands(dir, p));
+        let detection = best_detection(&registry, dir);
+        let dev_command = detection
+            .as_ref()
+            .map(|d| d.dev_command.clone())
+            .or_else(|| {
+                commands
+                    .as_ref()
+                    .and_then(|c| c.dev.clone().or(c.start.clone()))
+            })
+            .or_else(|| nx_serve_command(dir, name));
+
+        let Some(dev_command) = dev_command else {
+            workspace.packages.push(WorkspacePackage {
+                name: name.clone(),
+                path,
+                build_command: commands.and_then(|c| c.build),
+            });
+            continue;
+        };
+
+        let depends_on = package
+            .as_ref()
+            .map(|p| {
+                p.dependencies
+                    .keys()
+                    .chain(p.dev_dependencies.keys())
+                    .filter(|dep| *dep != name && member_names.contains(dep.as_str()))
+                    .cloned()
+                    .collect::<BTreeSet<_>>()
+                    .into_iter()
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        workspace.apps.push(WorkspaceApp {
+            name: name.clone(),
+            path,
+            role: detection.as_ref().map(|d| d.role),
+            stack: detection.as_ref().map(|d| d.stack.clone()),
+            confidence: detection.as_ref().map(|d| d.confidence).unwrap_or(0.0),
+            dev_command: Some(dev_command),
+            port: detection
+                .as_ref()
+                .and_then(|d| resolve_port(dir, &d.port_sources))
+                .map(|p| p.port),
+            depends_on,
+        });
+    }
+
+    Ok(Some(workspace))
+}
+
+/// Services des apps exécutables : le port détecté s'il est libre parmi les apps,
+/// sinon le premier port disponible après 3000
+fn app_services(workspace: &Workspace) -> BTreeMap<String, AppService> {
+    let mut reserved: BTreeSet<u16> = BTreeSet::new();
+    let mut apps = BTreeMap::new();
+    for app in &workspace.apps {
+        let Some(dev_command) = app.dev_command.clone() else {
+            continue;
+        };
+        let port = app
+            .port
+            .filter(|port| reserved.insert(*port))
+            .or_else(|| allocate_port(3000, &mut reserved));
+        let Some(port) = port else {
+            continue;
+        };
+        apps.insert(
+            app.name.clone(),
+            AppService {
+                path: app.path.clone(),
+                port,
+                dev_command,
+                depends_on: app.depends_on.clone(),
+            },
+        );
+    }
+    apps
+}
+
+/// Projet V3 d'un monorepo : chaque app exécutable devient un service, l'app
+/// backend et l'app frontend les plus probables occupent les services principaux
+pub fn project_from_workspace(root: &Path, workspace: Workspace) -> ProjectV3 {
+    let dir_name = root
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("project")
+        .to_string();
+    let apps = app_services(&workspace);
+    let backend = workspace.best_app(ServiceRole::Backend).cloned();
+    let frontend = workspace.best_app(ServiceRole::Frontend).cloned();
+    let app_port = |app: &Option<WorkspaceApp>| {
+        app.as_ref()
+            .and_then(|a| apps.get(&a.name))
+            .map(|service| service.port)
+    };
+
+    ProjectV3 {
+        id: project_id_from_name(&dir_name),
+        name: dir_name,
+        root_path: root.to_string_lossy().to_string(),
+        backend_path: backend.as_ref().map(|a| a.path.clone()).unwrap_or_default(),
+        frontend_path: frontend
+            .as_ref()
+            .map(|a| a.path.clone())
+            .unwrap_or_default(),
+        backend_type: backend
+            .as_ref()
+            .and_then(|a| a.stack.clone())
+            .filter(|stack| stack == "payload" || stack == "directus"),
+        ports: Ports {
+            backend: app_port(&backend).unwrap_or(3010),
+            frontend: app_port(&frontend).unwrap_or(3000),
+            ..Default::default()
+        },
+        commands: Some(ProjectCommands {
+            backend: backend.and_then(|a| a.dev_command),
+            frontend: frontend.and_then(|a| a.dev_command),
+            ..Default::default()
+        }),
+        created_at: Utc::now().to_rfc3339(),
+        enabled: true,
+        apps,
+        workspace: Some(workspace),
+        ..Default::default()
+    }
+}
+
+/// Apps et paquets d'un monorepo
+#[tauri::command]
+pub async fn scan_workspace(root_path: String) -> Result<Option<Workspace>, String> {
+    detect_workspace(Path::new(&root_path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pnpm_turbo_monorepo() {
+        let root = std::env::temp_dir().join(format!("gmdev-ws-{}", uuid::Uuid::new_v4()));
+        let files = [
+            (
+                "pnpm-workspace.yaml",
+                "packages:\n  - 'apps/*'\n  - 'packages/*'\n  - '!packages/legacy'\n",
+            ),
+            ("turbo.json", "{}"),
+            ("package.json", r#"{"name": "mono", "private": true}"#),
+            (
+                "apps/api/package.json",
+                r#"{"name": "api", "dependencies": {"payload": "3.0.0", "@mono/ui": "workspace:*"}, "scripts": {"dev": "payload dev"}}"#,
+            ),
+            (
+                "apps/web/package.json",
+                r#"{"name": "web", "dependencies": {"next": "15.0.0", "@mono/ui": "workspace:*"}, "scripts": {"dev": "next dev"}}"#,
+            ),
+            (
+                "packages/ui/package.json",
+                r#"{"name": "@mono/ui", "scripts": {"build": "tsc"}}"#,
+            ),
+            ("packages/legacy/package.json", r#"{"name": "legacy"}"#),
+        ];
+        for (name, content) in files {
+            let path = root.join(name);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+
+        let workspace = detect_workspace(&root).unwrap().unwrap();
+        assert_eq!(workspace.tools, vec!["pnpm", "turbo"]);
+        assert_eq!(
+            workspace
+                .apps
+                .iter()
+                .map(|a| a.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["api", "web"]
+        );
+        assert_eq!(
+            workspace.best_app(ServiceRole::Backend).unwrap().name,
+            "api"
+        );
+        assert_eq!(
+            workspace.best_app(ServiceRole::Frontend).unwrap().name,
+            "web"
+        );
+        assert_eq!(workspace.apps[1].dev_command.as_deref(), Some("pnpm dev"));
+        assert_eq!(workspace.apps[0].depends_on, vec!["@mono/ui"]);
+        assert_eq!(workspace.packages.len(), 1);
+        assert_eq!(
+            workspace.packages[0].build_command.as_deref(),
+            Some("pnpm build")
+        );
+
+        let project = project_from_workspace(&root, workspace);
+        assert_eq!(
+            project.apps.keys().map(String::as_str).collect::<Vec<_>>(),
+            vec!["api", "web"]
+        );
+        assert_ne!(project.apps["api"].port, project.apps["web"].port);
+        assert_eq!(project.ports.backend, project.apps["api"].port);
+        assert_eq!(project.ports.frontend, project.apps["web"].port);
+        assert_eq!(project.apps["web"].dev_command, "pnpm dev");
+        assert_eq!(project.apps["web"].depends_on, vec!["@mono/ui"]);
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
@@ -68,8 +68,12 @@ export default function AutoScanSummary({ scan }: AutoScanSummaryProps) {
             </div>
             {hasBackend && (
               <div className="flex items-center gap-2">
-                <Badge variant="secondary" className="text-xs">
-                  Port: {scan.backend_port ?? 3010}
+                <Badge
+                  variant="secondary"
+                  className="text-xs"
+                  title={scan.backend_port_source ?? "Port non trouvé, valeur par défaut"}
+                >
+                  Port: {scan.backend_port ?? "3010 (défaut)"}
                 </Badge>
                 {scan.backend_start && (
                   <span className="text-xs text-gray-400 font-mono">
@@ -88,8 +92,12 @@ export default function AutoScanSummary({ scan }: AutoScanSummaryProps) {
             </div>
             {hasFrontend && (
               <div className="flex items-center gap-2">
-                <Badge variant="secondary" className="text-xs">
-                  Port: {scan.frontend_port ?? 3000}
+                <Badge
+                  variant="secondary"
+                  className="text-xs"
+                  title={scan.frontend_port_source ?? "Port non trouvé, valeur par défaut"}
+                >
+                  Port: {scan.frontend_port ?? "3000 (défaut)"}
                 </Badge>
                 {scan.frontend_start && (
                   <span className="text-xs text-gray-400 font-mono">
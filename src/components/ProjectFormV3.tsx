@@ -10,7 +10,31 @@ import {
 import { Button } from "@/components/ui/button";
 import { Input } from "@/components/ui/input";
 import { Label } from "@/components/ui/label";
-import { ProjectV3 } from "@/types/ProjectV3";
+import { PortForward, ProjectV3, TunnelConfig } from "@/types/ProjectV3";
+
+const DEFAULT_MONGO_FORWARD: PortForward = {
+  name: "mongo",
+  localPort: 27017,
+  remotePort: 27017,
+};
+
+// Transfert MongoDB édité par le formulaire (nommé "mongo", sinon le premier)
+function mongoForwardIndex(tunnel: TunnelConfig): number {
+  const forwards = tunnel.forwards ?? [];
+  const index = forwards.findIndex((f) => f.name === "mongo");
+  return index >= 0 ? index : 0;
+}
+
+function mongoForward(tunnel: TunnelConfig): PortForward {
+  return tunnel.forwards?.[mongoForwardIndex(tunnel)] ?? DEFAULT_MONGO_FORWARD;
+}
+
+function withMongoForward(tunnel: TunnelConfig, patch: Partial<PortForward>): TunnelConfig {
+  const forwards = [...(tunnel.forwards ?? [])];
+  const index = mongoForwardIndex(tunnel);
+  forwards[index] = { ...mongoForward(tunnel), ...patch };
+  return { ...tunnel, forwards };
+}
 
 interface ProjectFormV3Props {
   project?: ProjectV3 | null;
@@ -46,8 +70,7 @@ export default function ProjectFormV3({
       user: "root",
       port: 22,
       privateKey: "",
-      localMongo: 27017,
-      remoteMongo: 27017,
+      forwards: [DEFAULT_MONGO_FORWARD],
     },
     createdAt: new Date().toISOString(),
     enabled: true, // Par défaut activé pour les nouveaux projets
@@ -83,8 +106,7 @@ export default function ProjectFormV3({
           user: "root",
           port: 22,
           privateKey: "",
-          localMongo: 27017,
-          remoteMongo: 27017,
+          forwards: [DEFAULT_MONGO_FORWARD],
         },
         createdAt: new Date().toISOString(),
         enabled: true, // Par défaut activé pour les nouveaux projets
@@ -127,8 +149,7 @@ export default function ProjectFormV3({
             user: "root",
             port: 22,
             privateKey: "",
-            localMongo: 27017,
-            remoteMongo: 27017,
+            forwards: [DEFAULT_MONGO_FORWARD],
           },
     }));
   };
@@ -475,15 +496,14 @@ export default function ProjectFormV3({
                     <Input
                       id="localMongo"
                       type="number"
-                      value={formData.tunnel.localMongo || 27017}
+                      value={mongoForward(formData.tunnel).localPort || 27017}
                       onChange={(e) =>
                         setFormData((prev) => ({
                           ...prev,
                           tunnel: prev.tunnel
-                            ? {
-                                ...prev.tunnel,
-                                localMongo: parseInt(e.target.value) || 27017,
-                              }
+                            ? withMongoForward(prev.tunnel, {
+                                localPort: parseInt(e.target.value) || 27017,
+                              })
                             : undefined,
                         }))
                       }
@@ -492,7 +512,7 @@ export default function ProjectFormV3({
                       max="65535"
                     />
                     <p className="text-xs text-gray-400 mt-1">
-                      Port local unique pour ce projet. Configure <code className="bg-gray-800 px-1 rounded">MONGODB_URI=mongodb://localhost:{formData.tunnel.localMongo || 27017}/your-db</code> dans le <code className="bg-gray-800 px-1 rounded">.env</code> du backend Payload.
+                      Port local unique pour ce projet. Configure <code className="bg-gray-800 px-1 rounded">MONGODB_URI=mongodb://localhost:{mongoForward(formData.tunnel).localPort || 27017}/your-db</code> dans le <code className="bg-gray-800 px-1 rounded">.env</code> du backend Payload.
                     </p>
                   </div>
                   <div>
@@ -500,15 +520,14 @@ export default function ProjectFormV3({
                     <Input
                       id="remoteMongo"
                       type="number"
-                      value={formData.tunnel.remoteMongo || 27017}
+                      value={mongoForward(formData.tunnel).remotePort || 27017}
                       onChange={(e) =>
                         setFormData((prev) => ({
                           ...prev,
                           tunnel: prev.tunnel
-                            ? {
-                                ...prev.tunnel,
-                                remoteMongo: parseInt(e.target.value) || 27017,
-                              }
+                            ? withMongoForward(prev.tunnel, {
+                                remotePort: parseInt(e.target.value) || 27017,
+                              })
                             : undefined,
                         }))
                       }
@@ -170,7 +170,16 @@ export function ProjectProvider({ children }: { children: React.ReactNode }) {
     }
     try {
       const status = await getServiceStatusV3(activeProject.id, service);
-      return status === "RUNNING" ? "running" : "stopped";
+      switch (status) {
+        case "RUNNING":
+          return "running";
+        case "STARTING":
+          return "starting";
+        case "ERROR":
+          return "error";
+        default:
+          return "stopped";
+      }
     } catch (error) {
       console.error(`Failed to get status for ${service}:`, error);
       return "error";
@@ -13,6 +13,8 @@ export interface ProjectServicesState {
   backend: ServiceState;
   frontend: ServiceState;
   tunnel: ServiceState;
+  // Services docker compose du projet, quand il en déclare
+  compose?: ServiceState;
 }
 
 /**
@@ -1,17 +1,48 @@
 import { invoke } from "@tauri-apps/api/core";
-import { ProjectScanResult } from "@/types/Project";
+import {
+  PickedFolder,
+  ProjectScanResult,
+  RecentFolders,
+  ScanReport,
+  StackDetection,
+} from "@/types/Project";
+import { GitRemote, ProjectV3, Workspace } from "@/types/ProjectV3";
 
 /**
- * Ouvre un dialogue pour choisir un dossier de projet
+ * Ouvre le sélecteur de dossier natif (null si annulé) ; le dossier est validé,
+ * classé et ajouté aux dossiers récents
  */
-export async function pickProjectFolder(): Promise<string> {
+export async function pickProjectFolder(): Promise<PickedFolder | null> {
   try {
-    return await invoke<string>("pick_project_folder");
+    return await invoke<PickedFolder | null>("pick_project_folder");
   } catch (error) {
     throw new Error(`Failed to pick folder: ${error}`);
   }
 }
 
+/**
+ * Valide et classe un chemin saisi (`~/` accepté)
+ */
+export async function inspectFolder(path: string): Promise<PickedFolder> {
+  try {
+    return await invoke<PickedFolder>("inspect_folder", { path });
+  } catch (error) {
+    throw new Error(`Invalid folder: ${error}`);
+  }
+}
+
+/**
+ * Dossiers choisis et dépôts clonés récemment (ceux qui existent encore),
+ * classés d'après le catalogue de l'indexeur : `inspectFolder` pour un dossier "unknown"
+ */
+export async function listRecentFolders(): Promise<RecentFolders> {
+  try {
+    return await invoke<RecentFolders>("list_recent_folders");
+  } catch (error) {
+    throw new Error(`Failed to load recent folders: ${error}`);
+  }
+}
+
 /**
  * Analyse automatiquement un projet et retourne sa configuration
  */
@@ -26,20 +57,138 @@ export async function autoscanProject(path: string): Promise<ProjectScanResult>
   }
 }
 
+/**
+ * Scan détaillé (provenance, candidats écartés) et différences avec le projet configuré, sans rien écrire
+ */
+export async function autoscanReport(path: string): Promise<ScanReport> {
+  try {
+    return await invoke<ScanReport>("autoscan_report", { rootPath: path });
+  } catch (error) {
+    throw new Error(`Failed to build scan report: ${error}`);
+  }
+}
+
+/**
+ * Applique au projet configuré les seuls champs choisis parmi les changements du rapport
+ */
+export async function applyScanChanges(path: string, fields: string[]): Promise<ProjectV3> {
+  try {
+    return await invoke<ProjectV3>("apply_scan_changes", { rootPath: path, fields });
+  } catch (error) {
+    throw new Error(`Failed to apply scan changes: ${error}`);
+  }
+}
+
+/**
+ * Liste les stacks détectées dans un dossier, de la plus probable à la moins probable
+ */
+export async function detectStacks(path: string): Promise<StackDetection[]> {
+  try {
+    return await invoke<StackDetection[]>("detect_stacks", { path });
+  } catch (error) {
+    throw new Error(`Failed to detect stacks: ${error}`);
+  }
+}
+
+/**
+ * Énumère les apps et paquets partagés d'un monorepo (null si ce n'en est pas un)
+ */
+export async function scanWorkspace(path: string): Promise<Workspace | null> {
+  try {
+    return await invoke<Workspace | null>("scan_workspace", { rootPath: path });
+  } catch (error) {
+    throw new Error(`Failed to scan workspace: ${error}`);
+  }
+}
+
+/**
+ * Options de clone ; sans options, clone superficiel (depth 1) de la branche par défaut
+ */
+export interface CloneOptions {
+  targetDir?: string;
+  // Nom du dossier cloné, à la place de celui déduit de l'URL
+  name?: string;
+  // Branche ou tag à extraire
+  reference?: string;
+  // null pour un clone complet
+  depth?: number | null;
+  submodules?: boolean;
+  // Dossiers à extraire (sparse checkout)
+  sparsePaths?: string[];
+}
+
+/**
+ * Interface pour l'event clone:progress
+ */
+export interface CloneProgressEvent {
+  run_id: string;
+  phase: string;
+  percent: number | null;
+  current: number | null;
+  total: number | null;
+}
+
+/**
+ * Interface pour l'event clone:finished (projet proposé par l'autoscan)
+ */
+export interface CloneFinishedEvent {
+  run_id: string;
+  path: string | null;
+  project: ProjectV3 | null;
+  error: string | null;
+  cancelled: boolean;
+}
+
+/**
+ * Décompose une URL Git (hébergeur, propriétaire, dépôt, page web) avant un clone
+ */
+export async function parseGitUrl(url: string): Promise<GitRemote> {
+  try {
+    return await invoke<GitRemote>("parse_git_url", { url });
+  } catch (error) {
+    throw new Error(`Invalid git URL: ${error}`);
+  }
+}
+
 /**
  * Clone un dépôt Git dans ~/CascadeProjects/
  */
-export async function cloneGitRepo(url: string, targetDir?: string): Promise<string> {
+export async function cloneGitRepo(
+  url: string,
+  targetDir?: string,
+  options?: CloneOptions
+): Promise<string> {
   try {
     return await invoke<string>("clone_git_repo", {
       url,
       targetDir,
+      options,
     });
   } catch (error) {
     throw new Error(`Failed to clone repo: ${error}`);
   }
 }
 
+/**
+ * Lance un clone en arrière-plan ; suivre clone:progress et clone:finished avec le runId
+ */
+export async function startClone(url: string, options?: CloneOptions): Promise<string> {
+  try {
+    const result = await invoke<{ run_id: string }>("start_clone", { url, options });
+    return result.run_id;
+  } catch (error) {
+    throw new Error(`Failed to start clone: ${error}`);
+  }
+}
+
+export async function cancelClone(runId: string): Promise<void> {
+  try {
+    await invoke("cancel_clone", { runId });
+  } catch (error) {
+    throw new Error(`Failed to cancel clone: ${error}`);
+  }
+}
+
 /**
  * Met à jour un dépôt Git existant (git pull)
  */
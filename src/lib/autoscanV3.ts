@@ -1,23 +1,54 @@
 import { invoke } from "@tauri-apps/api/core";
 
-import { ProjectScanResultV3, ProjectV3 } from "@/types/ProjectV3";
+import { PickedFolder } from "@/types/Project";
+import {
+  IndependentReposScan,
+  PairingCandidate,
+  ProjectScanResultV3,
+  RepoPair,
+} from "@/types/ProjectV3";
 
-export async function pickProjectFolderV3(): Promise<string> {
-  return await invoke<string>("pick_project_folder");
+export async function pickProjectFolderV3(): Promise<PickedFolder | null> {
+  return await invoke<PickedFolder | null>("pick_project_folder");
 }
 
 export async function autoscanProjectV3(rootPath: string): Promise<ProjectScanResultV3> {
   return await invoke<ProjectScanResultV3>("autoscan_project_v3", { rootPath });
 }
 
+/**
+ * Associations backend / frontend possibles sous un dossier parent, de la plus probable
+ * à la moins probable (similarité des noms, URL du backend dans l'env du frontend,
+ * organisation git commune, dossiers voisins)
+ */
+export async function rankRepoPairings(
+  parentPath: string,
+  depth?: number
+): Promise<PairingCandidate[]> {
+  try {
+    return await invoke<PairingCandidate[]>("rank_repo_pairings", { parentPath, depth });
+  } catch (error) {
+    throw new Error(`Failed to rank repo pairings: ${error}`);
+  }
+}
+
 /**
  * Scanne un dossier parent et détecte automatiquement tous les repos indépendants
- * (backends et frontends séparés) et les associe par nom
+ * (backends et frontends séparés). Sans `pairs`, les meilleures associations
+ * de `rankRepoPairings` sont retenues. Les dépôts au manifeste ou workspace invalide
+ * sont écartés et listés dans `warnings`.
  */
-export async function scanIndependentRepos(parentPath: string): Promise<ProjectV3[]> {
+export async function scanIndependentRepos(
+  parentPath: string,
+  options: { depth?: number; pairs?: RepoPair[] } = {}
+): Promise<IndependentReposScan> {
   try {
-    return await invoke<ProjectV3[]>("scan_independent_repos", { parentPath });
+    return await invoke<IndependentReposScan>("scan_independent_repos", {
+      parentPath,
+      depth: options.depth,
+      pairs: options.pairs,
+    });
   } catch (error) {
     throw new Error(`Failed to scan independent repos: ${error}`);
   }
-}
\ No newline at end of file
+}
@@ -1,6 +1,14 @@
 import { invoke } from "@tauri-apps/api/core";
+import type { StackDetection } from "@/types/Project";
+import type {
+  ComposeFile,
+  ComposeServiceStatus,
+  PortForward,
+  ProjectV3,
+  Workspace,
+} from "@/types/ProjectV3";
 
-export type ServiceName = "tunnel" | "backend" | "frontend" | "netdata";
+export type ServiceName = "tunnel" | "backend" | "frontend" | "netdata" | "compose";
 
 export interface SystemStatus {
   cpu: number;
@@ -99,9 +107,48 @@ export interface ScriptResult {
   stdout: string;
   stderr: string;
   code: number;
+  profile: string | null;
 }
 
-export type ServiceStatus = "RUNNING" | "STOPPED";
+// STARTING et ERROR : statut agrégé des services compose
+export type ServiceStatus = "RUNNING" | "STOPPED" | "STARTING" | "ERROR";
+
+/**
+ * Statut d'un service V3 marqué avec le profil d'environnement actif
+ */
+export interface ServiceStatusV3 {
+  status: ServiceStatus;
+  profile: string | null;
+}
+
+/**
+ * Résultat d'un changement de profil d'environnement
+ */
+export interface ProfileSwitchResult {
+  project_id: string;
+  previous_profile: string | null;
+  active_profile: string | null;
+  affected: ServiceName[];
+  restarted: ServiceName[];
+  results: ScriptResult[];
+}
+
+/**
+ * Variable de l'environnement effectif d'un service, avec sa provenance
+ */
+export interface EnvVar {
+  key: string;
+  value: string;
+  source: string;
+  masked: boolean;
+}
+
+export interface ResolvedEnv {
+  project_id: string;
+  service: ServiceName;
+  profile: string | null;
+  vars: EnvVar[];
+}
 
 export async function startServiceV3(
   projectId: string,
@@ -130,12 +177,470 @@ export async function getServiceStatusV3(
   service: ServiceName
 ): Promise<ServiceStatus> {
   try {
-    return await invoke<ServiceStatus>("status_service_v3", { projectId, service });
+    const result = await invoke<ServiceStatusV3>("status_service_v3", { projectId, service });
+    return result.status;
   } catch (error) {
     throw new Error(`Failed to get status for ${service}: ${error}`);
   }
 }
 
+/**
+ * Change le profil d'environnement actif d'un projet (null = configuration de base)
+ * et redémarre les services concernés
+ */
+export async function switchProfile(
+  projectId: string,
+  profile: string | null
+): Promise<ProfileSwitchResult> {
+  try {
+    return await invoke<ProfileSwitchResult>("switch_profile", { projectId, profile });
+  } catch (error) {
+    throw new Error(`Failed to switch profile: ${error}`);
+  }
+}
+
+/**
+ * Environnement effectif d'un service (valeurs secrètes masquées)
+ */
+export async function resolveServiceEnv(
+  projectId: string,
+  service: ServiceName
+): Promise<ResolvedEnv> {
+  try {
+    return await invoke<ResolvedEnv>("resolve_service_env", { projectId, service });
+  } catch (error) {
+    throw new Error(`Failed to resolve env for ${service}: ${error}`);
+  }
+}
+
+/**
+ * État du coffre de secrets (les valeurs ne sont jamais renvoyées)
+ *
+ * Une valeur de configuration `secret://<nom>` est résolue au démarrage du service.
+ */
+export interface VaultStatus {
+  exists: boolean;
+  unlocked: boolean;
+  names: string[];
+}
+
+export async function getVaultStatus(): Promise<VaultStatus> {
+  try {
+    return await invoke<VaultStatus>("vault_status");
+  } catch (error) {
+    throw new Error(`Failed to get vault status: ${error}`);
+  }
+}
+
+export async function unlockVault(passphrase: string): Promise<VaultStatus> {
+  try {
+    return await invoke<VaultStatus>("unlock_vault", { passphrase });
+  } catch (error) {
+    throw new Error(`Failed to unlock vault: ${error}`);
+  }
+}
+
+export async function lockVault(): Promise<VaultStatus> {
+  try {
+    return await invoke<VaultStatus>("lock_vault");
+  } catch (error) {
+    throw new Error(`Failed to lock vault: ${error}`);
+  }
+}
+
+export async function setSecret(name: string, value: string): Promise<VaultStatus> {
+  try {
+    return await invoke<VaultStatus>("set_secret", { name, value });
+  } catch (error) {
+    throw new Error(`Failed to store secret ${name}: ${error}`);
+  }
+}
+
+export async function deleteSecret(name: string): Promise<VaultStatus> {
+  try {
+    return await invoke<VaultStatus>("delete_secret", { name });
+  } catch (error) {
+    throw new Error(`Failed to delete secret ${name}: ${error}`);
+  }
+}
+
+/**
+ * Réglages de l'application (~/.gestionmax-devcenter/settings.json)
+ */
+export interface AppSettings {
+  // Dossiers indexés en arrière-plan, "~/CascadeProjects" par défaut
+  workspaceRoots: string[];
+  scanDepth: number;
+  // Motifs glob de dossiers ignorés par l'autoscan (syntaxe .gitignore)
+  ignorePatterns: string[];
+  // Du plus récent au plus ancien, 10 entrées au plus
+  recentWorkspaces: string[];
+  recentClones: string[];
+  // Réinstalle les dépendances périmées avant de démarrer un service
+  autoInstallDependencies: boolean;
+}
+
+export async function getSettings(): Promise<AppSettings> {
+  try {
+    return await invoke<AppSettings>("get_settings");
+  } catch (error) {
+    throw new Error(`Failed to load settings: ${error}`);
+  }
+}
+
+export async function saveSettings(settings: AppSettings): Promise<void> {
+  try {
+    await invoke("save_settings", { settings });
+  } catch (error) {
+    throw new Error(`Failed to save settings: ${error}`);
+  }
+}
+
+/**
+ * Dépôt du catalogue tenu à jour par l'indexeur
+ */
+export interface CatalogRepo {
+  path: string;
+  name: string;
+  root: string;
+  kind: "manifest" | "monorepo" | "backend" | "frontend" | "repository";
+  stacks: StackDetection[];
+  manifest_path: string | null;
+  workspace: Workspace | null;
+  fingerprint: Record<string, number>;
+  indexed_at: string;
+}
+
+export interface Catalog {
+  roots: string[];
+  repos: Record<string, CatalogRepo>;
+  updated_at: string | null;
+}
+
+/**
+ * Interface pour l'event catalog:changed (nouveau clone, dépôt supprimé, manifeste modifié)
+ */
+export interface CatalogChangedEvent {
+  added: CatalogRepo[];
+  removed: string[];
+  updated: CatalogRepo[];
+  // Indexation impossible (réglages illisibles, surveillance indisponible)
+  errors?: string[];
+}
+
+/**
+ * Catalogue des dépôts, sans parcourir le disque
+ */
+export async function getCatalog(): Promise<Catalog> {
+  try {
+    return await invoke<Catalog>("get_catalog");
+  } catch (error) {
+    throw new Error(`Failed to load catalog: ${error}`);
+  }
+}
+
+export async function refreshCatalog(): Promise<Catalog> {
+  try {
+    return await invoke<Catalog>("refresh_catalog");
+  } catch (error) {
+    throw new Error(`Failed to refresh catalog: ${error}`);
+  }
+}
+
+/**
+ * Fichier compose d'un dossier et ses services (sans rien lancer)
+ */
+export async function scanCompose(rootPath: string): Promise<ComposeFile | null> {
+  try {
+    return await invoke<ComposeFile | null>("scan_compose", { rootPath });
+  } catch (error) {
+    throw new Error(`Failed to read compose file: ${error}`);
+  }
+}
+
+/**
+ * Services compose : ceux du projet si `services` est omis
+ */
+export async function composeUp(projectId: string, services?: string[]): Promise<ScriptResult> {
+  try {
+    return await invoke<ScriptResult>("compose_up", { projectId, services });
+  } catch (error) {
+    throw new Error(`Failed to start compose services: ${error}`);
+  }
+}
+
+export async function composeDown(projectId: string, services?: string[]): Promise<ScriptResult> {
+  try {
+    return await invoke<ScriptResult>("compose_down", { projectId, services });
+  } catch (error) {
+    throw new Error(`Failed to stop compose services: ${error}`);
+  }
+}
+
+export async function getComposeStatus(projectId: string): Promise<ComposeServiceStatus[]> {
+  try {
+    return await invoke<ComposeServiceStatus[]>("compose_status", { projectId });
+  } catch (error) {
+    throw new Error(`Failed to get compose status: ${error}`);
+  }
+}
+
+export async function getComposeLogs(
+  projectId: string,
+  service: string,
+  tail?: number
+): Promise<ScriptResult> {
+  try {
+    return await invoke<ScriptResult>("compose_logs", { projectId, service, tail });
+  } catch (error) {
+    throw new Error(`Failed to read logs for ${service}: ${error}`);
+  }
+}
+
+export interface LastCommit {
+  hash: string;
+  summary: string;
+  author: string;
+  date: string;
+}
+
+/**
+ * État d'un dépôt git (branche, avance/retard, fichiers modifiés, opération en cours)
+ */
+export interface RepoStatus {
+  root: string;
+  git_dir: string;
+  // null pour une HEAD détachée
+  branch: string | null;
+  upstream: string | null;
+  ahead: number;
+  behind: number;
+  staged: number;
+  unstaged: number;
+  untracked: number;
+  conflicted: number;
+  stashes: number;
+  last_commit: LastCommit | null;
+  operation: "merge" | "rebase" | "cherry-pick" | "revert" | "bisect" | null;
+}
+
+export interface PathGitStatus {
+  role: "root" | "backend" | "frontend";
+  path: string;
+  status: RepoStatus | null;
+  error: string | null;
+}
+
+/**
+ * Interface pour l'event git:status (projets dont l'état git a changé)
+ */
+export interface ProjectGitStatus {
+  project_id: string;
+  paths: PathGitStatus[];
+}
+
+/**
+ * Interface pour l'event git:error (suivi git interrompu, configuration illisible)
+ */
+export interface GitWatchError {
+  error: string;
+}
+
+/**
+ * État git de tous les projets, ou de ceux demandés, en un seul appel
+ */
+export async function getGitStatusAll(projectIds?: string[]): Promise<ProjectGitStatus[]> {
+  try {
+    return await invoke<ProjectGitStatus[]>("git_status_all", { projectIds });
+  } catch (error) {
+    throw new Error(`Failed to get git status: ${error}`);
+  }
+}
+
+export type SyncStrategy = "ff-only" | "rebase" | "merge";
+
+export interface SyncOptions {
+  strategy?: SyncStrategy;
+  // Met de côté les modifications locales et les restaure après la mise à jour
+  autoStash?: boolean;
+}
+
+export interface SyncedCommit {
+  hash: string;
+  summary: string;
+  author: string;
+}
+
+/**
+ * Résultat d'une synchronisation (rien n'est modifié pour dirty, diverged et conflict)
+ */
+export interface SyncResult {
+  path: string;
+  branch: string | null;
+  upstream: string | null;
+  outcome: "up-to-date" | "updated" | "dirty" | "diverged" | "conflict" | "failed";
+  message: string | null;
+  before: string | null;
+  after: string | null;
+  commits: SyncedCommit[];
+  files: string[];
+  // package.json ou un lockfile a changé : dépendances à réinstaller
+  dependencies_changed: boolean;
+  conflicts: string[];
+  stashed: boolean;
+  stash_restored: boolean;
+}
+
+export interface RepoSyncResult {
+  path: string;
+  project_ids: string[];
+  result: SyncResult | null;
+  error: string | null;
+}
+
+export async function syncRepo(path: string, options?: SyncOptions): Promise<SyncResult> {
+  try {
+    return await invoke<SyncResult>("sync_repo", { path, options });
+  } catch (error) {
+    throw new Error(`Failed to sync ${path}: ${error}`);
+  }
+}
+
+/**
+ * Synchronise en parallèle chaque dépôt des projets configurés
+ */
+export async function syncAllProjects(options?: SyncOptions): Promise<RepoSyncResult[]> {
+  try {
+    return await invoke<RepoSyncResult[]>("sync_all_projects", { options });
+  } catch (error) {
+    throw new Error(`Failed to sync projects: ${error}`);
+  }
+}
+
+export interface BranchInfo {
+  // Nom court ("feature/x", "origin/feature/x")
+  name: string;
+  remote: boolean;
+  current: boolean;
+  upstream: string | null;
+  commit: string;
+  summary: string;
+  // Worktree où la branche est extraite
+  worktree: string | null;
+}
+
+export interface WorktreeInfo {
+  path: string;
+  head: string;
+  branch: string | null;
+  detached: boolean;
+  locked: boolean;
+  prunable: boolean;
+  // Variante de projet enregistrée pour ce worktree
+  project_id: string | null;
+}
+
+export interface BranchSwitchResult {
+  project_id: string;
+  previous_branch: string | null;
+  branch: string;
+  files: string[];
+  dependencies_changed: boolean;
+  // Dossiers où les dépendances ont été réinstallées
+  installed: string[];
+  // Services arrêtés avant le changement puis relancés
+  restarted: string[];
+  results: ScriptResult[];
+}
+
+export interface WorktreeProject {
+  project: ProjectV3;
+  results: ScriptResult[];
+}
+
+export async function listBranches(path: string): Promise<BranchInfo[]> {
+  try {
+    return await invoke<BranchInfo[]>("list_branches", { path });
+  } catch (error) {
+    throw new Error(`Failed to list branches: ${error}`);
+  }
+}
+
+export async function createBranch(
+  path: string,
+  name: string,
+  startPoint?: string
+): Promise<BranchInfo> {
+  try {
+    return await invoke<BranchInfo>("create_branch", { path, name, startPoint });
+  } catch (error) {
+    throw new Error(`Failed to create branch ${name}: ${error}`);
+  }
+}
+
+/**
+ * Change la branche d'un projet : arrête ses services, réinstalle les dépendances si besoin et les relance
+ */
+export async function switchProjectBranch(
+  projectId: string,
+  branch: string,
+  reinstall?: boolean
+): Promise<BranchSwitchResult> {
+  try {
+    return await invoke<BranchSwitchResult>("switch_project_branch", { projectId, branch, reinstall });
+  } catch (error) {
+    throw new Error(`Failed to switch to ${branch}: ${error}`);
+  }
+}
+
+export async function listWorktrees(path: string): Promise<WorktreeInfo[]> {
+  try {
+    return await invoke<WorktreeInfo[]>("list_worktrees", { path });
+  } catch (error) {
+    throw new Error(`Failed to list worktrees: ${error}`);
+  }
+}
+
+/**
+ * Extrait une branche dans un worktree et l'enregistre comme variante du projet (ports dédiés)
+ */
+export async function createProjectWorktree(
+  projectId: string,
+  branch: string,
+  startPoint?: string,
+  install?: boolean
+): Promise<WorktreeProject> {
+  try {
+    return await invoke<WorktreeProject>("create_project_worktree", {
+      projectId,
+      branch,
+      startPoint,
+      install,
+    });
+  } catch (error) {
+    throw new Error(`Failed to create worktree for ${branch}: ${error}`);
+  }
+}
+
+export async function removeProjectWorktree(projectId: string, force?: boolean): Promise<ScriptResult[]> {
+  try {
+    return await invoke<ScriptResult[]>("remove_project_worktree", { projectId, force });
+  } catch (error) {
+    throw new Error(`Failed to remove worktree ${projectId}: ${error}`);
+  }
+}
+
+/**
+ * Supprime toutes les variantes worktree d'un projet et arrête leurs services
+ */
+export async function cleanupProjectWorktrees(projectId: string, force?: boolean): Promise<string[]> {
+  try {
+    return await invoke<string[]>("cleanup_project_worktrees", { projectId, force });
+  } catch (error) {
+    throw new Error(`Failed to clean up worktrees: ${error}`);
+  }
+}
+
 export async function killZombiesV3(): Promise<ScriptResult> {
   try {
     return await invoke<ScriptResult>("kill_zombies_v3");
@@ -159,6 +664,7 @@ export interface GmdResult {
   stdout: string;
   stderr: string;
   code: number;
+  profile: string | null;
 }
 
 /**
@@ -178,6 +684,7 @@ export interface GmdLogEvent {
   line: string;
   cmd: string;
   cwd: string;
+  profile: string | null;
 }
 
 /**
@@ -186,6 +693,28 @@ export interface GmdLogEvent {
 export interface GmdExitEvent {
   run_id: string;
   exit_code: number;
+  profile: string | null;
+}
+
+/**
+ * Différences d'un projet (chemins pointés, ex. `ports.backend`)
+ */
+export interface ProjectChange {
+  project_id: string;
+  change: "added" | "removed" | "modified";
+  added: string[];
+  removed: string[];
+  modified: string[];
+  restart_required: ServiceName[];
+}
+
+/**
+ * Interface pour l'event config:changed, émis quand projects-v3.json
+ * ou un manifeste est modifié hors de l'application
+ */
+export interface ConfigChangedEvent {
+  changes: ProjectChange[];
+  errors: string[];
 }
 
 /**
@@ -242,6 +771,248 @@ export async function runGmdCommand(
   }
 }
 
+/**
+ * État des dépendances d'un service d'après l'empreinte de son lockfile
+ */
+export interface DependencyStatus {
+  service: "backend" | "frontend";
+  // Dossier d'installation (racine du monorepo si le lockfile y est)
+  dir: string;
+  file: string;
+  package_manager: "npm" | "pnpm" | "yarn" | "bun";
+  // untracked : installé hors du devcenter, l'empreinte actuelle devient la référence
+  state: "up-to-date" | "outdated" | "missing" | "untracked";
+  installed_at: string | null;
+}
+
+/**
+ * Interface pour l'event deps:exit (les lignes arrivent dans deps:log, au format GmdLogEvent)
+ */
+export interface DepsExitEvent {
+  run_id: string;
+  exit_code: number;
+  status: DependencyStatus | null;
+  // Installation réussie mais empreinte non enregistrée
+  error?: string;
+}
+
+/**
+ * État des dépendances des services Node d'un projet
+ */
+export async function checkDependencies(projectId: string): Promise<DependencyStatus[]> {
+  try {
+    return await invoke<DependencyStatus[]>("check_dependencies", { projectId });
+  } catch (error) {
+    throw new Error(`Failed to check dependencies: ${error}`);
+  }
+}
+
+/**
+ * Lance l'installation des dépendances d'un service ; suivre deps:log et deps:exit avec le runId
+ */
+export async function installDependencies(
+  projectId: string,
+  service: "backend" | "frontend"
+): Promise<GmdRunId> {
+  try {
+    return await invoke<GmdRunId>("install_dependencies", { projectId, service });
+  } catch (error) {
+    throw new Error(`Failed to install dependencies: ${error}`);
+  }
+}
+
+/**
+ * Transfert d'un tunnel et résultat de sa dernière vérification
+ */
+export interface TunnelForwardStatus extends PortForward {
+  ready: boolean;
+}
+
+/**
+ * Statut d'un tunnel SSH géré par l'application (aussi payload de l'event tunnel:status)
+ */
+export interface TunnelStatus {
+  projectId: string;
+  // failed : erreur définitive (authentification, clé d'hôte), pas de nouvelle tentative
+  state: "connecting" | "connected" | "reconnecting" | "failed" | "stopped";
+  destination: string | null;
+  forwards: TunnelForwardStatus[];
+  pid: number | null;
+  attempt: number;
+  connectedSince: string | null;
+  // Secondes avant la prochaine tentative de reconnexion
+  retryIn: number | null;
+  lastError: string | null;
+}
+
+/**
+ * Statut du tunnel SSH d'un projet
+ */
+export async function getTunnelStatus(projectId: string): Promise<TunnelStatus> {
+  try {
+    return await invoke<TunnelStatus>("get_tunnel_status", { projectId });
+  } catch (error) {
+    throw new Error(`Failed to get tunnel status: ${error}`);
+  }
+}
+
+/**
+ * Statut de tous les tunnels SSH lancés par l'application
+ */
+export async function listTunnels(): Promise<TunnelStatus[]> {
+  try {
+    return await invoke<TunnelStatus[]>("list_tunnels");
+  } catch (error) {
+    throw new Error(`Failed to list tunnels: ${error}`);
+  }
+}
+
+/**
+ * Clé d'hôte épinglée pour les tunnels (ligne du known_hosts du devcenter)
+ */
+export interface HostKey {
+  // "host" ou "[host]:port", comme l'écrit ssh
+  host: string;
+  keyType: string;
+  key: string;
+  // "SHA256:...", comme l'affiche ssh
+  fingerprint: string;
+}
+
+/**
+ * Vérification de la clé présentée par le serveur d'un tunnel (aussi payload de l'event tunnel:host-key)
+ */
+export interface HostKeyCheck {
+  projectId: string;
+  // unknown : à vérifier puis épingler ; changed : erreur bloquante, interception possible
+  state: "pinned" | "unknown" | "changed";
+  offered: HostKey | null;
+  offeredFingerprint: string | null;
+  pinned: HostKey | null;
+  message: string;
+}
+
+/**
+ * Clés d'hôte épinglées
+ */
+export async function listHostKeys(): Promise<HostKey[]> {
+  try {
+    return await invoke<HostKey[]>("list_host_keys");
+  } catch (error) {
+    throw new Error(`Failed to list host keys: ${error}`);
+  }
+}
+
+/**
+ * Récupère l'empreinte présentée par le serveur du tunnel d'un projet, sans l'épingler
+ */
+export async function checkTunnelHostKey(projectId: string): Promise<HostKeyCheck> {
+  try {
+    return await invoke<HostKeyCheck>("check_tunnel_host_key", { projectId });
+  } catch (error) {
+    throw new Error(`Failed to check host key: ${error}`);
+  }
+}
+
+/**
+ * Épingle une clé d'hôte vérifiée par l'utilisateur
+ */
+export async function pinHostKey(hostKey: HostKey): Promise<HostKey[]> {
+  try {
+    return await invoke<HostKey[]>("pin_host_key", { hostKey });
+  } catch (error) {
+    throw new Error(`Failed to pin host key: ${error}`);
+  }
+}
+
+/**
+ * Oublie les clés épinglées d'un hôte (toutes, ou seulement keyType)
+ */
+export async function forgetHostKey(host: string, keyType?: string): Promise<HostKey[]> {
+  try {
+    return await invoke<HostKey[]>("forget_host_key", { host, keyType: keyType ?? null });
+  } catch (error) {
+    throw new Error(`Failed to forget host key: ${error}`);
+  }
+}
+
+/**
+ * Clé privée trouvée dans ~/.ssh
+ */
+export interface SshKeyInfo {
+  path: string;
+  name: string;
+  // "ssh-ed25519", "ssh-rsa"... ; null si la clé publique n'a pas pu être lue
+  keyType: string | null;
+  fingerprint: string | null;
+  comment: string | null;
+  // Protégée par une phrase de passe
+  encrypted: boolean;
+  // Chargée dans ssh-agent ; null si l'agent est injoignable
+  inAgent: boolean | null;
+  // Mode du fichier ("0600")
+  mode: string | null;
+  permissionsOk: boolean;
+  issues: string[];
+}
+
+/**
+ * Résultat d'un test de connexion au serveur d'un tunnel
+ */
+export interface ConnectionTest {
+  projectId: string;
+  destination: string | null;
+  // Le serveur a répondu et sa clé d'hôte est épinglée
+  reachable: boolean;
+  authenticated: boolean;
+  durationMs: number;
+  message: string;
+}
+
+/**
+ * Inventaire des clés SSH (type, phrase de passe, agent, permissions)
+ */
+export async function listSshKeys(): Promise<SshKeyInfo[]> {
+  try {
+    return await invoke<SshKeyInfo[]>("list_ssh_keys");
+  } catch (error) {
+    throw new Error(`Failed to list SSH keys: ${error}`);
+  }
+}
+
+/**
+ * Restreint les permissions d'une clé privée à 0600
+ */
+export async function fixSshKeyPermissions(path: string): Promise<SshKeyInfo> {
+  try {
+    return await invoke<SshKeyInfo>("fix_ssh_key_permissions", { path });
+  } catch (error) {
+    throw new Error(`Failed to fix SSH key permissions: ${error}`);
+  }
+}
+
+/**
+ * Teste l'authentification sur le serveur du tunnel d'un projet, sans ouvrir les forwards
+ */
+export async function testTunnelConnection(projectId: string): Promise<ConnectionTest> {
+  try {
+    return await invoke<ConnectionTest>("test_tunnel_connection", { projectId });
+  } catch (error) {
+    throw new Error(`Failed to test tunnel connection: ${error}`);
+  }
+}
+
+/**
+ * Teste en parallèle la connexion de tous les tunnels activés
+ */
+export async function testAllTunnelConnections(): Promise<ConnectionTest[]> {
+  try {
+    return await invoke<ConnectionTest[]>("test_all_tunnel_connections");
+  } catch (error) {
+    throw new Error(`Failed to test tunnel connections: ${error}`);
+  }
+}
+
 /**
  * Lit les logs en temps réel
  */
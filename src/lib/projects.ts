@@ -1,6 +1,7 @@
 import { invoke } from "@tauri-apps/api/core";
 import { openPath, openUrl } from "@tauri-apps/plugin-opener";
 import { Project, ServiceStatus } from "./types";
+import type { GitRemote } from "@/types/ProjectV3";
 
 /**
  * Liste tous les projets depuis le fichier JSON
@@ -95,3 +96,16 @@ export async function openServiceUrl(port: number): Promise<void> {
   }
 }
 
+/**
+ * Ouvre la page du dépôt sur GitHub, GitLab, Bitbucket ou Azure DevOps
+ */
+export async function openProjectRemote(remote: GitRemote): Promise<void> {
+  if (!remote.webUrl) {
+    throw new Error(`No web page for ${remote.url}`);
+  }
+  try {
+    await openUrl(remote.webUrl);
+  } catch (error) {
+    throw new Error(`Failed to open URL: ${error}`);
+  }
+}
@@ -69,6 +69,7 @@ export function getServiceLabel(service: ServiceName): string {
     backend: "Backend Payload",
     frontend: "Frontend Next.js",
     netdata: "Netdata",
+    compose: "Docker Compose",
   };
   return labels[service];
 }
@@ -82,6 +83,7 @@ export function getServiceIcon(service: ServiceName): string {
     backend: "Server",
     frontend: "Globe",
     netdata: "Activity",
+    compose: "Container",
   };
   return icons[service];
 }
@@ -22,9 +22,9 @@ export default function AutoScanProject() {
       setScanData(null);
 
       // 1. Choisir le dossier
-      const folderPath = await pickProjectFolder();
+      const folder = await pickProjectFolder();
       
-      if (!folderPath) {
+      if (!folder) {
         toast({
           title: "Annulé",
           description: "Sélection du dossier annulée.",
@@ -34,9 +34,18 @@ export default function AutoScanProject() {
         return;
       }
 
+      if (folder.kind === "workspace-parent") {
+        toast({
+          title: "Dossier de dépôts",
+          description: `${folder.repos} dépôts trouvés dans "${folder.name}" : utilisez le scan des dépôts indépendants.`,
+        });
+        setIsScanning(false);
+        return;
+      }
+
       // 2. Scanner le projet
-      const result = await autoscanProject(folderPath);
-      setScanData({ rootPath: folderPath, result });
+      const result = await autoscanProject(folder.path);
+      setScanData({ rootPath: folder.path, result });
 
       toast({
         title: "Projet scanné",
@@ -64,8 +64,7 @@ export default function ProjectManager() {
           user: "root",
           port: 22,
           privateKey: "",
-          localMongo: 27017,
-          remoteMongo: 27017,
+          forwards: [{ name: "mongo", localPort: 27017, remotePort: 27017 }],
         },
         enabled: true, // Par défaut activé pour les nouveaux projets
       };
@@ -305,7 +304,10 @@ export default function ProjectManager() {
                   </Badge>
                   {project.tunnel?.enabled && (
                     <Badge variant="secondary" className="text-xs bg-green-900/50">
-                      Tunnel :{project.tunnel.localMongo}
+                      Tunnel :
+                      {(project.tunnel.forwards ?? [])
+                        .map((f) => f.localPort)
+                        .join(", ")}
                     </Badge>
                   )}
                 </div>
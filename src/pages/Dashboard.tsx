@@ -59,7 +59,14 @@ export default function Dashboard() {
     setIsScanning(true);
     try {
       const cascadePath = "~/CascadeProjects";
-      const detectedProjects = await scanIndependentRepos(cascadePath);
+      const { projects: detectedProjects, warnings } = await scanIndependentRepos(cascadePath);
+      if (warnings.length > 0 && showToast) {
+        toast({
+          title: `${warnings.length} dépôt(s) ignoré(s)`,
+          description: warnings.join("\n"),
+          variant: "destructive",
+        });
+      }
       
       if (detectedProjects.length > 0) {
         // ⚠️ RÈGLE FONDAMENTALE: Auto-scan ne crée JAMAIS de projets
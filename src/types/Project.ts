@@ -1,3 +1,5 @@
+import type { ComposeFile, Workspace } from "./ProjectV3";
+
 export interface ProjectCommand {
   start: string;
   stop?: string | null;
@@ -35,19 +37,112 @@ export interface ProjectScanScripts {
   netdata: (ProjectScanScript & { port: number });
 }
 
+export type PortSource =
+  | { kind: "env"; keys: string[] }
+  | { kind: "file"; file: string; pattern: string }
+  | { kind: "scripts" }
+  | { kind: "default"; port: number };
+
+/**
+ * Stack détectée dans un dossier (Payload, Next.js, Django...)
+ */
+export interface StackDetection {
+  stack: string;
+  label: string;
+  role: "backend" | "frontend";
+  confidence: number;
+  dev_command: string;
+  build_command: string | null;
+  start_command: string | null;
+  package_manager: "npm" | "pnpm" | "yarn" | "bun" | null;
+  port_sources: PortSource[];
+  health_endpoint: string | null;
+}
+
+// Nature d'un dossier choisi : projet, dossier de dépôts ou inconnu
+export type FolderKind = "project-root" | "workspace-parent" | "unknown";
+
+export interface PickedFolder {
+  path: string;
+  name: string;
+  kind: FolderKind;
+  // Dépôts et projets trouvés sous un dossier parent
+  repos: number;
+}
+
+export interface RecentFolders {
+  workspaces: PickedFolder[];
+  clones: PickedFolder[];
+}
+
 export interface ProjectScanResult {
   name: string;
   backend_path: string | null;
   backend_port: number | null;
+  // Provenance du port ("manifeste", ".env.local ligne 4"...), null si non trouvé
+  backend_port_source: string | null;
   backend_start: string | null;
   backend_stop: string | null;
 
   frontend_path: string | null;
   frontend_port: number | null;
+  frontend_port_source: string | null;
   frontend_start: string | null;
   frontend_stop: string | null;
 
   scripts_path: string | null;
   scripts: ProjectScanScripts;
+  // Manifeste versionné (.gmdev.toml / gmdev.json) utilisé pour le scan
+  manifest_path: string | null;
+  backend_stack: StackDetection | null;
+  frontend_stack: StackDetection | null;
+  workspace: Workspace | null;
+  compose: ComposeFile | null;
   warnings: string[];
 }
+
+/**
+ * Valeur retenue par l'autoscan, avec la règle et le fichier d'où elle vient.
+ * `field` est un chemin pointé de ProjectV3 (`ports.backend`) ou `backendStack` / `frontendStack`.
+ */
+export interface ScanField {
+  field: string;
+  value: unknown;
+  source: string | null;
+  rule: string;
+  confidence: number;
+}
+
+export interface RejectedCandidate {
+  field: string;
+  value: unknown;
+  rule: string;
+  confidence: number;
+  reason: string;
+}
+
+// Dossier écarté par une règle d'ignore
+export interface SkippedDir {
+  path: string;
+  pattern: string;
+  origin: "builtin" | "settings" | ".gitignore" | ".gmdevignore";
+}
+
+// Champ que le nouveau scan changerait dans le projet déjà configuré
+export interface FieldChange {
+  field: string;
+  current: unknown | null;
+  proposed: unknown;
+  rule: string;
+  source: string | null;
+  confidence: number;
+}
+
+export interface ScanReport {
+  scan: ProjectScanResult;
+  fields: ScanField[];
+  rejected: RejectedCandidate[];
+  skipped: SkippedDir[];
+  project_id: string | null;
+  changes: FieldChange[];
+}
@@ -1,7 +1,33 @@
+export interface HealthCheck {
+  url: string;
+  intervalSecs?: number;
+  timeoutSecs?: number;
+}
+
 export interface ServiceConfig {
   start?: string;
   stop?: string;
   port?: number;
+  envFiles?: string[];
+  healthCheck?: HealthCheck;
+  env?: Record<string, string>;
+}
+
+// local : -L, remote : -R (port du serveur vers cette machine), dynamic : -D (proxy SOCKS)
+export type ForwardKind = "local" | "remote" | "dynamic";
+
+// forward : la connexion locale reste ouverte ; listen : le port local accepte les connexions
+export type ForwardCheck = "forward" | "listen" | "none";
+
+export interface PortForward {
+  name?: string;
+  kind?: ForwardKind;
+  bindAddress?: string;
+  localPort: number;
+  remoteHost?: string;
+  // Ignoré pour un transfert dynamic
+  remotePort?: number;
+  check?: ForwardCheck;
 }
 
 export interface TunnelConfig {
@@ -10,8 +36,12 @@ export interface TunnelConfig {
   user?: string;
   port?: number;
   privateKey?: string;
-  localMongo?: number;
-  remoteMongo?: number;
+  // Alias de ~/.ssh/config, utilisé à la place de host/user/port/clé
+  sshAlias?: string;
+  // Rebonds successifs (ProxyJump), ex: "deploy@bastion:2222"
+  proxyJump?: string[];
+  // Les anciens champs localMongo/remoteMongo sont migrés en un transfert "mongo"
+  forwards?: PortForward[];
 }
 
 export interface ProjectEnvironment {
@@ -26,6 +56,19 @@ export interface ProjectCommands {
   netdata?: string;
 }
 
+// Surcharges locales appliquées par-dessus le manifeste du dépôt
+export interface ProjectOverrides {
+  ports?: { backend?: number; frontend?: number };
+  commands?: ProjectCommands;
+  environment?: ProjectEnvironment;
+  tunnel?: TunnelConfig;
+}
+
+// Profil d'environnement nommé (local, staging, prod-readonly...)
+export interface ProjectProfile extends ProjectOverrides {
+  description?: string;
+}
+
 export interface ProjectV3 {
   id: string;
   name: string;
@@ -41,6 +84,8 @@ export interface ProjectV3 {
   ports: {
     backend: number;
     frontend: number;
+    // Ports publiés par les services compose, par nom de service
+    services?: Record<string, number>;
   };
 
   environment?: ProjectEnvironment;
@@ -55,6 +100,120 @@ export interface ProjectV3 {
   
   // Activation du projet (un seul projet peut être actif à la fois)
   enabled?: boolean;
+
+  // Manifeste versionné dont le projet est issu (.gmdev.toml / gmdev.json)
+  manifestPath?: string;
+  // Manifeste absent ou invalide : le projet enregistré est utilisé tel quel
+  manifestError?: string;
+  overrides?: ProjectOverrides;
+
+  // Profils d'environnement et profil actif (aucun = configuration de base)
+  profiles?: Record<string, ProjectProfile>;
+  activeProfile?: string;
+
+  // Apps et paquets partagés d'un monorepo
+  workspace?: Workspace;
+
+  // Un service par app exécutable du monorepo, par nom d'app
+  apps?: Record<string, AppService>;
+
+  // Services docker compose lancés avec le projet
+  compose?: ComposeConfig;
+
+  // Variante temporaire d'un autre projet, dans un worktree git
+  worktree?: WorktreeVariant;
+
+  // Remote `origin` du dépôt (lien vers GitHub, GitLab...)
+  remote?: GitRemote;
+}
+
+export interface GitRemote {
+  // URL telle que saisie
+  url: string;
+  protocol: "https" | "http" | "ssh" | "git" | "file";
+  provider: "github" | "gitlab" | "bitbucket" | "azure" | "other";
+  // null pour un dépôt local
+  host: string | null;
+  port: number | null;
+  // "org", "group/subgroup" (GitLab) ou "org/project" (Azure)
+  owner: string | null;
+  repo: string;
+  // Page du dépôt sur l'hébergeur, null pour un dépôt local
+  webUrl: string | null;
+}
+
+export interface WorktreeVariant {
+  baseId: string;
+  branch: string;
+  // Dépôt principal, d'où le worktree est supprimé
+  repoRoot: string;
+  worktreePath: string;
+}
+
+export interface WorkspaceApp {
+  name: string;
+  path: string;
+  role: "backend" | "frontend" | null;
+  stack: string | null;
+  confidence: number;
+  devCommand: string | null;
+  port: number | null;
+  // Paquets du workspace à construire avant de lancer l'app
+  dependsOn: string[];
+}
+
+// App exécutable d'un monorepo, proposée comme service du projet
+export interface AppService {
+  path: string;
+  port: number;
+  devCommand: string;
+  // Paquets du workspace à construire avant de lancer l'app
+  dependsOn?: string[];
+}
+
+export interface WorkspacePackage {
+  name: string;
+  path: string;
+  buildCommand: string | null;
+}
+
+export interface Workspace {
+  tools: Array<"pnpm" | "workspaces" | "turbo" | "nx">;
+  apps: WorkspaceApp[];
+  packages: WorkspacePackage[];
+}
+
+export interface PublishedPort {
+  published: number;
+  target: number;
+  protocol: string;
+}
+
+export interface ComposeService {
+  name: string;
+  image: string | null;
+  kind: "mongo" | "postgres" | "redis" | "minio" | "mysql" | "mariadb" | null;
+  ports: PublishedPort[];
+}
+
+export interface ComposeFile {
+  path: string;
+  services: ComposeService[];
+}
+
+// Fichier compose et services lancés avec le projet (tous si vide)
+export interface ComposeConfig {
+  file: string;
+  services: string[];
+}
+
+export interface ComposeServiceStatus {
+  name: string;
+  status: "RUNNING" | "STARTING" | "STOPPED" | "ERROR";
+  // État du conteneur (running, exited...), vide s'il n'existe pas
+  state: string;
+  health: string | null;
+  ports: PublishedPort[];
 }
 
 export interface ProjectScanResultV3 {
@@ -71,3 +230,37 @@ export interface ProjectScanResultV3 {
   warnings: string[];
 }
 
+
+export interface PairingSignal {
+  kind: "name" | "env" | "git" | "siblings";
+  // Entre 0 et 1, avant pondération
+  score: number;
+  detail: string;
+}
+
+/**
+ * Association backend / frontend proposée par le scan des dépôts indépendants
+ */
+export interface PairingCandidate {
+  backend_path: string;
+  backend_name: string;
+  frontend_path: string;
+  frontend_name: string;
+  score: number;
+  signals: PairingSignal[];
+}
+
+/**
+ * Suggestions de scan_independent_repos
+ */
+export interface IndependentReposScan {
+  projects: ProjectV3[];
+  // Dépôts ignorés, avec la raison
+  warnings: string[];
+}
+
+// Association confirmée par l'utilisateur
+export interface RepoPair {
+  backend_path: string;
+  frontend_path: string;
+}